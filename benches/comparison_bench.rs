@@ -9,6 +9,12 @@ fn compare_berlin_256(c: &mut Criterion) {
     let mut group = c.benchmark_group("Comparison_Berlin_256");
     group.bench_function("A*", |b| b.iter(|| astar_context.astar()));
     group.bench_function("Fringe", |b| b.iter(|| fringe_context.fringe()));
+    group.bench_function("Bidirectional A*", |b| {
+        b.iter(|| astar_context.bidirectional_astar());
+    });
+    group.bench_function("Bidirectional Fringe", |b| {
+        b.iter(|| fringe_context.bidirectional_fringe());
+    });
 
     group.finish();
 }
@@ -19,6 +25,12 @@ fn compare_berlin_512(c: &mut Criterion) {
     let mut group = c.benchmark_group("Comparison_Berlin_512");
     group.bench_function("A*", |b| b.iter(|| astar_context.astar()));
     group.bench_function("Fringe", |b| b.iter(|| fringe_context.fringe()));
+    group.bench_function("Bidirectional A*", |b| {
+        b.iter(|| astar_context.bidirectional_astar());
+    });
+    group.bench_function("Bidirectional Fringe", |b| {
+        b.iter(|| fringe_context.bidirectional_fringe());
+    });
 
     group.finish();
 }
@@ -29,6 +41,12 @@ fn compare_berlin_1024(c: &mut Criterion) {
     let mut group = c.benchmark_group("Comparison_Berlin_1024");
     group.bench_function("A*", |b| b.iter(|| astar_context.astar()));
     group.bench_function("Fringe", |b| b.iter(|| fringe_context.fringe()));
+    group.bench_function("Bidirectional A*", |b| {
+        b.iter(|| astar_context.bidirectional_astar());
+    });
+    group.bench_function("Bidirectional Fringe", |b| {
+        b.iter(|| fringe_context.bidirectional_fringe());
+    });
 
     group.finish();
 }
@@ -0,0 +1,47 @@
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+mod common;
+
+fn greedy_berlin_256(c: &mut Criterion) {
+    let context = common::berlin256_context("greedy");
+
+    c.bench_function("greedy_Berlin_256", |b| b.iter(|| context.greedy()));
+}
+fn greedy_berlin_512(c: &mut Criterion) {
+    let context = common::berlin512_context("greedy");
+
+    c.bench_function("greedy_Berlin_512", |b| b.iter(|| context.greedy()));
+}
+fn greedy_berlin_1024(c: &mut Criterion) {
+    let context = common::berlin1024_context("greedy");
+
+    c.bench_function("greedy_Berlin_1024", |b| b.iter(|| context.greedy()));
+}
+
+fn beam_berlin_256(c: &mut Criterion) {
+    let context = common::berlin256_context("beam");
+
+    c.bench_function("beam_Berlin_256", |b| b.iter(|| context.beam()));
+}
+fn beam_berlin_512(c: &mut Criterion) {
+    let context = common::berlin512_context("beam");
+
+    c.bench_function("beam_Berlin_512", |b| b.iter(|| context.beam()));
+}
+fn beam_berlin_1024(c: &mut Criterion) {
+    let context = common::berlin1024_context("beam");
+
+    c.bench_function("beam_Berlin_1024", |b| b.iter(|| context.beam()));
+}
+
+criterion_group!(
+    greedy,
+    greedy_berlin_256,
+    greedy_berlin_512,
+    greedy_berlin_1024,
+    beam_berlin_256,
+    beam_berlin_512,
+    beam_berlin_1024
+);
+criterion_main!(greedy);
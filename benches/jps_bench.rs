@@ -0,0 +1,47 @@
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+mod common;
+
+fn jps_berlin_256(c: &mut Criterion) {
+    let context = common::berlin256_context("jps");
+
+    c.bench_function("jps_Berlin_256", |b| b.iter(|| context.jps()));
+}
+fn jps_berlin_512(c: &mut Criterion) {
+    let context = common::berlin512_context("jps");
+
+    c.bench_function("jps_Berlin_512", |b| b.iter(|| context.jps()));
+}
+fn jps_berlin_1024(c: &mut Criterion) {
+    let context = common::berlin1024_context("jps");
+
+    c.bench_function("jps_Berlin_1024", |b| b.iter(|| context.jps()));
+}
+
+fn jps_plus_berlin_256(c: &mut Criterion) {
+    let context = common::berlin256_context("jps-plus");
+
+    c.bench_function("jps_plus_Berlin_256", |b| b.iter(|| context.jps_plus()));
+}
+fn jps_plus_berlin_512(c: &mut Criterion) {
+    let context = common::berlin512_context("jps-plus");
+
+    c.bench_function("jps_plus_Berlin_512", |b| b.iter(|| context.jps_plus()));
+}
+fn jps_plus_berlin_1024(c: &mut Criterion) {
+    let context = common::berlin1024_context("jps-plus");
+
+    c.bench_function("jps_plus_Berlin_1024", |b| b.iter(|| context.jps_plus()));
+}
+
+criterion_group!(
+    jps,
+    jps_berlin_256,
+    jps_berlin_512,
+    jps_berlin_1024,
+    jps_plus_berlin_256,
+    jps_plus_berlin_512,
+    jps_plus_berlin_1024
+);
+criterion_main!(jps);
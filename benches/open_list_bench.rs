@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use fringe_vs_astar::algorithms::astar::open_list::{
+    BucketQueue, DaryHeap, Frontier, PairingHeap, RadixHeap,
+};
+use fringe_vs_astar::context::BareContext;
+
+mod common;
+
+fn open_lists(
+    c: &mut Criterion,
+    name: &str,
+    astar_context: &BareContext,
+    fringe_context: &BareContext,
+) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("Binary heap", |b| {
+        b.iter(|| astar_context.astar_with::<Frontier>());
+    });
+    group.bench_function("4-ary heap", |b| {
+        b.iter(|| astar_context.astar_with::<DaryHeap<4>>());
+    });
+    group.bench_function("Pairing heap", |b| {
+        b.iter(|| astar_context.astar_with::<PairingHeap>());
+    });
+    group.bench_function("Radix heap", |b| {
+        b.iter(|| astar_context.astar_with::<RadixHeap>());
+    });
+    group.bench_function("Bucket queue", |b| {
+        b.iter(|| astar_context.astar_with::<BucketQueue>());
+    });
+    group.bench_function("Fringe", |b| b.iter(|| fringe_context.fringe()));
+
+    group.finish();
+}
+
+fn open_lists_berlin_256(c: &mut Criterion) {
+    let fringe_context = common::berlin256_context("fringe");
+    let astar_context = common::berlin256_context("a-star");
+
+    open_lists(c, "Open_lists_Berlin_256", &astar_context, &fringe_context);
+}
+fn open_lists_berlin_512(c: &mut Criterion) {
+    let fringe_context = common::berlin512_context("fringe");
+    let astar_context = common::berlin512_context("a-star");
+
+    open_lists(c, "Open_lists_Berlin_512", &astar_context, &fringe_context);
+}
+fn open_lists_berlin_1024(c: &mut Criterion) {
+    let fringe_context = common::berlin1024_context("fringe");
+    let astar_context = common::berlin1024_context("a-star");
+
+    open_lists(c, "Open_lists_Berlin_1024", &astar_context, &fringe_context);
+}
+
+criterion_group!(
+    open_list,
+    open_lists_berlin_256,
+    open_lists_berlin_512,
+    open_lists_berlin_1024
+);
+criterion_main!(open_list);
@@ -0,0 +1,48 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use fringe_vs_astar::context::BareContext;
+use rayon::ThreadPoolBuilder;
+
+mod common;
+
+/// Serial Fringe as a baseline, then the parallel one with a growing number of threads
+fn speedup(c: &mut Criterion, name: &str, fringe: &BareContext, parallel: &BareContext) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("Fringe", |b| b.iter(|| fringe.fringe()));
+    for threads in [1, 2, 4, 8] {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("Parallel Fringe", threads),
+            &pool,
+            |b, pool| b.iter(|| parallel.parallel_fringe(pool)),
+        );
+    }
+
+    group.finish();
+}
+
+fn parallel_berlin_256(c: &mut Criterion) {
+    let fringe = common::berlin256_context("fringe");
+    let parallel = common::berlin256_context("parallel-fringe");
+    speedup(c, "Parallel_Berlin_256", &fringe, &parallel);
+}
+fn parallel_berlin_512(c: &mut Criterion) {
+    let fringe = common::berlin512_context("fringe");
+    let parallel = common::berlin512_context("parallel-fringe");
+    speedup(c, "Parallel_Berlin_512", &fringe, &parallel);
+}
+fn parallel_berlin_1024(c: &mut Criterion) {
+    let fringe = common::berlin1024_context("fringe");
+    let parallel = common::berlin1024_context("parallel-fringe");
+    speedup(c, "Parallel_Berlin_1024", &fringe, &parallel);
+}
+
+criterion_group!(
+    parallel,
+    parallel_berlin_256,
+    parallel_berlin_512,
+    parallel_berlin_1024
+);
+criterion_main!(parallel);
@@ -0,0 +1,67 @@
+use clap::Parser;
+use criterion::{criterion_group, criterion_main, Criterion};
+use fringe_vs_astar::cli::Cli;
+use fringe_vs_astar::context::{BareContext, Context};
+
+mod common;
+
+/// First problem of the scenario is a short path on a large map,
+/// so initializing buffers costs more than searching
+fn room1550_context(mode: &str) -> BareContext {
+    let arguments = ["", "-sss", "-n", "1", mode, "maps/room-1550-155.map"];
+    let cli = Cli::parse_from(arguments.iter());
+    Context::new(cli).unwrap().bare()
+}
+
+fn workspaces(
+    c: &mut Criterion,
+    name: &str,
+    astar_context: &BareContext,
+    fringe_context: &BareContext,
+) {
+    let mut group = c.benchmark_group(name);
+    group.bench_function("A*", |b| b.iter(|| astar_context.astar()));
+    group.bench_function("A* in workspace", |b| {
+        b.iter(|| astar_context.astar_in_workspace());
+    });
+    group.bench_function("Fringe", |b| b.iter(|| fringe_context.fringe()));
+    group.bench_function("Fringe in workspace", |b| {
+        b.iter(|| fringe_context.fringe_in_workspace());
+    });
+
+    group.finish();
+}
+
+fn workspaces_room_1550(c: &mut Criterion) {
+    let fringe_context = room1550_context("fringe");
+    let astar_context = room1550_context("a-star");
+
+    workspaces(c, "Workspaces_room_1550", &astar_context, &fringe_context);
+}
+fn workspaces_berlin_256(c: &mut Criterion) {
+    let fringe_context = common::berlin256_context("fringe");
+    let astar_context = common::berlin256_context("a-star");
+
+    workspaces(c, "Workspaces_Berlin_256", &astar_context, &fringe_context);
+}
+fn workspaces_berlin_512(c: &mut Criterion) {
+    let fringe_context = common::berlin512_context("fringe");
+    let astar_context = common::berlin512_context("a-star");
+
+    workspaces(c, "Workspaces_Berlin_512", &astar_context, &fringe_context);
+}
+fn workspaces_berlin_1024(c: &mut Criterion) {
+    let fringe_context = common::berlin1024_context("fringe");
+    let astar_context = common::berlin1024_context("a-star");
+
+    workspaces(c, "Workspaces_Berlin_1024", &astar_context, &fringe_context);
+}
+
+criterion_group!(
+    workspace,
+    workspaces_room_1550,
+    workspaces_berlin_256,
+    workspaces_berlin_512,
+    workspaces_berlin_1024
+);
+criterion_main!(workspace);
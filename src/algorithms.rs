@@ -4,25 +4,147 @@
 pub mod astar;
 pub use crate::algorithms::astar::AStar;
 
+/// Greedy best-first and beam search path finders
+pub mod greedy;
+pub use crate::algorithms::greedy::{BeamSearch, GreedySearch};
+
 /// Fringe search path finder
 pub mod fringesearch;
+pub use crate::algorithms::fringesearch::bidirectional::BidirectionalFringe;
+pub use crate::algorithms::fringesearch::parallel::ParallelFringe;
 pub use crate::algorithms::fringesearch::FringeSearch;
 
+/// Iterative deepening A* path finder
+pub mod ida;
+pub use crate::algorithms::ida::Ida;
+
+/// Jump Point Search path finder
+pub mod jps;
+pub use crate::algorithms::jps::Jps;
+
+/// JPS+ path finder and its precomputed jump table
+pub mod jpsplus;
+pub use crate::algorithms::jpsplus::JpsPlus;
+
+/// Bidirectional A* path finder
+pub mod bidirectional;
+pub use crate::algorithms::bidirectional::BidirectionalAStar;
+
+/// Focal search (A*ε) path finder
+pub mod focal;
+pub use crate::algorithms::focal::FocalSearch;
+
+/// Anytime Repairing A* path finder
+pub mod ara;
+pub use crate::algorithms::ara::Ara;
+
+/// Dijkstra path finder and distance fields
+pub mod dijkstra;
+pub use crate::algorithms::dijkstra::Dijkstra;
+
+/// Theta* and Lazy Theta* any-angle path finders
+pub mod theta;
+pub use crate::algorithms::theta::ThetaStar;
+
+/// D* Lite incremental path finder
+pub mod dstarlite;
+pub use crate::algorithms::dstarlite::DStarLite;
+
+/// HPA* hierarchical path finder and its abstract graph
+pub mod hpa;
+pub use crate::algorithms::hpa::Hpa;
+
+/// ALT heuristic: landmarks and their precomputed distances
+pub mod landmarks;
+pub use crate::algorithms::landmarks::Landmarks;
+
+/// Compressed path database and path extraction from it
+pub mod cpd;
+pub use crate::algorithms::cpd::Cpd;
+
+/// Conflict-Based Search for many agents and its space-time A*
+pub mod cbs;
+pub use crate::algorithms::cbs::Cbs;
+
+/// k shortest paths and alternative routes between the same start and goal
+pub mod alternatives;
+pub use crate::algorithms::alternatives::{Penalty, Yen};
+
+/// Search to the nearest of many goals and distances between sets of points
+pub mod multigoal;
+pub use crate::algorithms::multigoal::{DistanceMatrix, MultiGoal};
+
+/// Safe Interval Path Planning around moving obstacles
+pub mod sipp;
+pub use crate::algorithms::sipp::Sipp;
+
+/// Seeded pseudorandom numbers for landmarks and tie-breaking
+pub mod random;
+
+/// Ordering of nodes with equal estimates
+pub mod tie_break;
+pub use crate::algorithms::tie_break::TieBreak;
+
+/// Limits that stop searches early
+pub mod limits;
+pub use crate::algorithms::limits::{Abort, Limits};
+
+/// Buffers that are reused between searches
+pub mod workspace;
+pub use crate::algorithms::workspace::Workspace;
+
 /// Runner for search algorithms
 pub mod solver;
 pub use crate::algorithms::solver::*;
 
-use crate::{index_to_xy, Cost, Node, DIAGONAL_COST};
+use crate::{index_to_xy, xy_to_index, Cost, Node, DIAGONAL_COST};
+use std::sync::Arc;
 
 /// Enumerator for representing different stages of pathfinding.
-pub enum State {
-    Finished((Vec<Node>, Cost)),
+/// `Improved` carries a solution that is better than earlier ones and its suboptimality bound.
+/// `Aborted` tells which of the [`Limits`] stopped the search.
+/// Solution is a path and its length, except for searches that find something else, like [`Cbs`].
+pub enum State<S = (Vec<Node>, Cost)> {
+    Finished(S),
+    Improved(S, Cost),
     Processing(Node),
     Internal,
     NotFound,
+    Aborted(Abort),
+}
+
+impl<S> State<S> {
+    /// Check if the search has ended: a path was found, no path can be found or a limit was reached
+    #[must_use]
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            State::Finished(_) | State::NotFound | State::Aborted(_)
+        )
+    }
+
+    /// Solution if the search finished
+    #[must_use]
+    pub fn solution(self) -> Option<S> {
+        match self {
+            State::Finished(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+/// Advance a search with `progress` until it ends and return the final [`State`]
+pub(crate) fn until_final<S>(mut progress: impl FnMut() -> State<S>) -> State<S> {
+    loop {
+        let state = progress();
+        if state.is_final() {
+            return state;
+        }
+    }
 }
 
 /// Octile distance calculator for a set goal node.
+/// With [`Landmarks`] the larger of octile distance and the ALT lower bound is used.
 /// ```
 /// // ... | 012
 /// // .g. | 345
@@ -38,6 +160,7 @@ pub enum State {
 pub struct Heuristic {
     goal: (usize, usize),
     width: usize,
+    landmarks: Option<(Arc<Landmarks>, Vec<Cost>)>,
 }
 
 impl Heuristic {
@@ -47,9 +170,19 @@ impl Heuristic {
         Heuristic {
             goal: index_to_xy(goal, width),
             width,
+            landmarks: None,
         }
     }
 
+    /// Tighten the estimates with distances from landmarks
+    #[must_use]
+    pub fn with_landmarks(mut self, landmarks: Arc<Landmarks>) -> Self {
+        let goal = xy_to_index(self.goal.0, self.goal.1, self.width);
+        let at_goal = landmarks.distances(goal).to_vec();
+        self.landmarks = Some((landmarks, at_goal));
+        self
+    }
+
     /// Octile distance between two points
     #[must_use]
     pub fn calc(&self, node: Node) -> Cost {
@@ -58,10 +191,17 @@ impl Heuristic {
         let y_distance: Cost = ((start.1 as Cost) - (self.goal.1 as Cost)).abs();
         let distance: Cost = (x_distance - y_distance).abs();
 
-        if x_distance > y_distance {
+        let octile = if x_distance > y_distance {
             distance + DIAGONAL_COST * y_distance
         } else {
             distance + DIAGONAL_COST * x_distance
+        };
+
+        match &self.landmarks {
+            Some((landmarks, at_goal)) => {
+                octile.max(landmarks::bound(landmarks.distances(node), at_goal))
+            }
+            None => octile,
         }
     }
 }
@@ -0,0 +1,284 @@
+use super::Heuristic;
+use crate::algorithms::astar::weighted_cell::WeightedCell;
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// One of many paths between the same start and goal
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub path: Path,
+    pub cost: Cost,
+    /// Largest share of the length of the path that is also on a single earlier route, from 0 to 1
+    pub overlap: Cost,
+}
+
+/// Yen's algorithm for the k shortest loopless paths.
+///
+/// Paths are returned cheapest first by iterating.
+/// Every next path deviates from an earlier one at some spur node: the part before it is kept,
+/// nodes of that part are removed, and so are the edges that earlier paths with the same root take from the spur.
+/// Shortest path from the spur to the goal completes a candidate, and the cheapest candidate is the next path.
+///
+/// Grids have many paths of equal length, so consecutive paths often differ by a single cell.
+/// [`Penalty`] finds routes that are more different from each other.
+pub struct Yen<'a> {
+    found: Vec<(Path, Cost)>,
+    candidates: Vec<(Path, Cost)>,
+    heap: BinaryHeap<WeightedCell>,
+    seen: HashSet<Path>,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> Yen<'a> {
+    /// Initialize the search with a start, goal and a graph to be acted upon.
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        Yen {
+            found: vec![],
+            candidates: vec![],
+            heap: BinaryHeap::new(),
+            seen: HashSet::new(),
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Find the k shortest paths with their overlaps
+    #[must_use]
+    pub fn routes(self, k: usize) -> Vec<Route> {
+        let graph = self.graph;
+        routes(self.take(k).map(|(path, _)| path).collect(), graph)
+    }
+
+    /// Add candidates that deviate from the latest path
+    fn add_candidates(&mut self) {
+        let last = self.found[self.found.len() - 1].0.clone();
+        for i in 0..last.len() - 1 {
+            let (root, spur) = (&last[..i], last[i]);
+            let removed_nodes: HashSet<Node> = root.iter().copied().collect();
+            let removed_edges: HashSet<(Node, Node)> = self
+                .found
+                .iter()
+                .filter(|(path, _)| path.len() > i + 1 && path[..i] == *root && path[i] == spur)
+                .map(|(path, _)| (path[i], path[i + 1]))
+                .collect();
+
+            let spur_path = search(spur, self.goal, self.graph, |from, to, weight| {
+                (!removed_nodes.contains(&to) && !removed_edges.contains(&(from, to)))
+                    .then_some(weight)
+            });
+            let Some((spur_path, spur_cost)) = spur_path else {
+                continue;
+            };
+
+            let mut path = root.to_vec();
+            path.extend(spur_path);
+            if self.seen.insert(path.clone()) {
+                let cost = path_cost(&path[..=i], self.graph) + spur_cost;
+                self.candidates.push((path, cost));
+                self.heap
+                    .push(WeightedCell::new(self.candidates.len() as Node - 1, cost));
+            }
+        }
+    }
+}
+
+impl Iterator for Yen<'_> {
+    type Item = (Path, Cost);
+
+    /// Find the next shortest path
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.found.is_empty() {
+            let shortest = search(self.start, self.goal, self.graph, |_, _, w| Some(w))?;
+            self.seen.insert(shortest.0.clone());
+            self.found.push(shortest.clone());
+            return Some(shortest);
+        }
+
+        self.add_candidates();
+        let WeightedCell { node: index, .. } = self.heap.pop()?;
+        let next = std::mem::take(&mut self.candidates[index as usize]);
+        self.found.push(next.clone());
+        Some(next)
+    }
+}
+
+/// Alternative routes by penalizing used edges.
+///
+/// After a shortest path is found, weights of its edges are multiplied by the penalty,
+/// and the next search prefers edges that have not been used yet.
+/// Routes that have been found already are skipped. Costs of routes are their real lengths.
+pub struct Penalty<'a> {
+    penalty: Cost,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> Penalty<'a> {
+    /// Initialize with a start, goal, graph and the factor that used edges are multiplied by
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, penalty: Cost) -> Self {
+        Penalty {
+            penalty,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Find up to `k` different routes with their overlaps.
+    /// Search gives up after `4 * k` rounds, if penalties keep leading to routes that have been found.
+    #[must_use]
+    pub fn routes(&self, k: usize) -> Vec<Route> {
+        let mut factors: HashMap<(Node, Node), Cost> = HashMap::new();
+        let mut paths: Vec<Path> = vec![];
+
+        for _ in 0..4 * k {
+            if paths.len() == k {
+                break;
+            }
+            let Some((path, _)) = search(self.start, self.goal, self.graph, |from, to, w| {
+                Some(w * factors.get(&(from, to)).unwrap_or(&1.0))
+            }) else {
+                break;
+            };
+
+            for pair in path.windows(2) {
+                for edge in [(pair[0], pair[1]), (pair[1], pair[0])] {
+                    *factors.entry(edge).or_insert(1.0) *= self.penalty;
+                }
+            }
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        routes(paths, self.graph)
+    }
+}
+
+/// Share of the length of `path` that is on `other`, from 0 to 1.
+/// Edges are shared in either direction.
+/// ```
+/// # use fringe_vs_astar::algorithms::alternatives::overlap;
+/// # use fringe_vs_astar::structures::{Graph, Map};
+/// # use std::path::PathBuf;
+/// // ... | 012
+/// // .@. | 345
+/// // ... | 678
+/// let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+///
+/// assert_eq!(overlap(&[0, 1, 2, 5], &[2, 1, 0], &graph), 2.0 / 3.0);
+/// assert_eq!(overlap(&[0, 1, 2], &[0, 3, 6], &graph), 0.0);
+/// ```
+#[must_use]
+pub fn overlap(path: &[Node], other: &[Node], graph: &Graph) -> Cost {
+    let edges: HashSet<(Node, Node)> = other
+        .windows(2)
+        .flat_map(|pair| [(pair[0], pair[1]), (pair[1], pair[0])])
+        .collect();
+    let shared: Cost = path
+        .windows(2)
+        .filter(|pair| edges.contains(&(pair[0], pair[1])))
+        .map(|pair| edge_cost(pair[0], pair[1], graph))
+        .sum();
+    let length = path_cost(path, graph);
+    if length > 0.0 {
+        shared / length
+    } else {
+        0.0
+    }
+}
+
+/// Draw routes in different styles. Earlier routes are drawn on top of later ones.
+#[must_use]
+pub fn add_routes_to_printable(routes: &[Route], mut print: Printable) -> Printable {
+    for (i, route) in routes.iter().enumerate().rev() {
+        for node in &route.path {
+            print.add_route(*node, i);
+        }
+    }
+    for (i, route) in routes.iter().enumerate() {
+        print.add_header(
+            format!("Route {i}"),
+            format!("{} ({:.0} % shared)", route.cost, 100.0 * route.overlap),
+        );
+    }
+    print
+}
+
+/// Measure costs of paths and their overlaps with the earlier paths
+fn routes(paths: Vec<Path>, graph: &Graph) -> Vec<Route> {
+    let mut routes: Vec<Route> = vec![];
+    for path in paths {
+        let shared = routes
+            .iter()
+            .map(|r| overlap(&path, &r.path, graph))
+            .fold(0.0, Cost::max);
+        routes.push(Route {
+            cost: path_cost(&path, graph),
+            path,
+            overlap: shared,
+        });
+    }
+    routes
+}
+
+/// Sum of edge weights along a path
+fn path_cost(path: &[Node], graph: &Graph) -> Cost {
+    path.windows(2)
+        .map(|pair| edge_cost(pair[0], pair[1], graph))
+        .sum()
+}
+
+/// Weight of an edge, or 0 if the nodes are not neighbors
+fn edge_cost(from: Node, to: Node, graph: &Graph) -> Cost {
+    graph
+        .neighbors(from)
+        .find(|(n, _)| *n == to)
+        .map_or(0.0, |(_, w)| *w)
+}
+
+/// A* where `weight` can change the weight of an edge or remove it with `None`.
+/// Weights can only grow, so octile distance is still admissible.
+fn search(
+    start: Node,
+    goal: Node,
+    graph: &Graph,
+    weight: impl Fn(Node, Node, Cost) -> Option<Cost>,
+) -> Option<(Path, Cost)> {
+    let heuristic = Heuristic::new(goal, graph.get_width());
+    let mut costs: HashMap<Node, (Cost, Node)> = HashMap::from([(start, (0.0, start))]);
+    let mut heap = BinaryHeap::from([WeightedCell::new(start, heuristic.calc(start))]);
+
+    while let Some(WeightedCell { node, weight: f }) = heap.pop() {
+        let cost = costs[&node].0;
+        if f > cost + heuristic.calc(node) {
+            continue;
+        }
+        if node == goal {
+            let mut path = vec![goal];
+            while path[path.len() - 1] != start {
+                path.push(costs[&path[path.len() - 1]].1);
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+        for (child, w) in graph.neighbors(node) {
+            let Some(w) = weight(node, *child, *w) else {
+                continue;
+            };
+            if costs.get(child).is_some_and(|(c, _)| *c <= cost + w) {
+                continue;
+            }
+            costs.insert(*child, (cost + w, node));
+            heap.push(WeightedCell::new(*child, cost + w + heuristic.calc(*child)));
+        }
+    }
+    None
+}
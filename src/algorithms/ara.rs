@@ -0,0 +1,323 @@
+use super::astar::weighted_cell::WeightedCell;
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::collections::BinaryHeap;
+
+/// How much weight is decreased after each improved solution
+pub const WEIGHT_STEP: f32 = 0.5;
+
+/// ARA* cache value. `closed` and `inconsistent` refer to the current iteration.
+#[derive(Clone, Copy)]
+struct Value {
+    parent: Node,
+    cost: Cost,
+    heuristic: Cost,
+    open: bool,
+    closed: bool,
+    inconsistent: bool,
+}
+
+impl Default for Value {
+    fn default() -> Self {
+        Value {
+            parent: 0,
+            cost: f32::INFINITY,
+            heuristic: f32::MAX,
+            open: false,
+            closed: false,
+            inconsistent: false,
+        }
+    }
+}
+
+/// Anytime Repairing A* pathfinder.
+///
+/// Runs weighted A* with a decreasing weight, starting from the given one.
+/// Costs found by earlier iterations are kept, so each iteration repairs the previous search
+/// instead of starting over. Nodes improved after they have been expanded are not reopened
+/// during an iteration, but kept as inconsistent and moved to the open list for the next one.
+///
+/// Every better solution is reported with its suboptimality bound,
+/// either by [`Ara::progress`] as [`State::Improved`] or by iterating the solver.
+pub struct Ara<'a> {
+    heap: BinaryHeap<WeightedCell>,
+    values: Vec<Value>,
+    inconsistent: Vec<Node>,
+    heuristic: Heuristic,
+    pub weight: f32,
+    pub best: Cost,
+    pub iteration: u32,
+    limits: Limits,
+    /// Number of expanded nodes over every iteration
+    pub expansions: usize,
+    done: bool,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> Ara<'a> {
+    /// Create solver of a problem for a graph with the initial `weight`
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, weight: f32) -> Self {
+        let mut ara = Ara {
+            heap: BinaryHeap::with_capacity(graph.map_size()),
+            values: vec![Value::default(); graph.map_size()],
+            inconsistent: vec![],
+            heuristic: Heuristic::new(goal, graph.get_width()),
+            weight: weight.max(1.0),
+            best: f32::INFINITY,
+            iteration: 1,
+            limits: Limits::default(),
+            expansions: 0,
+            done: false,
+            start,
+            goal,
+            graph,
+        };
+        ara.values[start as usize].parent = start;
+        ara.values[start as usize].cost = 0.0;
+        ara.push(start);
+
+        ara
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem. Runs until the solution is proven optimal.
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`].
+    /// Solutions improved before an abort are only reported by [`Ara::progress`].
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step.
+    ///
+    /// When an iteration ends with a better solution, it is returned as [`State::Improved`]
+    /// and the next iteration is started with a smaller weight.
+    /// Iteration that does not improve the solution returns [`State::Internal`].
+    /// When the bound reaches 1, the solution is optimal and [`State::Finished`] is returned.
+    pub fn progress(&mut self) -> State {
+        if self.done {
+            return State::NotFound;
+        }
+        if self.start == self.goal {
+            self.done = true;
+            return State::Finished((vec![self.start], 0.0));
+        }
+
+        let Some(f_min) = self.min_estimate() else {
+            if self.values[self.goal as usize].cost == f32::INFINITY {
+                self.done = true;
+                return State::NotFound;
+            }
+            return self.end_iteration();
+        };
+        if self.estimate(self.goal) <= f_min {
+            return self.end_iteration();
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let node = self.heap.pop().expect("Heap can't be empty").node;
+        self.values[node as usize].open = false;
+        self.values[node as usize].closed = true;
+
+        let current_cost = self.values[node as usize].cost;
+        for (child, w) in self.graph.neighbors(node) {
+            let cost = current_cost + w;
+            let value = &mut self.values[*child as usize];
+            if cost >= value.cost {
+                continue;
+            }
+            value.cost = cost;
+            value.parent = node;
+
+            if !value.closed {
+                self.push(*child);
+            } else if !value.inconsistent {
+                value.inconsistent = true;
+                self.inconsistent.push(*child);
+            }
+        }
+
+        State::Processing(node)
+    }
+
+    /// Report the solution of the finished iteration and prepare the next one
+    fn end_iteration(&mut self) -> State {
+        let bound = self.bound();
+        let cost = self.values[self.goal as usize].cost;
+        let improved = cost < self.best;
+        self.best = self.best.min(cost);
+
+        if bound <= 1.0 || self.weight <= 1.0 {
+            self.done = true;
+            return State::Finished(self.construct_path());
+        }
+
+        self.weight = (self.weight - WEIGHT_STEP).max(1.0);
+        self.iteration += 1;
+
+        // Inconsistent nodes join the open list and every open node gets the new priority
+        let mut open: Vec<Node> = self.heap.drain().map(|c| c.node).collect();
+        open.append(&mut self.inconsistent);
+        for value in &mut self.values {
+            value.open = false;
+            value.closed = false;
+            value.inconsistent = false;
+        }
+        for node in open {
+            if !self.values[node as usize].open {
+                self.push(node);
+            }
+        }
+
+        if improved {
+            State::Improved(self.construct_path(), bound)
+        } else {
+            State::Internal
+        }
+    }
+
+    /// Suboptimality bound of the current solution.
+    /// Cost of the solution divided by the smallest unweighted estimate of nodes that may still improve it.
+    fn bound(&self) -> Cost {
+        let cost = self.values[self.goal as usize].cost;
+        let lowest = self
+            .heap
+            .iter()
+            .map(|c| c.node)
+            .filter(|n| self.values[*n as usize].open)
+            .chain(self.inconsistent.iter().copied())
+            .map(|n| self.values[n as usize].cost + self.values[n as usize].heuristic)
+            .fold(f32::INFINITY, f32::min);
+
+        if lowest == f32::INFINITY {
+            1.0
+        } else {
+            self.weight.min(cost / lowest)
+        }
+    }
+
+    /// Smallest weighted estimate of the open list. Outdated entries are discarded from the top.
+    fn min_estimate(&mut self) -> Option<Cost> {
+        while let Some(cell) = self.heap.peek() {
+            let node = cell.node;
+            if self.values[node as usize].open && cell.weight == self.estimate(node) {
+                return Some(cell.weight);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    /// Add a [`Node`] to the open list with its current weighted estimate
+    fn push(&mut self, node: Node) {
+        if self.values[node as usize].heuristic == f32::MAX {
+            self.values[node as usize].heuristic = self.heuristic.calc(node);
+        }
+        self.values[node as usize].open = true;
+        self.heap.push(WeightedCell::new(node, self.estimate(node)));
+    }
+
+    /// Weighted estimate f = g + w·h of a [`Node`]
+    fn estimate(&self, node: Node) -> Cost {
+        let value = &self.values[node as usize];
+        if value.heuristic == f32::MAX {
+            value.cost + self.weight * self.heuristic.calc(node)
+        } else {
+            value.cost + self.weight * value.heuristic
+        }
+    }
+
+    /// Reconstruct path to the goal through current parents
+    fn construct_path(&self) -> (Path, Cost) {
+        let mut path = vec![self.goal];
+        while path[path.len() - 1] != self.start {
+            path.push(self.values[path[path.len() - 1] as usize].parent);
+        }
+        path.reverse();
+
+        (path, self.values[self.goal as usize].cost)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        let mut open = 0;
+        for (i, value) in self.values.iter().enumerate() {
+            let node = i.try_into().unwrap();
+            if value.inconsistent {
+                print.add_inlater(node);
+            } else if value.open {
+                open += 1;
+                print.add_infrontier(node);
+            } else if value.closed {
+                print.add_inclosed(node);
+            }
+        }
+
+        print.add_header("Iteration", self.iteration);
+        print.add_header("Weight", self.weight);
+        print.add_header("|Open|", open);
+        print.add_header("|Incons|", self.inconsistent.len());
+        print.add_header("Best", self.best);
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.values[node as usize].cost
+    }
+
+    /// Get current weighted estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.estimate(node)
+    }
+
+    /// Get size of the open list, including outdated entries
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+/// Successive solutions, each better than the previous, with their suboptimality bounds.
+/// Last solution is optimal and has bound 1.
+impl Iterator for Ara<'_> {
+    type Item = ((Path, Cost), Cost);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.progress() {
+                State::Improved(solution, bound) => return Some((solution, bound)),
+                State::Finished(solution) => return Some((solution, 1.0)),
+                State::NotFound | State::Aborted(_) => return None,
+                _ => {}
+            }
+        }
+    }
+}
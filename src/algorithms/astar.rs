@@ -1,21 +1,30 @@
-use super::{Heuristic, State};
+use super::tie_break::{TieBreak, Ties};
+use super::{until_final, Heuristic, Landmarks, Limits, State, Workspace};
 use crate::printable::Printable;
 use crate::structures::Graph;
 use crate::Node;
+use std::sync::Arc;
 
 use self::cache::Cache;
 use self::frontier::Frontier;
+use self::open_list::{OpenList, TieBreaking};
 use self::weighted_cell::WeightedCell;
 
 pub mod cache;
 pub mod frontier;
+pub mod open_list;
 pub mod weighted_cell;
 
 /// A* pathfinder
-/// Main datastructures are [`Frontier`] and [`Cache`].
-pub struct AStar<'a> {
-    frontier: Frontier,
+/// Main datastructures are the open list, [`Frontier`] by default, and [`Cache`].
+/// Other open lists are used with [`AStar::with_open_list`].
+/// Heuristic can be inflated with [`AStar::with_weight`] for bounded-suboptimal weighted A*,
+/// and tightened with [`AStar::with_landmarks`].
+pub struct AStar<'a, O: OpenList = Frontier> {
+    frontier: O,
     cache: Cache,
+    limits: Limits,
+    pub expansions: usize,
     start: Node,
     goal: Node,
     graph: &'a Graph,
@@ -25,39 +34,141 @@ impl<'a> AStar<'a> {
     /// Create solver of a problem for a graph
     #[must_use]
     pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        Self::with_open_list(start, goal, graph)
+    }
+
+    /// Create solver like [`AStar::new`], but reuse the buffers of `workspace`.
+    /// Solve with [`AStar::solve_in`] to give them back.
+    #[must_use]
+    pub fn in_workspace(
+        start: Node,
+        goal: Node,
+        graph: &'a Graph,
+        workspace: &mut Workspace,
+    ) -> Self {
+        Self::with_open_list_in(start, goal, graph, workspace)
+    }
+}
+
+impl<'a, O: TieBreaking> AStar<'a, O> {
+    /// Order nodes with equal estimates by `tie_break`. `seed` is used by random tie-breaking.
+    /// Only open lists that break ties have this, like [`TiedFrontier`](open_list::TiedFrontier).
+    /// ```
+    /// # use fringe_vs_astar::algorithms::{AStar, TieBreak};
+    /// # use fringe_vs_astar::algorithms::astar::open_list::TiedFrontier;
+    /// # use fringe_vs_astar::structures::{Graph, Map};
+    /// # use std::path::PathBuf;
+    /// let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    /// let astar = AStar::<TiedFrontier>::with_open_list(0, 8, &graph).with_tie_break(TieBreak::HighG, 0);
+    /// let (_, length) = astar.solve().unwrap();
+    /// assert_eq!(length, 4.0);
+    /// ```
+    #[must_use]
+    pub fn with_tie_break(mut self, tie_break: TieBreak, seed: u64) -> Self {
+        self.frontier.break_ties(Ties::new(tie_break, seed));
+        self
+    }
+}
+
+impl<'a, O: OpenList> AStar<'a, O> {
+    /// Create solver of a problem for a graph, with the open list `O`
+    /// ```
+    /// # use fringe_vs_astar::algorithms::AStar;
+    /// # use fringe_vs_astar::algorithms::astar::open_list::PairingHeap;
+    /// # use fringe_vs_astar::structures::{Graph, Map};
+    /// # use std::path::PathBuf;
+    /// let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    /// let astar = AStar::<PairingHeap>::with_open_list(0, 8, &graph);
+    /// let (path, length) = astar.solve().unwrap();
+    /// assert_eq!((path.len(), length), (5, 4.0));
+    /// ```
+    #[must_use]
+    pub fn with_open_list(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        Self::with_open_list_in(start, goal, graph, &mut Workspace::default())
+    }
+
+    /// Create solver with the open list `O`, reusing the cache and open list buffers of `workspace`
+    #[must_use]
+    pub fn with_open_list_in(
+        start: Node,
+        goal: Node,
+        graph: &'a Graph,
+        workspace: &mut Workspace,
+    ) -> Self {
         let size = graph.get_width() * graph.get_height();
-        let frontier = Frontier::new(start, size);
+        let frontier = O::in_workspace(start, size, workspace);
 
         let heuristic = Heuristic::new(goal, graph.get_width());
-        let cache = Cache::new(start, heuristic, size);
+        let cache = Cache::from_buffer(start, heuristic, workspace.take_astar(size));
 
         AStar {
             frontier,
             cache,
+            limits: Limits::default(),
+            expansions: 0,
             start,
             goal,
             graph,
         }
     }
 
+    /// Inflate the heuristic by `weight`, so that nodes are ordered by f = g + w·h.
+    /// Found path is at most `weight` times longer than optimal.
+    ///
+    /// Weight above 1 must not be used with [`RadixHeap`](open_list::RadixHeap).
+    /// Weighted estimates can decrease along a path, and the radix heap lifts them to the last popped one,
+    /// so the bound would not hold.
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.cache = self.cache.with_weight(weight);
+        self
+    }
+
+    /// Use the ALT heuristic with precomputed landmarks
+    #[must_use]
+    pub fn with_landmarks(mut self, landmarks: Arc<Landmarks>) -> Self {
+        let heuristic = Heuristic::new(self.goal, self.graph.get_width()).with_landmarks(landmarks);
+        self.cache = self.cache.with_heuristic(heuristic);
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Try to solve the problem
     #[must_use]
     pub fn solve(mut self) -> Option<(Vec<Node>, f32)> {
-        while let Some(node) = self.frontier.pop() {
-            if node == self.goal {
-                return Some(self.construct_path());
-            }
+        self.run().solution()
+    }
 
-            let current_cost = self.cache.get_cost(node);
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        self.run()
+    }
 
-            for (child, w1) in self.graph.neighbors(node) {
-                if let Some((node, weight)) = self.cache.check(*child, node, current_cost + w1) {
-                    self.frontier.push(node, weight);
-                }
-            }
-        }
-        // If frontier is empty, no path can be found
-        None
+    /// Solve like [`AStar::solve_limited`] and give the cache and open list back to `workspace`
+    #[must_use]
+    pub fn solve_in(mut self, workspace: &mut Workspace) -> State {
+        let state = self.run();
+        self.into_workspace(workspace);
+        state
+    }
+
+    /// Give the cache and open list back to `workspace`
+    pub fn into_workspace(self, workspace: &mut Workspace) {
+        workspace.astar = self.cache.into_buffer();
+        self.frontier.into_workspace(workspace);
+    }
+
+    /// Expand nodes until the goal is found, the open list is empty or a limit is reached.
+    /// Unlike the solve methods this keeps the search, so that its expansions can be read afterwards.
+    pub fn run(&mut self) -> State {
+        until_final(|| self.progress())
     }
 
     /// Advance solving by one step
@@ -66,12 +177,17 @@ impl<'a> AStar<'a> {
             if node == self.goal {
                 return State::Finished(self.construct_path());
             }
+            if let Some(reason) = self.limits.check(self.expansions) {
+                return State::Aborted(reason);
+            }
 
             let current_cost = self.cache.get_cost(node);
+            self.expansions += 1;
 
             for (child, w1) in self.graph.neighbors(node) {
-                if let Some((node, weight)) = self.cache.check(*child, node, current_cost + w1) {
-                    self.frontier.push(node, weight);
+                let cost = current_cost + w1;
+                if let Some((node, weight)) = self.cache.check(*child, node, cost) {
+                    self.frontier.push_found(node, weight, cost);
                 }
             }
             State::Processing(node)
@@ -110,7 +226,10 @@ impl<'a> AStar<'a> {
                 print.add_inclosed(i.try_into().unwrap());
             });
 
-        self.frontier.iter().for_each(|n| print.add_infrontier(*n));
+        self.frontier
+            .entries()
+            .iter()
+            .for_each(|(n, _)| print.add_infrontier(*n));
         let top3 = self.frontier.top3();
         if let Some(first) = top3.0 {
             print.add_first(first);
@@ -123,6 +242,7 @@ impl<'a> AStar<'a> {
         }
         print.add_header("|Open|", self.frontier.size());
         print.add_header("|Closed|", closed - self.frontier.size());
+        print.add_header("Expanded", self.expansions);
 
         print.add_start(self.start);
         print.add_goal(self.goal);
@@ -142,7 +262,7 @@ impl<'a> AStar<'a> {
         self.cache.get_estimate(node)
     }
 
-    /// Get size of [`Frontier`]
+    /// Get size of the open list
     #[must_use]
     pub fn size(&self) -> usize {
         self.frontier.size()
@@ -1,4 +1,5 @@
 use super::Heuristic;
+use crate::algorithms::workspace::Buffer;
 use crate::Node;
 use std::ops::{Index, IndexMut};
 
@@ -23,26 +24,73 @@ impl Default for Value {
 }
 
 /// A* cache, can be indexed as `cache[node]`.
+/// Heuristic is multiplied by `weight` when estimates are calculated.
+/// Values are kept in a [`Buffer`], which can be reused by the next search.
 pub struct Cache {
-    cache: Vec<Value>,
+    cache: Buffer<Value>,
     heuristic: Heuristic,
+    weight: f32,
+    start: Node,
 }
 
 impl Cache {
     /// Initialize with start
     #[must_use]
     pub fn new(start: Node, heuristic: Heuristic, size: usize) -> Self {
-        let mut cache = vec![Value::default(); size];
-        cache[start as usize].parent = start;
-        cache[start as usize].heuristic = heuristic.calc(start);
+        Self::from_buffer(start, heuristic, Buffer::new(size))
+    }
+
+    /// Initialize with start, reusing a buffer of an earlier search
+    #[must_use]
+    pub fn from_buffer(start: Node, heuristic: Heuristic, mut cache: Buffer<Value>) -> Self {
+        cache.reset();
+        let value = cache.first_mut(start as usize);
+        value.parent = start;
+        value.heuristic = heuristic.calc(start);
+        value.estimate = value.heuristic;
+
+        Self {
+            cache,
+            heuristic,
+            weight: 1.0,
+            start,
+        }
+    }
+
+    /// Give the buffer back for the next search
+    #[must_use]
+    pub fn into_buffer(self) -> Buffer<Value> {
+        self.cache
+    }
+
+    /// Inflate the heuristic by `weight`, so that estimate is f = g + w·h.
+    /// Found paths are at most `weight` times longer than optimal.
+    /// Meant to be set before the search, when start is the only reached node, so only its estimate is recalculated.
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        let start = self.cache.get_mut(self.start as usize);
+        start.estimate = start.cost + weight * start.heuristic;
+        self
+    }
 
-        Self { cache, heuristic }
+    /// Replace the heuristic.
+    /// Like [`Cache::with_weight`], only the estimate of start is recalculated.
+    #[must_use]
+    pub fn with_heuristic(mut self, heuristic: Heuristic) -> Self {
+        let to_goal = heuristic.calc(self.start);
+        self.heuristic = heuristic;
+        let start = self.cache.get_mut(self.start as usize);
+        start.heuristic = to_goal;
+        start.estimate = start.cost + self.weight * to_goal;
+        self
     }
 
     /// Get heuristic value from cache or calculate it
     pub fn get_heuristic(&mut self, node: Node) -> f32 {
+        // A node without a heuristic has not been written since the last reset
         if self[node].heuristic == f32::MAX {
-            self[node].heuristic = self.heuristic.calc(node);
+            self.cache.first_mut(node as usize).heuristic = self.heuristic.calc(node);
         }
         self[node].heuristic
     }
@@ -56,7 +104,7 @@ impl Cache {
     /// Check if a node should be processed. Updates values as needed
     pub fn check(&mut self, node: Node, parent: Node, new_cost: f32) -> Option<(Node, f32)> {
         let to_goal = self.get_heuristic(node);
-        let estimate = new_cost + to_goal;
+        let estimate = new_cost + self.weight * to_goal;
 
         if estimate < self[node].estimate {
             self[node].parent = parent;
@@ -68,6 +116,12 @@ impl Cache {
         }
     }
 
+    /// Check if a [`Node`] has been reached
+    #[must_use]
+    pub fn is_reached(&self, node: Node) -> bool {
+        self[node].estimate < f32::MAX
+    }
+
     /// Ergonomy iterator for own cache
     pub fn iter(&self) -> impl Iterator<Item = &Value> {
         self.cache.iter()
@@ -84,12 +138,12 @@ impl Index<Node> for Cache {
     type Output = Value;
 
     fn index(&self, index: Node) -> &Self::Output {
-        &self.cache[index as usize]
+        self.cache.get(index as usize)
     }
 }
 
 impl IndexMut<Node> for Cache {
     fn index_mut(&mut self, index: Node) -> &mut Self::Output {
-        &mut self.cache[index as usize]
+        self.cache.get_mut(index as usize)
     }
 }
@@ -1,3 +1,4 @@
+use super::open_list::OpenList;
 use super::WeightedCell;
 use crate::Node;
 use std::collections::BinaryHeap;
@@ -31,6 +32,12 @@ impl Frontier {
         }
     }
 
+    /// Node with the highest priority and its weight, without removing it
+    #[must_use]
+    pub fn peek(&self) -> Option<(Node, f32)> {
+        self.heap.peek().map(|w| (w.node, w.weight))
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &Node> {
         self.heap.iter().map(|w| &w.node)
     }
@@ -58,4 +65,35 @@ impl Frontier {
     pub fn size(&self) -> usize {
         self.heap.len()
     }
+
+    /// Remove every node
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl OpenList for Frontier {
+    fn new(start: Node, size: usize) -> Self {
+        Frontier::new(start, size)
+    }
+
+    fn push(&mut self, node: Node, estimate: f32) {
+        Frontier::push(self, node, estimate);
+    }
+
+    fn pop(&mut self) -> Option<Node> {
+        Frontier::pop(self)
+    }
+
+    fn size(&self) -> usize {
+        Frontier::size(self)
+    }
+
+    fn entries(&self) -> Vec<(Node, f32)> {
+        self.heap.iter().map(|w| (w.node, w.weight)).collect()
+    }
+
+    fn top3(&self) -> (Option<Node>, Option<Node>, Option<Node>) {
+        Frontier::top3(self)
+    }
 }
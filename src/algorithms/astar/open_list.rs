@@ -0,0 +1,94 @@
+use crate::algorithms::tie_break::Ties;
+use crate::algorithms::Workspace;
+use crate::{Cost, Node};
+use clap::ValueEnum;
+
+pub use self::buckets::BucketQueue;
+pub use self::dary::DaryHeap;
+pub use self::pairing::PairingHeap;
+pub use self::radix::RadixHeap;
+pub use self::tied::TiedFrontier;
+pub use super::frontier::Frontier;
+
+pub mod buckets;
+pub mod dary;
+pub mod pairing;
+pub mod radix;
+pub mod tied;
+
+/// Open list of [`AStar`](super::AStar): nodes ordered by their estimate, smallest first.
+///
+/// Pushing a node that is already in the list either lowers its estimate
+/// or adds a duplicate that is popped later, depending on the implementation.
+/// A* handles both, because expanding an outdated duplicate finds nothing to improve.
+pub trait OpenList {
+    /// Create an open list for a graph of `size` nodes and initialize it with starting node
+    fn new(start: Node, size: usize) -> Self;
+
+    /// Create an open list like [`OpenList::new`], reusing buffers of `workspace` if it keeps any
+    fn in_workspace(start: Node, size: usize, _workspace: &mut Workspace) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(start, size)
+    }
+
+    /// Give buffers back to `workspace` for the next search
+    fn into_workspace(self, _workspace: &mut Workspace)
+    where
+        Self: Sized,
+    {
+    }
+
+    /// Add a node with an estimate
+    fn push(&mut self, node: Node, estimate: f32);
+
+    /// Add a node that was found with `cost`.
+    /// Only open lists that break ties look at the cost, others push the node like [`OpenList::push`].
+    fn push_found(&mut self, node: Node, estimate: f32, _cost: Cost) {
+        self.push(node, estimate);
+    }
+
+    /// Remove the node with the smallest estimate
+    fn pop(&mut self) -> Option<Node>;
+
+    /// Number of entries, including duplicates
+    fn size(&self) -> usize;
+
+    /// Entries with their estimates, in no particular order
+    fn entries(&self) -> Vec<(Node, f32)>;
+
+    /// Three nodes with the smallest estimates
+    fn top3(&self) -> (Option<Node>, Option<Node>, Option<Node>) {
+        let mut entries = self.entries();
+        entries.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let mut nodes = entries.into_iter().map(|(node, _)| node);
+        (nodes.next(), nodes.next(), nodes.next())
+    }
+}
+
+/// Open list that orders nodes with equal estimates by a tie-breaking key.
+/// Only these open lists can be used with [`AStar::with_tie_break`](super::AStar::with_tie_break).
+pub trait TieBreaking: OpenList {
+    /// Add a node with a key that breaks ties between equal estimates, larger first
+    fn push_tied(&mut self, node: Node, estimate: f32, tie: u32);
+
+    /// Give keys from `ties` to the nodes pushed with [`OpenList::push_found`]
+    fn break_ties(&mut self, ties: Ties);
+}
+
+/// Open lists that can be selected for A*
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug, Default)]
+pub enum Queue {
+    /// Binary heap with duplicates
+    #[default]
+    Binary,
+    /// 4-ary heap with duplicates
+    Dary,
+    /// Pairing heap with decrease-key
+    Pairing,
+    /// Radix heap with duplicates, only for estimates that never decrease, so not with --weight
+    Radix,
+    /// Buckets of estimates with duplicates
+    Buckets,
+}
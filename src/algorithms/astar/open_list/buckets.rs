@@ -0,0 +1,90 @@
+use super::OpenList;
+use crate::algorithms::astar::weighted_cell::WeightedCell;
+use crate::Node;
+use std::collections::BinaryHeap;
+
+/// Bucket queue keyed on the estimate, like a calendar queue whose days never wrap around.
+///
+/// Bucket `i` holds estimates in `[i · width, (i + 1) · width)`, and only the current bucket is ordered,
+/// so most pushes are cheap and only nodes of similar estimate are compared.
+/// Estimates below the current bucket are put to the current bucket, so nodes are still popped in order.
+/// ```
+/// # use fringe_vs_astar::algorithms::astar::open_list::{BucketQueue, OpenList};
+/// let mut queue = BucketQueue::new(0, 8);
+/// queue.push(1, 3.5);
+/// queue.push(2, 1.7);
+/// queue.push(3, 1.2);
+///
+/// assert_eq!(queue.pop(), Some(0));
+/// assert_eq!(queue.pop(), Some(3));
+/// assert_eq!(queue.pop(), Some(2));
+/// assert_eq!(queue.pop(), Some(1));
+/// assert_eq!(queue.pop(), None);
+/// ```
+pub struct BucketQueue {
+    buckets: Vec<BinaryHeap<WeightedCell>>,
+    current: usize,
+    width: f32,
+    len: usize,
+}
+
+impl BucketQueue {
+    /// Use buckets of `width` instead of 1
+    #[must_use]
+    pub fn with_width(mut self, width: f32) -> Self {
+        let entries = self.entries();
+        self.buckets.clear();
+        self.current = 0;
+        self.len = 0;
+        self.width = width;
+        for (node, estimate) in entries {
+            self.push(node, estimate);
+        }
+        self
+    }
+}
+
+impl OpenList for BucketQueue {
+    fn new(start: Node, _size: usize) -> Self {
+        let mut queue = BucketQueue {
+            buckets: vec![],
+            current: 0,
+            width: 1.0,
+            len: 0,
+        };
+        queue.push(start, 0.0);
+        queue
+    }
+
+    fn push(&mut self, node: Node, estimate: f32) {
+        let bucket = ((estimate / self.width) as usize).max(self.current);
+        if bucket >= self.buckets.len() {
+            self.buckets.resize_with(bucket + 1, BinaryHeap::new);
+        }
+        self.buckets[bucket].push(WeightedCell::new(node, estimate));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<Node> {
+        if self.len == 0 {
+            return None;
+        }
+        while self.buckets[self.current].is_empty() {
+            self.current += 1;
+        }
+        self.len -= 1;
+        self.buckets[self.current].pop().map(|cell| cell.node)
+    }
+
+    fn size(&self) -> usize {
+        self.len
+    }
+
+    fn entries(&self) -> Vec<(Node, f32)> {
+        self.buckets
+            .iter()
+            .flatten()
+            .map(|cell| (cell.node, cell.weight))
+            .collect()
+    }
+}
@@ -0,0 +1,84 @@
+use super::OpenList;
+use crate::Node;
+
+/// Implicit heap where every entry has `D` children.
+/// Wider heaps are shallower, so pushing is cheaper and popping compares more children.
+/// Improved nodes are pushed again as duplicates, like in [`Frontier`](super::Frontier).
+/// ```
+/// # use fringe_vs_astar::algorithms::astar::open_list::{DaryHeap, OpenList};
+/// let mut heap = DaryHeap::<4>::new(0, 8);
+/// heap.push(1, 3.0);
+/// heap.push(2, 1.0);
+/// heap.push(3, 2.0);
+///
+/// assert_eq!(heap.pop(), Some(0));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct DaryHeap<const D: usize> {
+    heap: Vec<(f32, Node)>,
+}
+
+impl<const D: usize> DaryHeap<D> {
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / D;
+            if self.heap[parent].0 <= self.heap[i].0 {
+                break;
+            }
+            self.heap.swap(parent, i);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let first = D * i + 1;
+            let last = (first + D).min(self.heap.len());
+            let Some(child) =
+                (first..last).min_by(|a, b| self.heap[*a].0.total_cmp(&self.heap[*b].0))
+            else {
+                break;
+            };
+            if self.heap[i].0 <= self.heap[child].0 {
+                break;
+            }
+            self.heap.swap(i, child);
+            i = child;
+        }
+    }
+}
+
+impl<const D: usize> OpenList for DaryHeap<D> {
+    fn new(start: Node, size: usize) -> Self {
+        let mut heap = Vec::with_capacity(size);
+        heap.push((0.0, start));
+        DaryHeap { heap }
+    }
+
+    fn push(&mut self, node: Node, estimate: f32) {
+        self.heap.push((estimate, node));
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    fn pop(&mut self) -> Option<Node> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let (_, node) = self.heap.swap_remove(0);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some(node)
+    }
+
+    fn size(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn entries(&self) -> Vec<(Node, f32)> {
+        self.heap.iter().map(|(e, n)| (*n, *e)).collect()
+    }
+}
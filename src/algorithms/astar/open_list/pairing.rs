@@ -0,0 +1,197 @@
+use super::OpenList;
+use crate::algorithms::workspace::Buffer;
+use crate::algorithms::Workspace;
+use crate::Node;
+
+const NONE: Node = Node::MAX;
+
+/// Entry of a node in the heap. `prev` is the parent of a first child and the previous sibling of others.
+#[derive(Clone, Copy)]
+pub(crate) struct Entry {
+    estimate: f32,
+    child: Node,
+    next: Node,
+    prev: Node,
+    in_heap: bool,
+}
+
+impl Default for Entry {
+    fn default() -> Self {
+        Entry {
+            estimate: f32::MAX,
+            child: NONE,
+            next: NONE,
+            prev: NONE,
+            in_heap: false,
+        }
+    }
+}
+
+/// Pairing heap with decrease-key.
+///
+/// Entries are stored by node, so every node is in the heap at most once
+/// and an improved estimate cuts the node's subtree and melds it back to the root.
+/// Entries are kept in a [`Buffer`], which is reused by the next search of a [`Workspace`].
+/// ```
+/// # use fringe_vs_astar::algorithms::astar::open_list::{PairingHeap, OpenList};
+/// let mut heap = PairingHeap::new(0, 8);
+/// heap.push(1, 3.0);
+/// heap.push(2, 2.0);
+/// heap.push(3, 4.0);
+/// heap.push(3, 1.0);
+///
+/// assert_eq!(heap.size(), 4);
+/// assert_eq!(heap.pop(), Some(0));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct PairingHeap {
+    entries: Buffer<Entry>,
+    root: Node,
+    len: usize,
+    /// Reused between pops for pairing the children of the root
+    pairs: Vec<Node>,
+}
+
+impl PairingHeap {
+    fn entry(&self, node: Node) -> &Entry {
+        self.entries.get(node as usize)
+    }
+
+    fn entry_mut(&mut self, node: Node) -> &mut Entry {
+        self.entries.get_mut(node as usize)
+    }
+
+    /// Make the root with the larger estimate the first child of the other
+    fn meld(&mut self, a: Node, b: Node) -> Node {
+        let (parent, child) = if self.entry(b).estimate < self.entry(a).estimate {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        let first = self.entry(parent).child;
+        if first != NONE {
+            self.entry_mut(first).prev = child;
+        }
+        self.entry_mut(child).next = first;
+        self.entry_mut(child).prev = parent;
+        self.entry_mut(parent).child = child;
+        parent
+    }
+
+    /// Detach a node and its subtree from its parent and siblings
+    fn cut(&mut self, node: Node) {
+        let (prev, next) = (self.entry(node).prev, self.entry(node).next);
+        if self.entry(prev).child == node {
+            self.entry_mut(prev).child = next;
+        } else {
+            self.entry_mut(prev).next = next;
+        }
+        if next != NONE {
+            self.entry_mut(next).prev = prev;
+        }
+        self.entry_mut(node).prev = NONE;
+        self.entry_mut(node).next = NONE;
+    }
+
+    /// Meld siblings in pairs from left to right, then the pairs from right to left
+    fn merge_pairs(&mut self, first: Node) -> Node {
+        let mut pairs = std::mem::take(&mut self.pairs);
+        let mut node = first;
+        while node != NONE {
+            let a = node;
+            let b = self.entry(a).next;
+            self.entry_mut(a).prev = NONE;
+            self.entry_mut(a).next = NONE;
+            if b == NONE {
+                pairs.push(a);
+                break;
+            }
+            node = self.entry(b).next;
+            self.entry_mut(b).prev = NONE;
+            self.entry_mut(b).next = NONE;
+            pairs.push(self.meld(a, b));
+        }
+
+        let mut root = pairs.pop().unwrap_or(NONE);
+        while let Some(pair) = pairs.pop() {
+            root = self.meld(pair, root);
+        }
+        self.pairs = pairs;
+        root
+    }
+}
+
+impl OpenList for PairingHeap {
+    fn new(start: Node, size: usize) -> Self {
+        Self::in_workspace(start, size, &mut Workspace::default())
+    }
+
+    fn in_workspace(start: Node, size: usize, workspace: &mut Workspace) -> Self {
+        let mut heap = PairingHeap {
+            entries: workspace.take_pairing(size),
+            root: NONE,
+            len: 0,
+            pairs: vec![],
+        };
+        heap.push(start, 0.0);
+        heap
+    }
+
+    fn into_workspace(self, workspace: &mut Workspace) {
+        workspace.pairing = self.entries;
+    }
+
+    fn push(&mut self, node: Node, estimate: f32) {
+        if self.entry(node).in_heap {
+            if estimate < self.entry(node).estimate {
+                self.entry_mut(node).estimate = estimate;
+                if node != self.root {
+                    self.cut(node);
+                    self.root = self.meld(self.root, node);
+                }
+            }
+            return;
+        }
+        self.len += 1;
+        *self.entries.first_mut(node as usize) = Entry {
+            estimate,
+            in_heap: true,
+            ..Entry::default()
+        };
+        self.root = if self.root == NONE {
+            node
+        } else {
+            self.meld(self.root, node)
+        };
+    }
+
+    fn pop(&mut self) -> Option<Node> {
+        let root = self.root;
+        if root == NONE {
+            return None;
+        }
+        self.len -= 1;
+        let entry = self.entry_mut(root);
+        entry.in_heap = false;
+        let child = entry.child;
+        entry.child = NONE;
+        self.root = self.merge_pairs(child);
+        Some(root)
+    }
+
+    fn size(&self) -> usize {
+        self.len
+    }
+
+    fn entries(&self) -> Vec<(Node, f32)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.in_heap)
+            .map(|(i, entry)| (i as Node, entry.estimate))
+            .collect()
+    }
+}
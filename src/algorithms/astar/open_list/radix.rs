@@ -0,0 +1,90 @@
+use super::OpenList;
+use crate::Node;
+
+/// Keys have 32 bits, so there are 32 buckets of growing ranges and one for the last popped key
+const BUCKETS: usize = 33;
+
+/// Monotone radix heap.
+///
+/// Estimates are keyed by the bits of the float, which are ordered like non-negative floats.
+/// Bucket `i` holds keys whose highest bit that differs from the last popped key is bit `i - 1`,
+/// so a key is moved at most 32 times before it is popped.
+/// Keys must not be smaller than the last popped key. With a consistent heuristic they never are.
+/// Smaller keys are raised to the last popped key, so nodes are no longer popped in order of estimates.
+/// Weighted A* makes such keys all the time, so this heap must not be used with a weight.
+/// ```
+/// # use fringe_vs_astar::algorithms::astar::open_list::{RadixHeap, OpenList};
+/// let mut heap = RadixHeap::new(0, 8);
+/// heap.push(1, 3.5);
+/// heap.push(2, 1.0);
+/// heap.push(3, 1.5);
+///
+/// assert_eq!(heap.pop(), Some(0));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct RadixHeap {
+    buckets: Vec<Vec<(u32, Node)>>,
+    last: u32,
+    len: usize,
+}
+
+impl RadixHeap {
+    fn bucket(key: u32, last: u32) -> usize {
+        if key == last {
+            0
+        } else {
+            32 - (key ^ last).leading_zeros() as usize
+        }
+    }
+}
+
+impl OpenList for RadixHeap {
+    fn new(start: Node, _size: usize) -> Self {
+        let mut heap = RadixHeap {
+            buckets: vec![vec![]; BUCKETS],
+            last: 0,
+            len: 0,
+        };
+        heap.push(start, 0.0);
+        heap
+    }
+
+    fn push(&mut self, node: Node, estimate: f32) {
+        let key = estimate.max(0.0).to_bits().max(self.last);
+        self.buckets[Self::bucket(key, self.last)].push((key, node));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<Node> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = (1..BUCKETS).find(|i| !self.buckets[*i].is_empty())?;
+            let mut moved = std::mem::take(&mut self.buckets[i]);
+            self.last = moved.iter().map(|(key, _)| *key).min()?;
+            // Every key goes to a lower bucket, because they share the higher bits with the new last key
+            for (key, node) in moved.drain(..) {
+                self.buckets[Self::bucket(key, self.last)].push((key, node));
+            }
+            self.buckets[i] = moved;
+        }
+        self.len -= 1;
+        self.buckets[0].pop().map(|(_, node)| node)
+    }
+
+    fn size(&self) -> usize {
+        self.len
+    }
+
+    fn entries(&self) -> Vec<(Node, f32)> {
+        self.buckets
+            .iter()
+            .flatten()
+            .map(|(key, node)| (*node, f32::from_bits(*key)))
+            .collect()
+    }
+}
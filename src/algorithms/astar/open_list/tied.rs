@@ -0,0 +1,104 @@
+use super::{OpenList, TieBreaking};
+use crate::algorithms::astar::WeightedCell;
+use crate::algorithms::tie_break::Ties;
+use crate::{Cost, Node};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Binary heap with duplicates, where nodes with equal estimates are ordered by their tie-breaking keys.
+/// Larger keys come first, and nodes pushed without a key get key 0.
+/// Nodes pushed with [`OpenList::push_found`] get their keys from [`Ties`] given by [`TieBreaking::break_ties`].
+/// ```
+/// # use fringe_vs_astar::algorithms::astar::open_list::{OpenList, TieBreaking, TiedFrontier};
+/// let mut frontier = TiedFrontier::new(0, 8);
+/// frontier.pop();
+/// frontier.push_tied(1, 5.0, 3);
+/// frontier.push_tied(2, 5.0, 7);
+/// frontier.push_tied(3, 4.0, 1);
+///
+/// assert_eq!(frontier.pop(), Some(3));
+/// assert_eq!(frontier.pop(), Some(2));
+/// assert_eq!(frontier.pop(), Some(1));
+/// ```
+pub struct TiedFrontier {
+    heap: BinaryHeap<TiedCell>,
+    ties: Option<Ties>,
+}
+
+/// [`WeightedCell`] with a key for breaking ties. Larger keys are greater.
+#[derive(Clone, Copy)]
+struct TiedCell {
+    cell: WeightedCell,
+    tie: u32,
+}
+
+impl PartialEq for TiedCell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TiedCell {}
+
+impl Ord for TiedCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.cell.cmp(&other.cell) {
+            Ordering::Equal => self.tie.cmp(&other.tie),
+            ordering => ordering,
+        }
+    }
+}
+
+impl PartialOrd for TiedCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl OpenList for TiedFrontier {
+    fn new(start: Node, size: usize) -> Self {
+        let mut heap = BinaryHeap::with_capacity(size);
+        heap.push(TiedCell {
+            cell: WeightedCell::new(start, 0.0),
+            tie: 0,
+        });
+        TiedFrontier { heap, ties: None }
+    }
+
+    fn push(&mut self, node: Node, estimate: f32) {
+        self.push_tied(node, estimate, 0);
+    }
+
+    fn push_found(&mut self, node: Node, estimate: f32, cost: Cost) {
+        let tie = self.ties.as_mut().map_or(0, |ties| ties.key(cost));
+        self.push_tied(node, estimate, tie);
+    }
+
+    fn pop(&mut self) -> Option<Node> {
+        self.heap.pop().map(|w| w.cell.node)
+    }
+
+    fn size(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn entries(&self) -> Vec<(Node, f32)> {
+        self.heap
+            .iter()
+            .map(|w| (w.cell.node, w.cell.weight))
+            .collect()
+    }
+}
+
+impl TieBreaking for TiedFrontier {
+    fn push_tied(&mut self, node: Node, estimate: f32, tie: u32) {
+        self.heap.push(TiedCell {
+            cell: WeightedCell::new(node, estimate),
+            tie,
+        });
+    }
+
+    fn break_ties(&mut self, ties: Ties) {
+        self.ties = Some(ties);
+    }
+}
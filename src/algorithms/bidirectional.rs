@@ -0,0 +1,240 @@
+use super::astar::cache::Cache;
+use super::astar::frontier::Frontier;
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+
+/// One direction of a bidirectional search.
+/// Has its own [`Frontier`] and [`Cache`] with heuristic towards the other end.
+pub struct Search {
+    frontier: Frontier,
+    cache: Cache,
+    closed: Vec<bool>,
+    pub expansions: usize,
+}
+
+impl Search {
+    fn new(origin: Node, target: Node, graph: &Graph) -> Self {
+        let size = graph.map_size();
+        let heuristic = Heuristic::new(target, graph.get_width());
+
+        Search {
+            frontier: Frontier::new(origin, size),
+            cache: Cache::new(origin, heuristic, size),
+            closed: vec![false; size],
+            expansions: 0,
+        }
+    }
+
+    /// Smallest estimate in the frontier. Already closed nodes are discarded from the top.
+    fn min_estimate(&mut self) -> Cost {
+        while let Some((node, estimate)) = self.frontier.peek() {
+            if !self.closed[node as usize] {
+                return estimate;
+            }
+            self.frontier.pop();
+        }
+        f32::INFINITY
+    }
+
+    /// Path from origin of this search to `node`, starting from `node`
+    fn path_from(&self, node: Node) -> Path {
+        let mut path = vec![node];
+        loop {
+            let node = path[path.len() - 1];
+            let parent = self.cache[node].parent;
+            if parent == node {
+                break;
+            }
+            path.push(parent);
+        }
+        path
+    }
+}
+
+/// Bidirectional A* pathfinder.
+///
+/// Searches forward from start and backward from goal at the same time.
+/// Graph is assumed to be symmetric, so backward search uses the same neighbors.
+/// Side with the smaller frontier is expanded on each step.
+///
+/// Every time a [`Node`] is reached with a better cost, it is checked against the other side,
+/// and the best meeting cost μ is kept up to date.
+/// Search stops when smallest estimate of either frontier is at least μ,
+/// because then no path through that frontier can be shorter than the best one found.
+/// Expansions of both sides count towards the [`Limits`].
+pub struct BidirectionalAStar<'a> {
+    pub forward: Search,
+    pub backward: Search,
+    limits: Limits,
+    best: Cost,
+    meeting: Option<Node>,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> BidirectionalAStar<'a> {
+    /// Create solver of a problem for a graph
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        BidirectionalAStar {
+            forward: Search::new(start, goal, graph),
+            backward: Search::new(goal, start, graph),
+            limits: Limits::default(),
+            best: f32::INFINITY,
+            meeting: if start == goal { Some(start) } else { None },
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(mut self) -> Option<(Path, Cost)> {
+        self.run().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        self.run()
+    }
+
+    /// Run through the whole solving process. Leaves the search available for inspection.
+    pub fn run(&mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        if self.start == self.goal {
+            return State::Finished((vec![self.start], 0.0));
+        }
+
+        let forward_min = self.forward.min_estimate();
+        let backward_min = self.backward.min_estimate();
+        if self.meeting.is_some() && forward_min.max(backward_min) >= self.best {
+            return State::Finished(self.construct_path());
+        }
+        if forward_min == f32::INFINITY || backward_min == f32::INFINITY {
+            return State::NotFound;
+        }
+        let expansions = self.forward.expansions + self.backward.expansions;
+        if let Some(reason) = self.limits.check(expansions) {
+            return State::Aborted(reason);
+        }
+
+        let forward = self.forward.frontier.size() <= self.backward.frontier.size();
+        let (this, other) = if forward {
+            (&mut self.forward, &self.backward)
+        } else {
+            (&mut self.backward, &self.forward)
+        };
+
+        let node = this.frontier.pop().expect("Frontier can't be empty");
+        this.closed[node as usize] = true;
+        this.expansions += 1;
+        let current_cost = this.cache.get_cost(node);
+
+        for (child, w) in self.graph.neighbors(node) {
+            if let Some((child, weight)) = this.cache.check(*child, node, current_cost + w) {
+                this.frontier.push(child, weight);
+
+                if other.cache.is_reached(child) {
+                    let cost = this.cache.get_cost(child) + other.cache.get_cost(child);
+                    if cost < self.best {
+                        self.best = cost;
+                        self.meeting = Some(child);
+                    }
+                }
+            }
+        }
+        State::Processing(node)
+    }
+
+    /// Join paths of both searches at the meeting [`Node`]
+    fn construct_path(&self) -> (Path, Cost) {
+        let meeting = self.meeting.expect("Searches have not met");
+        let mut path = self.forward.path_from(meeting);
+        path.reverse();
+        path.extend(self.backward.path_from(meeting).iter().skip(1));
+
+        (path, self.best)
+    }
+
+    /// Number of expanded nodes in forward and backward direction
+    #[must_use]
+    pub fn expansions(&self) -> (usize, usize) {
+        (self.forward.expansions, self.backward.expansions)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, _) in self.forward.closed.iter().enumerate().filter(|(_, c)| **c) {
+            print.add_inclosed(i.try_into().unwrap());
+        }
+        for (i, _) in self.backward.closed.iter().enumerate().filter(|(_, c)| **c) {
+            print.add_oldlater(i.try_into().unwrap());
+        }
+        self.forward
+            .frontier
+            .iter()
+            .for_each(|n| print.add_infrontier(*n));
+        self.backward
+            .frontier
+            .iter()
+            .for_each(|n| print.add_inlater(*n));
+        if let Some(meeting) = self.meeting {
+            print.add_first(meeting);
+        }
+
+        print.add_header("|Open| →", self.forward.frontier.size());
+        print.add_header("|Open| ←", self.backward.frontier.size());
+        print.add_header("Expanded →", self.forward.expansions);
+        print.add_header("Expanded ←", self.backward.expansions);
+        print.add_header("μ", self.best);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Search that has expanded a [`Node`], forward search is preferred
+    fn side(&self, node: Node) -> &Search {
+        if self.backward.closed[node as usize] && !self.forward.closed[node as usize] {
+            &self.backward
+        } else {
+            &self.forward
+        }
+    }
+
+    /// Get current cost of a [`Node`] from the side that has expanded it
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.side(node).cache.get_cost(node)
+    }
+
+    /// Get current estimate of a [`Node`] from the side that has expanded it
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.side(node).cache.get_estimate(node)
+    }
+
+    /// Get combined size of both frontiers
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.forward.frontier.size() + self.backward.frontier.size()
+    }
+}
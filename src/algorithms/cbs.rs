@@ -0,0 +1,316 @@
+use self::space_time::SpaceTimeAStar;
+use super::{Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Space-time A* that plans a single agent around constraints
+pub mod space_time;
+
+/// Rule that forbids an agent from a cell or a move at a timestep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// Agent can't be at `node` at `time`
+    Vertex { node: Node, time: u32 },
+    /// Agent can't move from `from` to `to` between `time` and `time + 1`
+    Edge { from: Node, to: Node, time: u32 },
+}
+
+impl Constraint {
+    /// Timestep the constraint starts at
+    #[must_use]
+    pub fn time(&self) -> u32 {
+        match self {
+            Constraint::Vertex { time, .. } | Constraint::Edge { time, .. } => *time,
+        }
+    }
+}
+
+/// Two agents that collide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conflict {
+    /// Both agents are at `node` at `time`
+    Vertex {
+        agents: (usize, usize),
+        node: Node,
+        time: u32,
+    },
+    /// First agent moves from `from` to `to` while the second one moves from `to` to `from`
+    Edge {
+        agents: (usize, usize),
+        from: Node,
+        to: Node,
+        time: u32,
+    },
+}
+
+impl Conflict {
+    /// Constraints that resolve the conflict, one for each agent
+    #[must_use]
+    pub fn constraints(&self) -> [(usize, Constraint); 2] {
+        match *self {
+            Conflict::Vertex {
+                agents: (a, b),
+                node,
+                time,
+            } => [a, b].map(|agent| (agent, Constraint::Vertex { node, time })),
+            Conflict::Edge {
+                agents: (a, b),
+                from,
+                to,
+                time,
+            } => [
+                (a, Constraint::Edge { from, to, time }),
+                (
+                    b,
+                    Constraint::Edge {
+                        from: to,
+                        to: from,
+                        time,
+                    },
+                ),
+            ],
+        }
+    }
+}
+
+/// Paths of every agent, one position for every timestep.
+/// Agent stays at its goal after its path ends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    pub paths: Vec<Path>,
+    pub costs: Vec<Cost>,
+}
+
+impl Plan {
+    /// Position of an agent at a timestep
+    #[must_use]
+    pub fn position(&self, agent: usize, time: u32) -> Node {
+        let path = &self.paths[agent];
+        path[(time as usize).min(path.len() - 1)]
+    }
+
+    /// Sum of the costs of every path
+    #[must_use]
+    pub fn sum_of_costs(&self) -> Cost {
+        self.costs.iter().sum()
+    }
+
+    /// Timestep when the last agent reaches its goal
+    #[must_use]
+    pub fn makespan(&self) -> u32 {
+        self.paths
+            .iter()
+            .map(|p| p.len() as u32 - 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Earliest conflict between any two agents, or `None` if the plan is safe.
+    /// ```
+    /// # use fringe_vs_astar::algorithms::cbs::{Conflict, Plan};
+    /// // Agents swap cells 0 and 1
+    /// let plan = Plan { paths: vec![vec![0, 1], vec![1, 0]], costs: vec![1.0, 1.0] };
+    /// assert_eq!(
+    ///     plan.first_conflict(),
+    ///     Some(Conflict::Edge { agents: (0, 1), from: 0, to: 1, time: 0 })
+    /// );
+    ///
+    /// // Second agent waits at its goal when the first one passes through
+    /// let plan = Plan { paths: vec![vec![0, 1, 2], vec![1]], costs: vec![2.0, 0.0] };
+    /// assert_eq!(
+    ///     plan.first_conflict(),
+    ///     Some(Conflict::Vertex { agents: (0, 1), node: 1, time: 1 })
+    /// );
+    /// ```
+    #[must_use]
+    pub fn first_conflict(&self) -> Option<Conflict> {
+        let agents = self.paths.len();
+        for time in 0..=self.makespan() {
+            for a in 0..agents {
+                for b in a + 1..agents {
+                    let (a_now, b_now) = (self.position(a, time), self.position(b, time));
+                    if a_now == b_now {
+                        return Some(Conflict::Vertex {
+                            agents: (a, b),
+                            node: a_now,
+                            time,
+                        });
+                    }
+                    let (a_next, b_next) = (self.position(a, time + 1), self.position(b, time + 1));
+                    if a_now == b_next && b_now == a_next {
+                        return Some(Conflict::Edge {
+                            agents: (a, b),
+                            from: a_now,
+                            to: a_next,
+                            time,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Draw positions of the agents at every timestep, from start until every agent is at its goal
+    #[must_use]
+    pub fn render(&self, printable: &Printable) -> Vec<Printable> {
+        (0..=self.makespan())
+            .map(|time| {
+                let mut frame = printable.clone();
+                for path in &self.paths {
+                    frame.add_goal(path[path.len() - 1]);
+                }
+                for agent in 0..self.paths.len() {
+                    frame.add_agent(self.position(agent, time), agent);
+                }
+                frame.add_header("Timestep", format!("{time} / {}", self.makespan()));
+                frame
+            })
+            .collect()
+    }
+}
+
+/// Node of the constraint tree
+struct Branch {
+    constraints: Vec<(usize, Constraint)>,
+    plan: Plan,
+}
+
+/// Priority of a [`Branch`]. Lower sum of costs is ordered as greater, so `BinaryHeap` is a minimum heap.
+#[derive(Clone, Copy, PartialEq)]
+struct Priority {
+    cost: Cost,
+    index: usize,
+}
+
+impl Eq for Priority {}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Conflict-Based Search for multi-agent pathfinding.
+///
+/// High level searches a tree of constraints, cheapest sum of costs first.
+/// Every agent of a branch is planned alone with [`SpaceTimeAStar`].
+/// When two agents collide, the branch is split in two, and each child forbids the collision from one of them.
+/// First branch without conflicts has the smallest sum of costs.
+pub struct Cbs<'a> {
+    agents: Vec<(Node, Node)>,
+    limits: Limits,
+    pub expansions: usize,
+    pub low_level_expansions: usize,
+    graph: &'a Graph,
+}
+
+impl<'a> Cbs<'a> {
+    /// Create solver for agents given as start and goal pairs
+    #[must_use]
+    pub fn new(agents: Vec<(Node, Node)>, graph: &'a Graph) -> Self {
+        Cbs {
+            agents,
+            limits: Limits::default(),
+            expansions: 0,
+            low_level_expansions: 0,
+            graph,
+        }
+    }
+
+    /// Stop the high level search early when one of `limits` is reached.
+    /// Only branches of the constraint tree count as expansions
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to find a plan without conflicts.
+    /// Agents that share a start or a goal can never be separated, so there is no plan for them.
+    pub fn solve(&mut self) -> Option<Plan> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    pub fn solve_limited(&mut self) -> State<Plan> {
+        let starts: HashSet<Node> = self.agents.iter().map(|(s, _)| *s).collect();
+        let goals: HashSet<Node> = self.agents.iter().map(|(_, g)| *g).collect();
+        if starts.len() < self.agents.len() || goals.len() < self.agents.len() {
+            return State::NotFound;
+        }
+
+        let mut paths = vec![];
+        let mut costs = vec![];
+        for agent in 0..self.agents.len() {
+            let Some((path, cost)) = self.plan(agent, &[]) else {
+                return State::NotFound;
+            };
+            paths.push(path);
+            costs.push(cost);
+        }
+        let root = Branch {
+            constraints: vec![],
+            plan: Plan { paths, costs },
+        };
+
+        let mut heap = BinaryHeap::from([Priority {
+            cost: root.plan.sum_of_costs(),
+            index: 0,
+        }]);
+        let mut branches = vec![root];
+        while let Some(Priority { index, .. }) = heap.pop() {
+            if let Some(reason) = self.limits.check(self.expansions) {
+                return State::Aborted(reason);
+            }
+            self.expansions += 1;
+            let Some(conflict) = branches[index].plan.first_conflict() else {
+                return State::Finished(branches.swap_remove(index).plan);
+            };
+
+            for (agent, constraint) in conflict.constraints() {
+                let mut constraints = branches[index].constraints.clone();
+                constraints.push((agent, constraint));
+                let Some((path, cost)) = self.plan(agent, &constraints) else {
+                    continue;
+                };
+
+                let mut plan = branches[index].plan.clone();
+                plan.paths[agent] = path;
+                plan.costs[agent] = cost;
+                heap.push(Priority {
+                    cost: plan.sum_of_costs(),
+                    index: branches.len(),
+                });
+                branches.push(Branch { constraints, plan });
+            }
+        }
+        State::NotFound
+    }
+
+    /// Plan a single agent with its constraints
+    fn plan(&mut self, agent: usize, constraints: &[(usize, Constraint)]) -> Option<(Path, Cost)> {
+        let own: Vec<Constraint> = constraints
+            .iter()
+            .filter(|(a, _)| *a == agent)
+            .map(|(_, c)| *c)
+            .collect();
+        let (start, goal) = self.agents[agent];
+        let mut search = SpaceTimeAStar::new(start, goal, self.graph, &own);
+        let result = search.solve();
+        self.low_level_expansions += search.expansions;
+        result
+    }
+}
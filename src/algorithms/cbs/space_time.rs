@@ -0,0 +1,146 @@
+use super::Constraint;
+use crate::algorithms::astar::weighted_cell::WeightedCell;
+use crate::algorithms::Heuristic;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Cost of staying in place for one timestep
+pub const WAIT_COST: Cost = 1.0;
+
+/// A [`Node`] at a timestep, and how it was reached
+struct Visit {
+    node: Node,
+    time: u32,
+    cost: Cost,
+    parent: u32,
+}
+
+/// Space-time A*, the low level of [`Cbs`](super::Cbs).
+///
+/// States are pairs of a [`Node`] and a timestep. Every move along the [`Graph`] takes one timestep
+/// and costs its weight, and waiting in place costs [`WAIT_COST`].
+/// States that break a [`Constraint`] of the agent are never entered.
+///
+/// After the last constrained timestep, time no longer matters,
+/// so later states of a node are treated as one and the search space stays finite.
+pub struct SpaceTimeAStar<'a> {
+    vertices: HashSet<(Node, u32)>,
+    edges: HashSet<(Node, Node, u32)>,
+    /// Timesteps after this are not constrained
+    horizon: u32,
+    /// Agent can't stay at goal before this timestep
+    earliest_finish: u32,
+    heuristic: Heuristic,
+    pub expansions: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> SpaceTimeAStar<'a> {
+    /// Create solver of a problem with constraints of the agent
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, constraints: &[Constraint]) -> Self {
+        let mut vertices = HashSet::new();
+        let mut edges = HashSet::new();
+        for constraint in constraints {
+            match *constraint {
+                Constraint::Vertex { node, time } => {
+                    vertices.insert((node, time));
+                }
+                Constraint::Edge { from, to, time } => {
+                    edges.insert((from, to, time));
+                }
+            }
+        }
+        let horizon = constraints.iter().map(|c| c.time() + 1).max().unwrap_or(0);
+        let earliest_finish = vertices
+            .iter()
+            .filter(|(node, _)| *node == goal)
+            .map(|(_, time)| time + 1)
+            .max()
+            .unwrap_or(0);
+
+        SpaceTimeAStar {
+            vertices,
+            edges,
+            horizon,
+            earliest_finish,
+            heuristic: Heuristic::new(goal, graph.get_width()),
+            expansions: 0,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Find the cheapest path that obeys the constraints.
+    /// Path has the position of the agent at every timestep until it stays at goal for good.
+    #[must_use]
+    pub fn solve(&mut self) -> Option<(Path, Cost)> {
+        if self.vertices.contains(&(self.start, 0)) {
+            return None;
+        }
+
+        let mut visits = vec![Visit {
+            node: self.start,
+            time: 0,
+            cost: 0.0,
+            parent: 0,
+        }];
+        let mut best: HashMap<(Node, u32), Cost> = HashMap::from([((self.start, 0), 0.0)]);
+        let mut heap = BinaryHeap::from([WeightedCell::new(0, self.heuristic.calc(self.start))]);
+
+        while let Some(WeightedCell { node: index, .. }) = heap.pop() {
+            let Visit {
+                node, time, cost, ..
+            } = visits[index as usize];
+            if best[&(node, time.min(self.horizon))] < cost {
+                continue;
+            }
+            if node == self.goal && time >= self.earliest_finish {
+                return Some((Self::construct_path(&visits, index), cost));
+            }
+            self.expansions += 1;
+
+            let wait = (time < self.horizon).then_some((node, WAIT_COST));
+            for (child, w) in self.graph.neighbors(node).copied().chain(wait) {
+                let next = time + 1;
+                if self.vertices.contains(&(child, next))
+                    || self.edges.contains(&(node, child, time))
+                {
+                    continue;
+                }
+                let child_cost = cost + w;
+                let key = (child, next.min(self.horizon));
+                if best.get(&key).is_some_and(|c| *c <= child_cost) {
+                    continue;
+                }
+                best.insert(key, child_cost);
+                visits.push(Visit {
+                    node: child,
+                    time: next,
+                    cost: child_cost,
+                    parent: index,
+                });
+                heap.push(WeightedCell::new(
+                    visits.len() as u32 - 1,
+                    child_cost + self.heuristic.calc(child),
+                ));
+            }
+        }
+        None
+    }
+
+    /// Follow parents of visits back to the start
+    fn construct_path(visits: &[Visit], mut index: u32) -> Path {
+        let mut path = vec![visits[index as usize].node];
+        while index != 0 {
+            index = visits[index as usize].parent;
+            path.push(visits[index as usize].node);
+        }
+        path.reverse();
+        path
+    }
+}
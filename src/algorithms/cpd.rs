@@ -0,0 +1,121 @@
+use self::database::PathDatabase;
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::{index_to_xy, xy_to_index, Cost, Node, Path, DIAGONAL_COST};
+
+/// First moves of every source compressed into runs, and their sidecar files
+pub mod database;
+
+/// Path extraction from a compressed path database.
+///
+/// Nothing is searched: the first move toward the goal is looked up from a [`PathDatabase`]
+/// and taken until the goal is reached, so every step of a query takes the same time.
+pub struct Cpd<'a> {
+    path: Path,
+    cost: Cost,
+    heuristic: Heuristic,
+    limits: Limits,
+    start: Node,
+    goal: Node,
+    database: &'a PathDatabase,
+}
+
+impl<'a> Cpd<'a> {
+    /// Create solver of a problem for a precomputed database
+    #[must_use]
+    pub fn new(start: Node, goal: Node, database: &'a PathDatabase) -> Self {
+        Cpd {
+            path: vec![start],
+            cost: 0.0,
+            heuristic: Heuristic::new(goal, database.get_width()),
+            limits: Limits::default(),
+            start,
+            goal,
+            database,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached.
+    /// Every move counts as an expansion
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by taking one move.
+    /// [`State::Processing`] carries the cell that was moved to.
+    pub fn progress(&mut self) -> State {
+        let current = self.path[self.path.len() - 1];
+        if current == self.goal {
+            return State::Finished((self.path.clone(), self.cost));
+        }
+        let Some((dx, dy)) = self.database.first_move(current, self.goal) else {
+            return State::NotFound;
+        };
+        if let Some(reason) = self.limits.check(self.path.len() - 1) {
+            return State::Aborted(reason);
+        }
+
+        let width = self.database.get_width();
+        let (x, y) = index_to_xy(current, width);
+        let next = xy_to_index((x as i32 + dx) as usize, (y as i32 + dy) as usize, width);
+        self.cost += if dx != 0 && dy != 0 {
+            DIAGONAL_COST
+        } else {
+            1.0
+        };
+        self.path.push(next);
+        State::Processing(next)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for node in &self.path {
+            print.add_inclosed(*node);
+        }
+
+        print.add_header("Moves", self.path.len() - 1);
+        print.add_header("Runs", self.database.row(self.start).len());
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get cost of a cell, if it is the end of the path so far
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        if self.path[self.path.len() - 1] == node {
+            self.cost
+        } else {
+            f32::INFINITY
+        }
+    }
+
+    /// Get estimate of a cell, if it is the end of the path so far
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.get_cost(node) + self.heuristic.calc(node)
+    }
+
+    /// Get size of the path so far
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.path.len()
+    }
+}
@@ -0,0 +1,326 @@
+use crate::algorithms::astar::weighted_cell::WeightedCell;
+use crate::algorithms::jps::DIRECTIONS;
+use crate::algorithms::jpsplus::table::direction_index;
+use crate::sidecar::{checksum, read_u32};
+use crate::structures::Graph;
+use crate::{index_to_xy, Node};
+
+use anyhow::{bail, Context};
+use rayon::prelude::*;
+use std::collections::BinaryHeap;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::mem::{size_of, size_of_val};
+use std::path::{Path, PathBuf};
+
+/// Identifier in the beginning of a sidecar file
+const MAGIC: &[u8; 4] = b"CPD\0";
+/// Version of the sidecar format
+const VERSION: u32 = 1;
+/// First move of targets that don't need one: the source itself, walls and unreachable cells
+const ANY: u8 = u8::MAX;
+
+/// Consecutive targets that share the same first move from a source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    /// Smallest target of the run
+    pub first: Node,
+    /// Index of the first move in [`DIRECTIONS`]
+    pub direction: u8,
+}
+
+/// Compressed path database (CPD).
+///
+/// For every source, the first move of a shortest path to every target is computed with Dijkstra's algorithm.
+/// Targets are ordered by their index and the moves of a source are compressed into runs of equal moves.
+/// Targets that need no move can join any run, which keeps the runs long.
+/// A path is extracted by looking up the first move from the current cell until the goal is reached.
+///
+/// Like a [`JumpTable`](crate::algorithms::jpsplus::table::JumpTable),
+/// the database can be saved to a sidecar file next to the map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathDatabase {
+    /// Runs of source `s` are `runs[offsets[s]..offsets[s + 1]]`
+    offsets: Vec<u32>,
+    runs: Vec<Run>,
+    /// Connected component of every cell, [`u32::MAX`] for walls
+    components: Vec<u32>,
+    width: usize,
+    height: usize,
+    checksum: u64,
+}
+
+impl PathDatabase {
+    /// Compute and compress first moves of every source. Sources are handled in parallel.
+    #[must_use]
+    pub fn new(graph: &Graph) -> Self {
+        let rows: Vec<Vec<Run>> = (0..graph.map_size() as Node)
+            .into_par_iter()
+            .map(|source| compress(&first_moves(graph, source)))
+            .collect();
+
+        let mut offsets = vec![0];
+        for row in &rows {
+            offsets.push(offsets[offsets.len() - 1] + row.len() as u32);
+        }
+
+        PathDatabase {
+            offsets,
+            runs: rows.into_iter().flatten().collect(),
+            components: components(graph),
+            width: graph.get_width(),
+            height: graph.get_height(),
+            checksum: checksum(graph),
+        }
+    }
+
+    /// First move of a shortest path from `source` to `target` as `(dx, dy)`.
+    /// `None` if the target can't be reached or is the source.
+    /// ```
+    /// # use fringe_vs_astar::algorithms::cpd::database::PathDatabase;
+    /// # use fringe_vs_astar::structures::{Graph, Map};
+    /// # use std::path::PathBuf;
+    /// // ... | 012
+    /// // .@. | 345
+    /// // ... | 678
+    /// let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    /// let database = PathDatabase::new(&graph);
+    ///
+    /// assert_eq!(database.first_move(0, 4), None);
+    /// assert_eq!(database.first_move(0, 0), None);
+    /// assert_eq!(database.first_move(0, 2), Some((1, 0)));
+    /// assert_eq!(database.first_move(7, 3), Some((-1, 0)));
+    /// ```
+    #[must_use]
+    pub fn first_move(&self, source: Node, target: Node) -> Option<(i32, i32)> {
+        if source == target || !self.connected(source, target) {
+            return None;
+        }
+        let row = self.row(source);
+        let run = row[row.partition_point(|r| r.first <= target) - 1];
+        Some(DIRECTIONS[run.direction as usize])
+    }
+
+    /// Check if a path exists between two cells
+    #[must_use]
+    pub fn connected(&self, source: Node, target: Node) -> bool {
+        let component = self.components[source as usize];
+        component != u32::MAX && component == self.components[target as usize]
+    }
+
+    /// Runs of a source
+    #[must_use]
+    pub fn row(&self, source: Node) -> &[Run] {
+        &self.runs
+            [self.offsets[source as usize] as usize..self.offsets[source as usize + 1] as usize]
+    }
+
+    /// Total number of runs
+    #[must_use]
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Get width of the map
+    #[must_use]
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Size of the compressed database in bytes
+    #[must_use]
+    pub fn size_in_bytes(&self) -> usize {
+        size_of_val(self.offsets.as_slice())
+            + size_of_val(self.runs.as_slice())
+            + size_of_val(self.components.as_slice())
+    }
+
+    /// Size of an uncompressed database with a byte for every pair of passable cells
+    #[must_use]
+    pub fn uncompressed_size_in_bytes(&self) -> usize {
+        let passable = self.components.iter().filter(|c| **c != u32::MAX).count();
+        passable * passable * size_of::<u8>()
+    }
+
+    /// Location of the sidecar file for a map file. `.cpd` is appended to the name of the map.
+    /// ```
+    /// # use fringe_vs_astar::algorithms::cpd::database::PathDatabase;
+    /// # use std::path::PathBuf;
+    /// let sidecar = PathDatabase::sidecar(&PathBuf::from("maps/3x3.map"));
+    /// assert_eq!(PathBuf::from("maps/3x3.map.cpd"), sidecar);
+    /// ```
+    #[must_use]
+    pub fn sidecar(map_file: &Path) -> PathBuf {
+        let mut name = OsString::from(map_file.as_os_str());
+        name.push(".cpd");
+        PathBuf::from(name)
+    }
+
+    /// Load the database from sidecar of a map file if it is valid for the graph, otherwise build and save it.
+    /// Returns the database and `true` if it was built.
+    pub fn load_or_build(map_file: &Path, graph: &Graph) -> anyhow::Result<(Self, bool)> {
+        let sidecar = Self::sidecar(map_file);
+        if let Ok(database) = Self::load(&sidecar, graph) {
+            return Ok((database, false));
+        }
+        let database = Self::new(graph);
+        database.save(&sidecar)?;
+        Ok((database, true))
+    }
+
+    /// Write the database to a file.
+    /// File is first written to a temporary file and then renamed, so a half written database is never read.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut temporary = OsString::from(path.as_os_str());
+        temporary.push(format!(".{}.tmp", std::process::id()));
+        let temporary = PathBuf::from(temporary);
+
+        let mut file = BufWriter::new(File::create(&temporary)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&VERSION.to_le_bytes())?;
+        file.write_all(&(self.width as u32).to_le_bytes())?;
+        file.write_all(&(self.height as u32).to_le_bytes())?;
+        file.write_all(&self.checksum.to_le_bytes())?;
+        file.write_all(&(self.runs.len() as u32).to_le_bytes())?;
+        for offset in &self.offsets {
+            file.write_all(&offset.to_le_bytes())?;
+        }
+        for run in &self.runs {
+            file.write_all(&run.first.to_le_bytes())?;
+            file.write_all(&[run.direction])?;
+        }
+        file.flush()?;
+        drop(file);
+
+        fs::rename(&temporary, path)
+            .with_context(|| format!("Could not write path database {}", path.display()))
+    }
+
+    /// Read the database from a file. Fails if the file is not a database for the supplied graph.
+    pub fn load(path: &Path, graph: &Graph) -> anyhow::Result<Self> {
+        let mut file = BufReader::new(File::open(path)?);
+
+        let mut magic = [0; 4];
+        file.read_exact(&mut magic)?;
+        if &magic != MAGIC || read_u32(&mut file)? != VERSION {
+            bail!("{} is not a path database", path.display());
+        }
+
+        let width = read_u32(&mut file)? as usize;
+        let height = read_u32(&mut file)? as usize;
+        let mut checksum_bytes = [0; 8];
+        file.read_exact(&mut checksum_bytes)?;
+        if width != graph.get_width()
+            || height != graph.get_height()
+            || u64::from_le_bytes(checksum_bytes) != checksum(graph)
+        {
+            bail!("Path database {} is for a different map", path.display());
+        }
+
+        let run_count = read_u32(&mut file)? as usize;
+        let offsets: Vec<u32> = (0..=width * height)
+            .map(|_| read_u32(&mut file))
+            .collect::<anyhow::Result<_>>()?;
+        if offsets.windows(2).any(|pair| pair[0] > pair[1]) {
+            bail!("Offsets of path database {} decrease", path.display());
+        }
+        if offsets[offsets.len() - 1] as usize != run_count {
+            bail!(
+                "Offsets of path database {} do not end at the run count {run_count}",
+                path.display()
+            );
+        }
+
+        let mut bytes = vec![0; run_count * 5];
+        file.read_exact(&mut bytes)?;
+        let runs = bytes
+            .chunks_exact(5)
+            .map(|b| Run {
+                first: u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+                direction: b[4],
+            })
+            .collect();
+
+        Ok(PathDatabase {
+            offsets,
+            runs,
+            components: components(graph),
+            width,
+            height,
+            checksum: u64::from_le_bytes(checksum_bytes),
+        })
+    }
+}
+
+/// Dijkstra's algorithm from a source, where every cell inherits the first move of its parent
+fn first_moves(graph: &Graph, source: Node) -> Vec<u8> {
+    let mut moves = vec![ANY; graph.map_size()];
+    if graph.neighbors(source).len() == 0 {
+        return moves;
+    }
+    let mut distances = vec![f32::INFINITY; graph.map_size()];
+    distances[source as usize] = 0.0;
+
+    let width = graph.get_width();
+    let (sx, sy) = index_to_xy(source, width);
+    let mut heap = BinaryHeap::new();
+    heap.push(WeightedCell::new(source, 0.0));
+    while let Some(WeightedCell { node, weight }) = heap.pop() {
+        if weight > distances[node as usize] {
+            continue;
+        }
+        for (child, w) in graph.neighbors(node) {
+            let cost = weight + w;
+            if cost < distances[*child as usize] {
+                distances[*child as usize] = cost;
+                moves[*child as usize] = if node == source {
+                    let (x, y) = index_to_xy(*child, width);
+                    direction_index(x as i32 - sx as i32, y as i32 - sy as i32) as u8
+                } else {
+                    moves[node as usize]
+                };
+                heap.push(WeightedCell::new(*child, cost));
+            }
+        }
+    }
+    moves
+}
+
+/// Run-length compress moves of a source.
+/// Targets that need no move extend the run before them, or the first run if there is none.
+fn compress(moves: &[u8]) -> Vec<Run> {
+    let mut runs: Vec<Run> = vec![];
+    for (target, direction) in moves.iter().enumerate().filter(|(_, d)| **d != ANY) {
+        if runs.last().map(|r| r.direction) != Some(*direction) {
+            runs.push(Run {
+                first: if runs.is_empty() { 0 } else { target as Node },
+                direction: *direction,
+            });
+        }
+    }
+    runs
+}
+
+/// Label connected components of the graph with a flood fill
+fn components(graph: &Graph) -> Vec<u32> {
+    let mut components = vec![u32::MAX; graph.map_size()];
+    let mut count = 0;
+    for root in 0..graph.map_size() as Node {
+        if components[root as usize] != u32::MAX || graph.neighbors(root).len() == 0 {
+            continue;
+        }
+        components[root as usize] = count;
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            for (child, _) in graph.neighbors(node) {
+                if components[*child as usize] == u32::MAX {
+                    components[*child as usize] = count;
+                    stack.push(*child);
+                }
+            }
+        }
+        count += 1;
+    }
+    components
+}
@@ -0,0 +1,152 @@
+use super::astar::frontier::Frontier;
+use super::{until_final, Limits, State};
+use crate::printable::Printable;
+use crate::structures::{DistanceField, Graph};
+use crate::{Cost, Node, Path};
+
+/// Dijkstra's algorithm, or uniform-cost search.
+///
+/// Nodes are expanded in the order of their distance from the source, without a heuristic.
+/// Search either stops at a goal or runs until every reachable [`Node`] has been expanded,
+/// after which the result is a full [`DistanceField`].
+pub struct Dijkstra<'a> {
+    frontier: Frontier,
+    field: DistanceField,
+    closed: Vec<bool>,
+    limits: Limits,
+    pub expansions: usize,
+    start: Node,
+    goal: Option<Node>,
+    graph: &'a Graph,
+}
+
+impl<'a> Dijkstra<'a> {
+    /// Create solver of a problem for a graph
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        let mut dijkstra = Self::exhaustive(start, graph);
+        dijkstra.goal = Some(goal);
+        dijkstra
+    }
+
+    /// Create solver without a goal. Search runs until every reachable [`Node`] is expanded.
+    #[must_use]
+    pub fn exhaustive(source: Node, graph: &'a Graph) -> Self {
+        let size = graph.map_size();
+
+        Dijkstra {
+            frontier: Frontier::new(source, size),
+            field: DistanceField::new(source, size, graph.get_width()),
+            closed: vec![false; size],
+            limits: Limits::default(),
+            expansions: 0,
+            start: source,
+            goal: None,
+            graph,
+        }
+    }
+
+    /// Distances from `source` to every [`Node`] of the graph
+    #[must_use]
+    pub fn distance_field(source: Node, graph: &'a Graph) -> DistanceField {
+        let mut dijkstra = Self::exhaustive(source, graph);
+        while let State::Processing(_) = dijkstra.progress() {}
+        dijkstra.field
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step.
+    /// Without a goal, [`State::NotFound`] is returned once every reachable [`Node`] is expanded.
+    pub fn progress(&mut self) -> State {
+        // Frontier may have outdated duplicates of already expanded nodes
+        let Some(node) =
+            std::iter::from_fn(|| self.frontier.pop()).find(|node| !self.closed[*node as usize])
+        else {
+            return State::NotFound;
+        };
+        self.closed[node as usize] = true;
+
+        if Some(node) == self.goal {
+            let path = self.field.path_to(node).expect("Goal has been reached");
+            return State::Finished((path, self.field.get(node)));
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let current_cost = self.field.get(node);
+        for (child, w) in self.graph.neighbors(node) {
+            let cost = current_cost + w;
+            if cost < self.field.get(*child) {
+                self.field.set(*child, cost, node);
+                self.frontier.push(*child, cost);
+            }
+        }
+        State::Processing(node)
+    }
+
+    /// Get distances found so far
+    #[must_use]
+    pub fn field(&self) -> &DistanceField {
+        &self.field
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, _) in self.closed.iter().enumerate().filter(|(_, c)| **c) {
+            print.add_inclosed(i.try_into().unwrap());
+        }
+        self.frontier
+            .iter()
+            .filter(|n| !self.closed[**n as usize])
+            .for_each(|n| print.add_infrontier(*n));
+
+        print.add_header("|Open|", self.frontier.size());
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        if let Some(goal) = self.goal {
+            print.add_goal(goal);
+        }
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.field.get(node)
+    }
+
+    /// Get current estimate of a [`Node`], which is the same as its cost
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.field.get(node)
+    }
+
+    /// Get size of [`Frontier`]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.frontier.size()
+    }
+}
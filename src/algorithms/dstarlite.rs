@@ -0,0 +1,361 @@
+use super::{until_final, Heuristic, Limits, State};
+use crate::edits::{Edit, Edits};
+use crate::printable::Printable;
+use crate::structures::{Graph, Grid};
+use crate::{Cost, Node, Path};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::time::{Duration, Instant};
+
+/// Difference in keys that is considered as rounding error
+const TOLERANCE: Cost = 0.001;
+
+/// Priority of a [`Node`] in D* Lite. Lower keys are ordered as greater, so `BinaryHeap` is a minimum heap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Key {
+    key: (Cost, Cost),
+    node: Node,
+}
+
+impl Eq for Key {}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .key
+            .0
+            .total_cmp(&self.key.0)
+            .then(other.key.1.total_cmp(&self.key.1))
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Outcome of one replan while replaying [`Edits`]
+pub struct Replan {
+    pub number: usize,
+    pub solution: Option<(Path, Cost)>,
+    pub changed_cells: usize,
+    pub expansions: usize,
+    pub duration: Duration,
+}
+
+/// D* Lite incremental pathfinder.
+///
+/// Searches backward from the goal, so costs are distances to the goal and stay valid when start moves.
+/// Solver owns a copy of the [`Graph`] and keeps its state between queries.
+/// When cells change with [`DStarLite::set_passable`], only the affected nodes are queued again,
+/// and the next [`DStarLite::replan`] repairs the search from them instead of starting over.
+pub struct DStarLite {
+    heap: BinaryHeap<Key>,
+    queued: Vec<Option<(Cost, Cost)>>,
+    g: Vec<Cost>,
+    rhs: Vec<Cost>,
+    heuristic: Heuristic,
+    km: Cost,
+    limits: Limits,
+    pub expansions: usize,
+    start: Node,
+    goal: Node,
+    graph: Graph,
+}
+
+impl DStarLite {
+    /// Create solver of a problem. The graph is copied, so that it can be changed later.
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &Graph) -> Self {
+        let size = graph.map_size();
+        let mut dstar = DStarLite {
+            heap: BinaryHeap::new(),
+            queued: vec![None; size],
+            g: vec![f32::INFINITY; size],
+            rhs: vec![f32::INFINITY; size],
+            heuristic: Heuristic::new(start, graph.get_width()),
+            km: 0.0,
+            limits: Limits::default(),
+            expansions: 0,
+            start,
+            goal,
+            graph: graph.clone(),
+        };
+        dstar.rhs[goal as usize] = 0.0;
+        dstar.queue(goal);
+
+        dstar
+    }
+
+    /// Stop the search early when one of `limits` is reached.
+    /// Expansions are counted over every replan
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(mut self) -> Option<(Path, Cost)> {
+        self.replan()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        self.replan_limited()
+    }
+
+    /// Repair the search after changes and provide the current shortest path
+    pub fn replan(&mut self) -> Option<(Path, Cost)> {
+        self.replan_limited().solution()
+    }
+
+    /// Repair the search after changes and tell how the repair ended
+    pub fn replan_limited(&mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        let Some(top) = self.top() else {
+            return self.finish();
+        };
+        // Keys are sums of floats, so nodes with almost the same key as start are still expanded
+        let (k1, k2) = self.key(self.start);
+        if (top.key.0 - TOLERANCE, top.key.1) >= (k1, k2)
+            && self.rhs[self.start as usize] == self.g[self.start as usize]
+        {
+            return self.finish();
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        let node = top.node;
+
+        let key = self.key(node);
+        if top.key < key {
+            self.queue(node);
+        } else if self.g[node as usize] > self.rhs[node as usize] {
+            self.g[node as usize] = self.rhs[node as usize];
+            self.queued[node as usize] = None;
+            self.expansions += 1;
+            self.update_neighbors(node);
+        } else {
+            self.g[node as usize] = f32::INFINITY;
+            self.expansions += 1;
+            self.update_vertex(node);
+            self.update_neighbors(node);
+        }
+        State::Processing(node)
+    }
+
+    /// Report the path, if there is one
+    fn finish(&self) -> State {
+        match self.construct_path() {
+            Some(solution) => State::Finished(solution),
+            None => State::NotFound,
+        }
+    }
+
+    /// Change passability of a cell. Nodes whose neighbors change are queued for the next replan.
+    pub fn set_passable(&mut self, node: Node, passable: bool) {
+        if self.graph.get(node as i32) == passable {
+            return;
+        }
+        self.graph.set_passable(node, passable);
+        if !passable {
+            self.g[node as usize] = f32::INFINITY;
+        }
+        self.update_vertex(node);
+        // Diagonal moves between the neighbors may have changed too, so every cell around is updated
+        let around: Vec<Node> = self.around(node).collect();
+        for n in around {
+            self.update_vertex(n);
+        }
+    }
+
+    /// Move start to a new [`Node`]. Queued keys stay valid by adding the distance moved to them.
+    pub fn move_start(&mut self, start: Node) {
+        self.km += self.heuristic.calc(start);
+        self.start = start;
+        self.heuristic = Heuristic::new(start, self.graph.get_width());
+    }
+
+    /// Apply edits one by one and replan when requested.
+    /// `report` is called with the solver and the outcome of every replan.
+    pub fn replay(&mut self, edits: &Edits, mut report: impl FnMut(&Self, Replan)) {
+        let mut changed_cells = 0;
+        let mut number = 0;
+        let width = self.graph.get_width();
+
+        for edit in edits.iter() {
+            match (edit, edit.node(width)) {
+                (Edit::Block(..), Some(node)) => {
+                    self.set_passable(node, false);
+                    changed_cells += 1;
+                }
+                (Edit::Free(..), Some(node)) => {
+                    self.set_passable(node, true);
+                    changed_cells += 1;
+                }
+                (Edit::Move(..), Some(node)) => self.move_start(node),
+                _ => {
+                    number += 1;
+                    let expansions = self.expansions;
+                    let now = Instant::now();
+                    let solution = self.replan();
+                    report(
+                        self,
+                        Replan {
+                            number,
+                            solution,
+                            changed_cells,
+                            expansions: self.expansions - expansions,
+                            duration: now.elapsed(),
+                        },
+                    );
+                    changed_cells = 0;
+                }
+            }
+        }
+    }
+
+    /// Recalculate the right hand side value of a [`Node`] and queue it if it is inconsistent
+    fn update_vertex(&mut self, node: Node) {
+        if node != self.goal {
+            self.rhs[node as usize] = self
+                .graph
+                .neighbors(node)
+                .map(|(n, w)| w + self.g[*n as usize])
+                .fold(f32::INFINITY, f32::min);
+        }
+        if self.g[node as usize] == self.rhs[node as usize] {
+            self.queued[node as usize] = None;
+        } else {
+            self.queue(node);
+        }
+    }
+
+    /// Update every neighbor of a [`Node`]
+    fn update_neighbors(&mut self, node: Node) {
+        let neighbors: Vec<Node> = self.graph.neighbors(node).map(|(n, _)| *n).collect();
+        for n in neighbors {
+            self.update_vertex(n);
+        }
+    }
+
+    /// Cells around a [`Node`] inside the map, whether they are passable or not
+    fn around(&self, node: Node) -> impl Iterator<Item = Node> {
+        let width = self.graph.get_width() as i32;
+        let height = self.graph.get_height() as i32;
+        let (x, y) = (node as i32 % width, node as i32 / width);
+
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|d| *d != (0, 0))
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(move |(x, y)| *x >= 0 && *y >= 0 && *x < width && *y < height)
+            .map(move |(x, y)| (x + y * width) as Node)
+    }
+
+    /// Priority of a [`Node`]
+    fn key(&self, node: Node) -> (Cost, Cost) {
+        let min = self.g[node as usize].min(self.rhs[node as usize]);
+        (min + self.heuristic.calc(node) + self.km, min)
+    }
+
+    /// Put a [`Node`] to the queue with its current key. Earlier entries become outdated.
+    fn queue(&mut self, node: Node) {
+        let key = self.key(node);
+        self.queued[node as usize] = Some(key);
+        self.heap.push(Key { key, node });
+    }
+
+    /// Smallest key in the queue. Outdated entries are discarded from the top.
+    fn top(&mut self) -> Option<Key> {
+        while let Some(top) = self.heap.peek() {
+            if self.queued[top.node as usize] == Some(top.key) {
+                return Some(*top);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    /// Follow the cheapest neighbors from start to goal
+    fn construct_path(&self) -> Option<(Path, Cost)> {
+        let cost = self.rhs[self.start as usize];
+        if cost == f32::INFINITY {
+            return None;
+        }
+
+        let mut path = vec![self.start];
+        while path[path.len() - 1] != self.goal && path.len() <= self.g.len() {
+            let (next, _) = self
+                .graph
+                .neighbors(path[path.len() - 1])
+                .map(|(n, w)| (*n, w + self.g[*n as usize]))
+                .min_by(|a, b| a.1.total_cmp(&b.1))?;
+            path.push(next);
+        }
+        Some((path, cost))
+    }
+
+    /// Get the current graph with all changes applied
+    #[must_use]
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, g) in self.g.iter().enumerate() {
+            let node = i.try_into().unwrap();
+            // Cells may have been changed since the map was loaded
+            if !self.graph.get(node as i32) {
+                print.add_wall(node);
+                continue;
+            }
+            print.add_open(node);
+            if self.queued[i].is_some() {
+                print.add_infrontier(node);
+            } else if g.is_finite() {
+                print.add_inclosed(node);
+            }
+        }
+
+        print.add_header(
+            "|Queue|",
+            self.queued.iter().filter(|q| q.is_some()).count(),
+        );
+        print.add_header("Expanded", self.expansions);
+        print.add_header("km", self.km);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`], which is its distance to the goal
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.g[node as usize]
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.key(node).0
+    }
+
+    /// Get size of the queue, including outdated entries
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+}
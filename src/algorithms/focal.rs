@@ -0,0 +1,244 @@
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::collections::BTreeSet;
+use std::ops::Bound::{Excluded, Included};
+
+/// Key of a [`Node`] in an ordered set.
+/// Costs are never negative, so their bits are ordered the same way as the costs themselves.
+type Key = (u32, Node);
+
+fn key(cost: Cost, node: Node) -> Key {
+    (cost.to_bits(), node)
+}
+
+/// Focal search cache value. Heuristic is calculated once.
+#[derive(Clone, Copy)]
+struct Value {
+    parent: Node,
+    cost: Cost,
+    heuristic: Cost,
+    open: bool,
+}
+
+impl Default for Value {
+    fn default() -> Self {
+        Value {
+            parent: 0,
+            cost: f32::INFINITY,
+            heuristic: f32::MAX,
+            open: false,
+        }
+    }
+}
+
+/// Focal search (A*ε) pathfinder.
+///
+/// Open list is ordered by f = g + h like in A*.
+/// Every open [`Node`] with f at most `weight` times the smallest f is also in the focal list,
+/// which is ordered by the distance to goal.
+/// Nodes are always expanded from the focal list, so the search heads straight for the goal
+/// while the found path stays at most `weight` times longer than optimal.
+pub struct FocalSearch<'a> {
+    open: BTreeSet<Key>,
+    focal: BTreeSet<Key>,
+    values: Vec<Value>,
+    heuristic: Heuristic,
+    pub weight: f32,
+    pub bound: Cost,
+    limits: Limits,
+    pub expansions: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> FocalSearch<'a> {
+    /// Create solver of a problem for a graph with suboptimality bound `weight`
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, weight: f32) -> Self {
+        let mut search = FocalSearch {
+            open: BTreeSet::new(),
+            focal: BTreeSet::new(),
+            values: vec![Value::default(); graph.map_size()],
+            heuristic: Heuristic::new(goal, graph.get_width()),
+            weight,
+            bound: 0.0,
+            limits: Limits::default(),
+            expansions: 0,
+            start,
+            goal,
+            graph,
+        };
+        search.values[start as usize].parent = start;
+        search.values[start as usize].cost = 0.0;
+        search.insert(start);
+        search.update_bound();
+
+        search
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        // Focal list always holds the node with the smallest f, so it is empty only with the open list
+        let Some(&(_, node)) = self.focal.first() else {
+            return State::NotFound;
+        };
+        self.remove(node);
+
+        if node == self.goal {
+            return State::Finished(self.construct_path());
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let current_cost = self.values[node as usize].cost;
+        for (child, w) in self.graph.neighbors(node) {
+            let cost = current_cost + w;
+            if cost < self.values[*child as usize].cost {
+                if self.values[*child as usize].open {
+                    self.remove(*child);
+                }
+                self.values[*child as usize].cost = cost;
+                self.values[*child as usize].parent = node;
+                self.insert(*child);
+            }
+        }
+        self.update_bound();
+
+        State::Processing(node)
+    }
+
+    /// Get heuristic value from cache or calculate it
+    fn get_heuristic(&mut self, node: Node) -> Cost {
+        if self.values[node as usize].heuristic == f32::MAX {
+            self.values[node as usize].heuristic = self.heuristic.calc(node);
+        }
+        self.values[node as usize].heuristic
+    }
+
+    /// Add a [`Node`] to the open list and to the focal list if it is within the bound
+    fn insert(&mut self, node: Node) {
+        let heuristic = self.get_heuristic(node);
+        let estimate = self.get_estimate(node);
+
+        self.open.insert(key(estimate, node));
+        if estimate <= self.bound {
+            self.focal.insert(key(heuristic, node));
+        }
+        self.values[node as usize].open = true;
+    }
+
+    /// Remove a [`Node`] from both lists
+    fn remove(&mut self, node: Node) {
+        let estimate = self.get_estimate(node);
+        let heuristic = self.values[node as usize].heuristic;
+
+        self.open.remove(&key(estimate, node));
+        self.focal.remove(&key(heuristic, node));
+        self.values[node as usize].open = false;
+    }
+
+    /// Move the bound to `weight` times the smallest f and move nodes between the lists accordingly
+    fn update_bound(&mut self) {
+        let Some(&(f_min, _)) = self.open.first() else {
+            return;
+        };
+        let bound = self.weight * f32::from_bits(f_min);
+
+        let (low, high) = if bound > self.bound {
+            (self.bound, bound)
+        } else {
+            (bound, self.bound)
+        };
+        let range = (
+            Excluded(key(low, Node::MAX)),
+            Included(key(high, Node::MAX)),
+        );
+        for &(_, node) in self.open.range(range) {
+            let heuristic = key(self.values[node as usize].heuristic, node);
+            if bound > self.bound {
+                self.focal.insert(heuristic);
+            } else {
+                self.focal.remove(&heuristic);
+            }
+        }
+        self.bound = bound;
+    }
+
+    /// Reconstruct path that was found
+    fn construct_path(&self) -> (Path, Cost) {
+        let mut path = vec![self.goal];
+        while path[path.len() - 1] != self.start {
+            path.push(self.values[path[path.len() - 1] as usize].parent);
+        }
+        path.reverse();
+
+        (path, self.values[self.goal as usize].cost)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.cost < f32::INFINITY && !v.open)
+            .for_each(|(i, _)| print.add_inclosed(i.try_into().unwrap()));
+        self.open.iter().for_each(|(_, n)| print.add_infrontier(*n));
+        self.focal.iter().for_each(|(_, n)| print.add_inopen(*n));
+        if let Some((_, first)) = self.focal.first() {
+            print.add_first(*first);
+        }
+
+        print.add_header("|Open|", self.open.len());
+        print.add_header("|Focal|", self.focal.len());
+        print.add_header("Bound", self.bound);
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.values[node as usize].cost
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.values[node as usize].cost + self.values[node as usize].heuristic
+    }
+
+    /// Get size of the open list
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.open.len()
+    }
+}
@@ -1,11 +1,14 @@
 use self::action::Action;
-use self::bucket::Bucket;
+use self::bucket::{Bucket, BucketLayout};
 use self::buckets::Buckets;
 use self::cache::Value;
 use self::fringe::Fringe;
 use self::indexes::Indexes;
+use super::tie_break::{TieBreak, Ties};
 use super::Heuristic;
+use super::Limits;
 use super::State;
+use super::Workspace;
 
 use crate::printable::Printable;
 use crate::structures::Graph;
@@ -15,7 +18,9 @@ use crate::Path;
 
 /// Enum for representing an action for some [`Node`]
 pub mod action;
-/// Enum for accessing a bucket in [`Fringe`]
+/// Fringe search from both ends at the same time
+pub mod bidirectional;
+/// Index of a bucket in [`Fringe`] and the layout of buckets
 pub mod bucket;
 /// Fringe's internal representation of Nodes
 pub mod buckets;
@@ -25,6 +30,8 @@ pub mod cache;
 pub mod fringe;
 /// Auxilliary structure for holding and modifying bucket-indexes
 pub mod indexes;
+/// Fringe search that processes the Now-list with many threads
+pub mod parallel;
 
 /// Fringe search implementation.
 ///
@@ -49,13 +56,97 @@ impl<'a> FringeSearch<'a> {
         }
     }
 
+    /// Initialize the search like [`FringeSearch::new`], but reuse the buffers of `workspace`.
+    /// Solve with [`FringeSearch::solve_in`] to give them back.
+    #[must_use]
+    pub fn in_workspace(
+        start: Node,
+        goal: Node,
+        graph: &'a Graph,
+        workspace: &mut Workspace,
+    ) -> Self {
+        let heuristic = Heuristic::new(goal, graph.get_width());
+        Self::with_heuristic_in(start, goal, graph, heuristic, workspace)
+    }
+
+    /// Initialize the search with a heuristic, like the ALT heuristic of [`Heuristic::with_landmarks`]
+    #[must_use]
+    pub fn with_heuristic(start: Node, goal: Node, graph: &'a Graph, heuristic: Heuristic) -> Self {
+        Self::with_heuristic_in(start, goal, graph, heuristic, &mut Workspace::default())
+    }
+
+    /// Initialize the search like [`FringeSearch::with_heuristic`], but reuse the buffers of `workspace`
+    #[must_use]
+    pub fn with_heuristic_in(
+        start: Node,
+        goal: Node,
+        graph: &'a Graph,
+        heuristic: Heuristic,
+        workspace: &mut Workspace,
+    ) -> Self {
+        let fringe = Fringe::in_workspace(start, goal, graph, heuristic, workspace);
+
+        FringeSearch {
+            fringe,
+            start,
+            goal,
+        }
+    }
+
+    /// Order siblings in the Now-list by `tie_break`. `seed` is used by random tie-breaking.
+    /// See [`Fringe::with_tie_break`].
+    #[must_use]
+    pub fn with_tie_break(mut self, tie_break: TieBreak, seed: u64) -> Self {
+        self.fringe = self.fringe.with_tie_break(Ties::new(tie_break, seed));
+        self
+    }
+
+    /// Split estimates into buckets by `layout` instead of the one laid out for the graph.
+    /// See [`Fringe::with_buckets`].
+    #[must_use]
+    pub fn with_buckets(mut self, layout: BucketLayout) -> Self {
+        self.fringe = self.fringe.with_buckets(layout);
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.fringe.limits = limits;
+        self
+    }
+
     /// Solve from start to goal. Returns `Some((path,cost))` if it can be found, `None` if path can't be found.
     /// Does not leak internal state and lets [`Fringe`] do some optimizations.
     #[must_use]
     pub fn solve(mut self) -> Option<(Path, Cost)> {
+        self.fringe.run().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
         self.fringe.run()
     }
 
+    /// Solve like [`FringeSearch::solve_limited`] and give the buffers back to `workspace`
+    #[must_use]
+    pub fn solve_in(mut self, workspace: &mut Workspace) -> State {
+        let state = self.run();
+        self.into_workspace(workspace);
+        state
+    }
+
+    /// Solve like [`FringeSearch::solve_limited`], but keep the search so that its expansions can be read afterwards
+    pub fn run(&mut self) -> State {
+        self.fringe.run()
+    }
+
+    /// Give the buffers back to `workspace`
+    pub fn into_workspace(self, workspace: &mut Workspace) {
+        self.fringe.into_workspace(workspace);
+    }
+
     /// One step of the solving process. This is used for getting the state of [`Fringe`] at each step of solving process
     pub fn progress(&mut self) -> State {
         self.fringe.act()
@@ -91,7 +182,6 @@ impl<'a> FringeSearch<'a> {
             .for_each(|n| print.add_first(*n));
 
         self.fringe
-            .cache
             .cache
             .iter()
             .enumerate()
@@ -103,6 +193,7 @@ impl<'a> FringeSearch<'a> {
 
         print.add_header("f_limit", self.fringe.f_limit);
         print.add_header("f_min", self.fringe.f_min);
+        print.add_header("Expanded", self.fringe.expansions);
         print.add_spacing();
 
         print
@@ -117,7 +208,7 @@ impl<'a> FringeSearch<'a> {
         self.fringe.cache.get_estimate(node)
     }
 
-    pub fn fringe(&self) -> &Fringe {
+    pub fn fringe(&self) -> &Fringe<'_> {
         &self.fringe
     }
 }
@@ -0,0 +1,205 @@
+use super::{BucketLayout, Fringe};
+use crate::algorithms::{until_final, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+
+/// Bidirectional Fringe search.
+///
+/// Runs one [`Fringe`] forward from start and another backward from goal, each with its own
+/// [`Buckets`](super::Buckets), [`Cache`](super::cache::Cache) and f-limits.
+/// Graph is assumed to be symmetric, so backward search uses the same neighbors.
+/// Side with fewer expansions is advanced on each step.
+///
+/// Every time a [`Node`] is reached with a better cost, it is checked against the other side,
+/// and the best meeting cost μ is kept up to date.
+/// Every node left in a fringe has an estimate of at least its `f_limit`,
+/// so search stops when `f_limit` of either side is at least μ.
+/// Expansions of both sides count towards the [`Limits`].
+pub struct BidirectionalFringe<'a> {
+    pub forward: Fringe<'a>,
+    pub backward: Fringe<'a>,
+    limits: Limits,
+    best: Cost,
+    meeting: Option<Node>,
+    start: Node,
+    goal: Node,
+}
+
+impl<'a> BidirectionalFringe<'a> {
+    /// Create solver of a problem for a graph
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        BidirectionalFringe {
+            forward: Fringe::new(start, goal, graph),
+            backward: Fringe::new(goal, start, graph),
+            limits: Limits::default(),
+            best: f32::INFINITY,
+            meeting: None,
+            start,
+            goal,
+        }
+    }
+
+    /// Split estimates of both sides into buckets by `layout`.
+    /// See [`Fringe::with_buckets`].
+    #[must_use]
+    pub fn with_buckets(mut self, layout: BucketLayout) -> Self {
+        self.forward = self.forward.with_buckets(layout);
+        self.backward = self.backward.with_buckets(layout);
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(mut self) -> Option<(Path, Cost)> {
+        self.run().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        self.run()
+    }
+
+    /// Run through the whole solving process. Leaves the search available for inspection.
+    pub fn run(&mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step of either side
+    pub fn progress(&mut self) -> State {
+        if self.start == self.goal {
+            return State::Finished((vec![self.start], 0.0));
+        }
+        if self.meeting.is_some() && self.forward.f_limit.max(self.backward.f_limit) >= self.best {
+            return State::Finished(self.construct_path());
+        }
+        let expansions = self.forward.expansions + self.backward.expansions;
+        if let Some(reason) = self.limits.check(expansions) {
+            return State::Aborted(reason);
+        }
+
+        let forward = self.forward.expansions <= self.backward.expansions;
+        let (this, other) = if forward {
+            (&mut self.forward, &self.backward)
+        } else {
+            (&mut self.backward, &self.forward)
+        };
+
+        let (best, meeting) = (&mut self.best, &mut self.meeting);
+        let state = this.act_with(|node, cost| {
+            let total = cost + other.cache.get_cost(node);
+            if total < *best {
+                *best = total;
+                *meeting = Some(node);
+            }
+        });
+
+        match state {
+            // Either side alone finds an optimal path, backward one only needs to be turned around
+            State::Finished((mut path, cost)) => {
+                if !forward {
+                    path.reverse();
+                }
+                State::Finished((path, cost))
+            }
+            // Exhausted side has reached every node it can, so the best meeting is optimal
+            State::NotFound if self.meeting.is_some() => State::Finished(self.construct_path()),
+            state => state,
+        }
+    }
+
+    /// Join paths of both searches at the meeting [`Node`]
+    fn construct_path(&self) -> (Path, Cost) {
+        let meeting = self.meeting.expect("Searches have not met");
+        let mut path = path_from(&self.forward, meeting);
+        path.reverse();
+        path.extend(path_from(&self.backward, meeting).iter().skip(1));
+
+        (path, self.best)
+    }
+
+    /// Number of expanded nodes in forward and backward direction
+    #[must_use]
+    pub fn expansions(&self) -> (usize, usize) {
+        (self.forward.expansions, self.backward.expansions)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        closed(&self.forward).for_each(|n| print.add_inclosed(n));
+        closed(&self.backward).for_each(|n| print.add_oldlater(n));
+        self.forward
+            .buckets
+            .all()
+            .iter()
+            .flatten()
+            .filter(|n| !self.forward.cache[**n].closed)
+            .for_each(|n| print.add_infrontier(*n));
+        self.backward
+            .buckets
+            .all()
+            .iter()
+            .flatten()
+            .filter(|n| !self.backward.cache[**n].closed)
+            .for_each(|n| print.add_inlater(*n));
+        if let Some(meeting) = self.meeting {
+            print.add_first(meeting);
+        }
+
+        print.add_header("f_limit →", self.forward.f_limit);
+        print.add_header("f_limit ←", self.backward.f_limit);
+        print.add_header("Expanded →", self.forward.expansions);
+        print.add_header("Expanded ←", self.backward.expansions);
+        print.add_header("μ", self.best);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Search that has expanded a [`Node`], forward search is preferred
+    fn side(&self, node: Node) -> &Fringe<'a> {
+        if self.backward.cache[node].closed && !self.forward.cache[node].closed {
+            &self.backward
+        } else {
+            &self.forward
+        }
+    }
+
+    /// Get current cost of a [`Node`] from the side that has expanded it
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.side(node).cache.get_cost(node)
+    }
+
+    /// Get current estimate of a [`Node`] from the side that has expanded it
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.side(node).cache[node].estimate
+    }
+}
+
+/// Path from origin of a fringe to `node`, starting from `node`
+fn path_from(fringe: &Fringe<'_>, node: Node) -> Path {
+    let mut path = vec![node];
+    while path[path.len() - 1] != fringe.cache.start {
+        path.push(fringe.cache[path[path.len() - 1]].parent);
+    }
+    path
+}
+
+/// Nodes that a fringe has closed
+fn closed<'b>(fringe: &'b Fringe<'_>) -> impl Iterator<Item = Node> + 'b {
+    (0..fringe.cache.len() as Node).filter(|n| fringe.cache[*n].closed)
+}
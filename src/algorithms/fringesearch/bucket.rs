@@ -1,107 +1,144 @@
-/// Enum for indexing to a correct bucket.
-/// Main idea is that a float is converted to an integer and then taken a modulo 8 is taken from that.
-/// This is an enum to clarify the finite values it can hold.
+use crate::structures::Graph;
+use crate::Cost;
+use anyhow::bail;
+
+/// Index for accessing a bucket in [`Buckets`](super::Buckets).
+/// Indexes wrap around after the last bucket, just like modulo works.
 ///
 ///```
-/// # use fringe_vs_astar::algorithms::fringesearch::bucket::Bucket;
-/// let mut bucket = Bucket::from(14.4 as f32);
-/// assert_eq!(Bucket::Six, bucket);
+/// # use fringe_vs_astar::algorithms::fringesearch::bucket::{Bucket, BucketLayout};
+/// let layout = BucketLayout::for_growth(5.0, 1.0);
+/// let mut bucket = layout.bucket(14.4);
+/// assert_eq!(Bucket::new(6, 8), bucket);
 ///
-/// bucket = bucket.add(); // Bucket::Seven
-/// bucket = bucket.add(); // Bucket::Zero
+/// bucket = bucket.add(); // 7
+/// bucket = bucket.add(); // 0
 /// assert_eq!(0 as usize, bucket.into());
-///
+/// assert_eq!(7 as usize, bucket.sub().into());
 ///```
-
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
-pub enum Bucket {
-    Zero,
-    One,
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    None,
+pub struct Bucket {
+    index: usize,
+    count: usize,
 }
 
 impl Bucket {
-    /// Add 1 to the value, wrap to Zero if it was Seven (just like modulo works)
+    /// Bucket at `index` of `count` buckets
     #[must_use]
-    pub fn add(&self) -> Bucket {
-        match self {
-            Bucket::Zero => Bucket::One,
-            Bucket::One => Bucket::Two,
-            Bucket::Two => Bucket::Three,
-            Bucket::Three => Bucket::Four,
-            Bucket::Four => Bucket::Five,
-            Bucket::Five => Bucket::Six,
-            Bucket::Six => Bucket::Seven,
-            Bucket::Seven => Bucket::Zero,
-            Bucket::None => Bucket::None,
+    pub fn new(index: usize, count: usize) -> Bucket {
+        Bucket {
+            index: index % count,
+            count,
         }
     }
 
-    pub fn sub(&self) -> Bucket {
-        match self {
-            Bucket::Zero => Bucket::Seven,
-            Bucket::One => Bucket::Zero,
-            Bucket::Two => Bucket::One,
-            Bucket::Three => Bucket::Two,
-            Bucket::Four => Bucket::Three,
-            Bucket::Five => Bucket::Four,
-            Bucket::Six => Bucket::Five,
-            Bucket::Seven => Bucket::Six,
-            Bucket::None => Bucket::None,
-        }
+    /// Add 1 to the index, wrap to 0 if it was the last bucket
+    #[must_use]
+    pub fn add(&self) -> Bucket {
+        Bucket::new(self.index + 1, self.count)
     }
-    pub const SIZE: usize = 8;
-}
 
-impl From<f32> for Bucket {
-    fn from(value: f32) -> Self {
-        match (value as u32) % 8 {
-            0 => Bucket::Zero,
-            1 => Bucket::One,
-            2 => Bucket::Two,
-            3 => Bucket::Three,
-            4 => Bucket::Four,
-            5 => Bucket::Five,
-            6 => Bucket::Six,
-            7 => Bucket::Seven,
-            _ => Bucket::None,
-        }
+    /// Subtract 1 from the index, wrap to the last bucket if it was 0
+    #[must_use]
+    pub fn sub(&self) -> Bucket {
+        Bucket::new(self.index + self.count - 1, self.count)
     }
 }
 
 impl From<Bucket> for usize {
     fn from(bucket: Bucket) -> Self {
-        match bucket {
-            Bucket::Zero => 0,
-            Bucket::One => 1,
-            Bucket::Two => 2,
-            Bucket::Three => 3,
-            Bucket::Four => 4,
-            Bucket::Five => 5,
-            Bucket::Six => 6,
-            Bucket::Seven => 7,
-            Bucket::None => usize::MAX, // We want this to fail
-        }
+        bucket.index
     }
 }
-impl From<usize> for Bucket {
-    fn from(value: usize) -> Self {
-        match value % 8 {
-            0 => Bucket::Zero,
-            1 => Bucket::One,
-            2 => Bucket::Two,
-            3 => Bucket::Three,
-            4 => Bucket::Four,
-            5 => Bucket::Five,
-            6 => Bucket::Six,
-            7 => Bucket::Seven,
-            _ => Bucket::None,
+
+/// How estimates are split into buckets: an estimate goes to bucket `⌊estimate / width⌋ mod count`.
+///
+/// Estimates that are in buckets at the same time must not wrap around to the same bucket,
+/// and the bucket before the one of `f_limit` must stay empty, because it holds the Now-list.
+/// If estimates of found nodes are at most `growth` above `f_limit`, this holds with `⌊growth / width⌋ + 3` buckets.
+///
+/// With octile distance, estimates of neighbors differ by at most two diagonal moves,
+/// so 5 buckets of width 1 are enough.
+/// ```
+/// # use fringe_vs_astar::algorithms::fringesearch::bucket::BucketLayout;
+/// # use fringe_vs_astar::DIAGONAL_COST;
+/// let layout = BucketLayout::for_growth(2.0 * DIAGONAL_COST, 1.0);
+/// assert_eq!(layout.count(), 5);
+/// assert!(layout.holds(2.0 * DIAGONAL_COST));
+/// assert!(!BucketLayout::for_growth(1.0, 1.0).holds(2.0 * DIAGONAL_COST));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketLayout {
+    count: usize,
+    width: Cost,
+}
+
+impl BucketLayout {
+    /// Layout of `count` buckets of `width` for a graph.
+    /// Fails if the buckets can't hold the growth of estimates on the graph, see [`BucketLayout::growth`].
+    pub fn new(count: usize, width: Cost, graph: &Graph) -> anyhow::Result<BucketLayout> {
+        if count < 3 {
+            bail!("At least 3 buckets are needed, got {count}");
+        }
+        if !(width > 0.0 && width.is_finite()) {
+            bail!("Width of a bucket must be positive, got {width}");
+        }
+        let layout = BucketLayout { count, width };
+        let growth = Self::growth(graph);
+        if !layout.holds(growth) {
+            bail!(
+                "Estimates grow by up to {growth} on this graph, so buckets of width {width} need at least {} buckets",
+                Self::for_graph(graph, width).count()
+            );
+        }
+        Ok(layout)
+    }
+
+    /// Fewest buckets of `width` that hold estimates at most `growth` above `f_limit`
+    #[must_use]
+    pub fn for_growth(growth: Cost, width: Cost) -> BucketLayout {
+        BucketLayout {
+            count: (growth / width).floor() as usize + 3,
+            width,
         }
     }
+
+    /// Fewest buckets of `width` for a graph and a heuristic that changes at most by the weight of an edge
+    /// between neighbors, like octile distance and the ALT heuristic.
+    /// Estimate of a neighbor is then at most two edge weights larger.
+    #[must_use]
+    pub fn for_graph(graph: &Graph, width: Cost) -> BucketLayout {
+        Self::for_growth(Self::growth(graph), width)
+    }
+
+    /// Largest growth of an estimate from a node to its neighbor on a graph,
+    /// with a heuristic that changes at most by the weight of an edge
+    #[must_use]
+    pub fn growth(graph: &Graph) -> Cost {
+        2.0 * graph.max_edge_cost()
+    }
+
+    /// Check if estimates up to `growth` above `f_limit` are always put to the right bucket
+    #[must_use]
+    pub fn holds(&self, growth: Cost) -> bool {
+        growth < (self.count - 2) as Cost * self.width
+    }
+
+    /// Bucket of an estimate
+    #[must_use]
+    pub fn bucket(&self, estimate: Cost) -> Bucket {
+        Bucket::new((estimate / self.width) as usize, self.count)
+    }
+
+    /// Number of buckets
+    #[must_use]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Range of estimates in a bucket
+    #[must_use]
+    pub fn width(&self) -> Cost {
+        self.width
+    }
 }
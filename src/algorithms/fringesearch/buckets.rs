@@ -1,18 +1,19 @@
 use super::Bucket;
+use super::BucketLayout;
 use super::Indexes;
 use crate::Cost;
 use crate::Node;
 use std::fmt::Display;
 use std::ops::{Index, IndexMut};
 
-/// Store now, later, and inactive buckets in 8 Vecs and access them with indexes.
+/// Store now, later, and inactive buckets in one Vec and access them with indexes.
 ///
 /// Now-list is `Node`s found in current iteration.
-/// Later-list is `Node`s found in previous iterations, but which have same integer part as current `f_limit`.
-/// A bucket is a list that holds all found nodes that have the same integer part, such as 3.13 would go to `Bucket::Three`.
+/// Later-list is `Node`s found in previous iterations, but which are in the same bucket as current `f_limit`.
+/// A bucket is a list that holds all found nodes whose estimates are in the same range of the [`BucketLayout`],
+/// such as 3.13 would go to bucket 3 with buckets of width 1.
 ///
-/// With octile distance heuristic, every found node has an upper bound estimate of +4,
-/// This means that we can take an modulo 8 of an estimate and push it to an unique bucket.
+/// Layout has enough buckets that estimates of found nodes never wrap around to a bucket that is in use.
 /// Because at least the bucket before current one is empty, we can use it to store the Now-list.
 ///
 /// I am not sure if this really provides any real speedups, but I don't want to redo old work, so this stays.
@@ -27,42 +28,72 @@ pub struct Buckets {
     pub(crate) later_last: usize,
     pub(crate) indexes: Indexes,
     pub(crate) bucket_length: usize,
+    pub(crate) layout: BucketLayout,
+    /// Largest index that has been written in each bucket, so that only those are cleared on reset
+    extents: Vec<usize>,
 }
 
 impl Buckets {
     /// Create Buckets and initialize them.
     /// Every bucket contains only `None`s, but Now has start-node.
     /// `min_size` should be the size of graph (maximum amount of nodes to be discovered).
-    pub fn new(start: Node, f_limit: Cost, min_size: usize) -> Self {
-        let mut length = min_size.next_power_of_two();
-        length *= 2;
-        let mut buckets: Vec<Option<Node>> = vec![None; length];
-
-        let bucket_length = length / Bucket::SIZE;
-        let indexes = Indexes::new(bucket_length);
-
-        let current_bucket = Bucket::from(f_limit);
-        let later_last = indexes[current_bucket];
-        let later_head = later_last + 1;
-
-        let now_last = indexes[current_bucket.sub()];
-        buckets[now_last] = Some(start);
-
-        Buckets {
-            buckets,
-            current_bucket,
-            now_last,
-            later_head,
-            later_last,
+    /// Wider buckets hold more nodes, so they are made longer.
+    pub fn new(start: Node, f_limit: Cost, min_size: usize, layout: BucketLayout) -> Self {
+        let bucket_length = Self::bucket_length(min_size, layout);
+        let indexes = Indexes::new(bucket_length, layout.count());
+
+        let mut buckets = Buckets {
+            buckets: vec![None; bucket_length * layout.count()],
+            current_bucket: layout.bucket(f_limit),
+            now_last: 0,
+            later_head: 0,
+            later_last: 0,
+            extents: (0..layout.count()).map(|i| i * bucket_length).collect(),
             indexes,
             bucket_length,
+            layout,
+        };
+        buckets.start(start, f_limit);
+        buckets
+    }
+
+    /// Make Buckets ready for a new search, like [`Buckets::new`].
+    /// If the size and the layout are the same, only the parts of buckets that were written are cleared.
+    pub fn reset(&mut self, start: Node, f_limit: Cost, min_size: usize, layout: BucketLayout) {
+        if layout != self.layout || Self::bucket_length(min_size, layout) != self.bucket_length {
+            *self = Self::new(start, f_limit, min_size, layout);
+            return;
         }
+        for (i, extent) in self.extents.iter_mut().enumerate() {
+            let bucket_start = i * self.bucket_length;
+            self.buckets[bucket_start..=*extent].fill(None);
+            *extent = bucket_start;
+        }
+        self.indexes = Indexes::new(self.bucket_length, layout.count());
+        self.start(start, f_limit);
+    }
+
+    /// Length of a bucket for a graph of `min_size` nodes
+    fn bucket_length(min_size: usize, layout: BucketLayout) -> usize {
+        (min_size.next_power_of_two() / 4).max(1) * layout.width().ceil() as usize
+    }
+
+    /// Point Later to the bucket of `f_limit` and put start to Now
+    fn start(&mut self, start: Node, f_limit: Cost) {
+        self.current_bucket = self.layout.bucket(f_limit);
+        self.later_last = self.indexes[self.current_bucket];
+        self.later_head = self.later_last + 1;
+
+        self.now_last = self.indexes[self.current_bucket.sub()];
+        self.buckets[self.now_last] = Some(start);
     }
 
     /// Push a node to Now-list
     pub(crate) fn push_now(&mut self, node: Node) {
         self.now_last += 1;
         self.buckets[self.now_last] = Some(node);
+        let now = usize::from(self.current_bucket.sub());
+        self.extents[now] = self.extents[now].max(self.now_last);
     }
 
     /// Pop a node from Now or try to get it from Later.
@@ -122,6 +153,8 @@ impl Buckets {
         }
 
         self.buckets[self.indexes[bucket]] = Some(node);
+        let extent = &mut self.extents[usize::from(bucket)];
+        *extent = (*extent).max(self.indexes[bucket]);
     }
 
     /// Try to make Later a viable vector again. Returns if the operation succeeded.
@@ -144,19 +177,19 @@ impl Buckets {
     /// Rotate buckets until a viable one is found and refresh it.
     /// If all buckets are empty, then no path can be found and a `false` is returned.
     pub(crate) fn rotate(&mut self) -> bool {
-        for _ in 0..8 {
+        for _ in 0..self.layout.count() {
             if self.buckets[self.indexes[self.current_bucket]].is_some() {
                 self.refresh_index();
                 return true;
             }
             self.current_bucket = self.current_bucket.add();
         }
-        return false;
+        false
     }
 
     /// Bucket's default starting index
     fn bucket_start(&self, bucket: Bucket) -> usize {
-        self.bucket_length * bucket as usize
+        self.bucket_length * usize::from(bucket)
     }
 
     /// Reset Now, Later and the current bucket.
@@ -176,6 +209,12 @@ impl Buckets {
         self.now_last = self.bucket_start(self.current_bucket.sub());
     }
 
+    /// How estimates are split into buckets
+    #[must_use]
+    pub fn layout(&self) -> BucketLayout {
+        self.layout
+    }
+
     fn bucket(&self, bucket: Bucket) -> &[Option<Node>] {
         let start = self.bucket_length * Into::<usize>::into(bucket);
         &self.buckets[start..(start + self.bucket_length)]
@@ -185,7 +224,7 @@ impl Buckets {
         &self.buckets
     }
     pub fn now(&self) -> &[Option<Node>] {
-        let i = self.current_bucket.sub() as usize * self.bucket_length;
+        let i = usize::from(self.current_bucket.sub()) * self.bucket_length;
         &self.buckets[i..=self.now_last]
     }
     pub fn later(&self) -> &[Option<Node>] {
@@ -193,7 +232,7 @@ impl Buckets {
     }
     pub fn next_later(&self) -> &[Option<Node>] {
         let i = self.later_last + 1;
-        let j = self.current_bucket as usize * self.bucket_length + self.bucket_length;
+        let j = usize::from(self.current_bucket) * self.bucket_length + self.bucket_length;
         &self.buckets[i..j]
     }
 }
@@ -240,7 +279,7 @@ impl Display for Buckets {
             result.push_str(&format!(
                 "{:<5} {}\t{:?}\n",
                 i * self.bucket_length,
-                self.indexes[i.into()],
+                self.indexes[Bucket::new(i, self.layout.count())],
                 bucket.iter().map_while(|n| *n).collect::<Vec<_>>()
             ));
         }
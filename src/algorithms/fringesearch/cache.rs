@@ -1,13 +1,12 @@
 use super::Action;
 use super::Heuristic;
+use crate::algorithms::workspace::Buffer;
 use crate::Cost;
 use crate::Node;
 use crate::Path;
 
 use std::ops::{Index, IndexMut};
 
-use std::f32::INFINITY;
-
 /// Stored values for a single node in graph as wanted by Fringe Search.
 ///
 /// Cost, estimate and parent are updated as needed, heuristic is calculated only once
@@ -25,9 +24,9 @@ pub struct Value {
 impl Default for Value {
     fn default() -> Self {
         Value {
-            cost: INFINITY,
-            heuristic: INFINITY,
-            estimate: INFINITY,
+            cost: f32::INFINITY,
+            heuristic: f32::INFINITY,
+            estimate: f32::INFINITY,
             parent: 0,
             closed: false,
         }
@@ -39,8 +38,9 @@ impl Default for Value {
 /// This does the book keeping for every node and updates the values as needed.
 ///
 /// Cache can be indexed with a [`Node`]: `cache[node]` or `self[node]`.
+/// Values are kept in a [`Buffer`], which can be reused by the next search.
 pub struct Cache {
-    pub cache: Vec<Value>,
+    cache: Buffer<Value>,
     heuristic: Heuristic,
     pub iteration: u32,
     pub start: Node,
@@ -51,10 +51,22 @@ impl Cache {
     /// Initialize cache
     #[must_use]
     pub fn new(start: Node, goal: Node, size: usize, heuristic: Heuristic) -> Self {
-        let mut cache = vec![Value::default(); size];
-        cache[start as usize].cost = 0.0;
-        cache[start as usize].heuristic = heuristic.calc(start);
-        cache[start as usize].estimate = cache[start as usize].heuristic;
+        Self::from_buffer(start, goal, heuristic, Buffer::new(size))
+    }
+
+    /// Initialize cache, reusing a buffer of an earlier search
+    #[must_use]
+    pub fn from_buffer(
+        start: Node,
+        goal: Node,
+        heuristic: Heuristic,
+        mut cache: Buffer<Value>,
+    ) -> Self {
+        cache.reset();
+        let value = cache.first_mut(start as usize);
+        value.cost = 0.0;
+        value.heuristic = heuristic.calc(start);
+        value.estimate = value.heuristic;
         Cache {
             cache,
             heuristic,
@@ -64,15 +76,38 @@ impl Cache {
         }
     }
 
+    /// Give the buffer back for the next search
+    #[must_use]
+    pub fn into_buffer(self) -> Buffer<Value> {
+        self.cache
+    }
+
+    /// Number of nodes in the cache
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Check if the cache has no nodes
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Values of every node
+    pub fn iter(&self) -> impl Iterator<Item = &Value> {
+        self.cache.iter()
+    }
+
     /// Decide if [`Node`] should go to now or to later or if nothing should be done to it.
     ///
     /// Checks if a node is already closed, otherwise will check the estimate.
     /// Updates `self.f_limit` if needed.
     pub fn decide_action(&mut self, node: Node) -> Action {
         if self[node].closed {
-            return Action::Nothing;
+            Action::Nothing
         } else if node == self.goal {
-            return Action::Finish(self.construct_path());
+            Action::Finish(self.construct_path())
         } else {
             self[node].closed = true;
             Action::Process(node)
@@ -83,18 +118,20 @@ impl Cache {
     ///
     /// Also calculates `heuristic`, `estimate` and `bucket` in advance
     pub fn update(&mut self, node: Node, parent: Node, cost: Cost) -> Cost {
+        // The heuristic is written first, so that a node seen for the first time is remembered for the reset
+        let heuristic = self.get_heuristic(node);
         self[node].cost = cost;
         self[node].parent = parent;
         self[node].closed = false;
-        self[node].estimate = self.get_heuristic(node) + cost;
+        self[node].estimate = heuristic + cost;
 
         self[node].estimate
     }
 
     /// Get heuristic value from cache or calculate it
     pub fn get_heuristic(&mut self, node: Node) -> Cost {
-        if self[node].heuristic == INFINITY {
-            self[node].heuristic = self.heuristic.calc(node);
+        if self[node].heuristic == f32::INFINITY {
+            self.cache.first_mut(node as usize).heuristic = self.heuristic.calc(node);
         }
         self[node].heuristic
     }
@@ -145,12 +182,12 @@ impl Index<Node> for Cache {
     type Output = Value;
 
     fn index(&self, index: Node) -> &Self::Output {
-        &self.cache[index as usize]
+        self.cache.get(index as usize)
     }
 }
 
 impl IndexMut<Node> for Cache {
     fn index_mut(&mut self, index: Node) -> &mut Self::Output {
-        &mut self.cache[index as usize]
+        self.cache.get_mut(index as usize)
     }
 }
@@ -1,11 +1,12 @@
 use super::action::Action;
 use super::cache::Cache;
 use super::Bucket;
+use super::BucketLayout;
 use super::Buckets;
-use crate::algorithms::{Heuristic, State};
+use crate::algorithms::tie_break::Ties;
+use crate::algorithms::{Heuristic, Limits, State, Workspace};
 use crate::structures::Graph;
-use crate::{Cost, Node, Path};
-use std::f32::INFINITY;
+use crate::{Cost, Node};
 
 /// Datastructure used for storing nodes in Fringe search.
 /// Does not hold any data on existence of a node in fringe
@@ -18,28 +19,97 @@ pub struct Fringe<'a> {
     pub f_min: Cost,
     pub graph: &'a Graph,
     pub buckets: Buckets,
+    pub expansions: usize,
+    /// Order of siblings in the Now-list, if they are not pushed in the order they are found
+    pub ties: Option<Ties>,
+    /// Limits that stop the search early
+    pub limits: Limits,
+    /// Siblings waiting to be pushed to the Now-list in order of their keys
+    siblings: Vec<(u32, Node)>,
 }
 
 impl<'a> Fringe<'a> {
     /// Create new Fringe
     #[must_use]
     pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
-        let heuristic = Heuristic::new(goal, graph.get_width());
+        Self::with_heuristic(start, goal, graph, Heuristic::new(goal, graph.get_width()))
+    }
+
+    /// Create new Fringe that estimates with the supplied heuristic.
+    /// Buckets of width 1 are laid out for the largest edge cost of the graph.
+    #[must_use]
+    pub fn with_heuristic(start: Node, goal: Node, graph: &'a Graph, heuristic: Heuristic) -> Self {
+        Self::in_workspace(start, goal, graph, heuristic, &mut Workspace::default())
+    }
+
+    /// Create new Fringe that estimates with the supplied heuristic and reuses the buffers of `workspace`.
+    /// Buckets are laid out by the layout of the workspace, or for the graph like in [`Fringe::with_heuristic`].
+    /// Buffers are given back with [`Fringe::into_workspace`].
+    #[must_use]
+    pub fn in_workspace(
+        start: Node,
+        goal: Node,
+        graph: &'a Graph,
+        heuristic: Heuristic,
+        workspace: &mut Workspace,
+    ) -> Self {
         let f_limit = heuristic.calc(start);
-        let cache = Cache::new(start, goal, graph.map_size(), heuristic);
+        let buffer = workspace.take_fringe(graph.map_size());
+        let cache = Cache::from_buffer(start, goal, heuristic, buffer);
 
-        let buckets = Buckets::new(start, f_limit, graph.size());
+        let layout = workspace
+            .layout
+            .unwrap_or_else(|| BucketLayout::for_graph(graph, 1.0));
+        debug_assert!(layout.holds(BucketLayout::growth(graph)));
+        let buckets = match workspace.buckets.take() {
+            Some(mut buckets) => {
+                buckets.reset(start, f_limit, graph.size(), layout);
+                buckets
+            }
+            None => Buckets::new(start, f_limit, graph.size(), layout),
+        };
 
         Fringe {
             cache,
-            current: Bucket::from(f_limit),
+            current: layout.bucket(f_limit),
             f_limit,
-            f_min: INFINITY,
+            f_min: f32::INFINITY,
             graph,
             buckets,
+            expansions: 0,
+            ties: None,
+            limits: Limits::default(),
+            siblings: vec![],
         }
     }
 
+    /// Split estimates into buckets by `layout` instead of the one laid out for the graph.
+    /// This must be done before solving starts.
+    /// [`BucketLayout::new`] makes sure that the buckets hold estimates of neighbors on the graph.
+    #[must_use]
+    pub fn with_buckets(mut self, layout: BucketLayout) -> Self {
+        debug_assert!(layout.holds(BucketLayout::growth(self.graph)));
+        self.buckets
+            .reset(self.cache.start, self.f_limit, self.graph.size(), layout);
+        self.current = layout.bucket(self.f_limit);
+        self
+    }
+
+    /// Give the cache and buckets back to `workspace` for the next search
+    pub fn into_workspace(self, workspace: &mut Workspace) {
+        workspace.fringe = self.cache.into_buffer();
+        workspace.buckets = Some(self.buckets);
+    }
+
+    /// Order children that are pushed to the Now-list by `ties`.
+    /// Now-list is a stack, so only siblings found by the same expansion are reordered,
+    /// and by default the last found sibling is processed first.
+    #[must_use]
+    pub fn with_tie_break(mut self, ties: Ties) -> Self {
+        self.ties = Some(ties);
+        self
+    }
+
     /// Check if estimation of length is smaller than current limits and decide if anything should be done with the node.
     /// Updates f_min if needed.
     pub fn estimation_check(&mut self, node: Node) -> Action {
@@ -56,40 +126,62 @@ impl<'a> Fringe<'a> {
     /// Update f_limit and f_min, if a lower estimate was found this iteration.
     /// If this is the first passthrough of a bucket, it probably was not found.
     pub fn refresh_limit(&mut self) {
-        if self.f_min < INFINITY {
+        if self.f_min < f32::INFINITY {
             self.f_limit = self.f_min;
-            self.f_min = INFINITY;
+            self.f_min = f32::INFINITY;
         }
     }
 
     /// Add node's neighbors to either Now-list or their corresponding bucket according to estimated length to goal.
-    fn process_node(&mut self, node: Node) {
+    /// `reached` is called with every neighbor whose cost was improved and its new cost.
+    fn process_node(&mut self, node: Node, reached: &mut impl FnMut(Node, Cost)) {
+        self.expansions += 1;
+        let mut siblings = std::mem::take(&mut self.siblings);
         for (child, cost) in self.graph.neighbors(node) {
             if let Some((child, parent, cost)) = self.cache.check(*child, node, *cost) {
                 let estimate = self.cache.update(child, parent, cost);
+                reached(child, cost);
 
                 if estimate <= self.f_limit {
-                    self.buckets.push_now(child);
+                    match &mut self.ties {
+                        Some(ties) => siblings.push((ties.key(cost), child)),
+                        None => self.buckets.push_now(child),
+                    }
                 } else {
-                    self.buckets.push_bucket(child, Bucket::from(estimate));
+                    // Children pushed to current bucket are not seen before refreshing, so they must count now
+                    let bucket = self.buckets.layout.bucket(estimate);
+                    if bucket == self.buckets.current_bucket {
+                        self.f_min = self.f_min.min(estimate);
+                    }
+                    self.buckets.push_bucket(child, bucket);
                 }
             }
         }
+        // Sibling with the largest key is pushed last, so it is popped first
+        siblings.sort_unstable_by_key(|(key, _)| *key);
+        for (_, child) in siblings.drain(..) {
+            self.buckets.push_now(child);
+        }
+        self.siblings = siblings;
     }
 
     /// Run through the whole solving process. Internally this works the same as [`act`], but does not reveal internal state.
-    pub fn run(&mut self) -> Option<(Path, Cost)> {
+    /// Returns [`State::Finished`], [`State::NotFound`] or [`State::Aborted`].
+    pub fn run(&mut self) -> State {
         loop {
             match self.buckets.pop() {
                 (None, true) => self.refresh_limit(),
-                (None, false) => return None,
+                (None, false) => return State::NotFound,
                 (Some(node), from_now) => match self.estimation_check(node) {
-                    Action::Finish(path) => return Some(path),
+                    Action::Finish(path) => return State::Finished(path),
                     Action::Process(node) => {
+                        if let Some(reason) = self.limits.check(self.expansions) {
+                            return State::Aborted(reason);
+                        }
                         if !from_now {
                             self.buckets.remove_later_head();
                         }
-                        self.process_node(node);
+                        self.process_node(node, &mut |_, _| {});
                     }
                     Action::ToLater(_) => {
                         self.buckets.keep_current();
@@ -108,6 +200,11 @@ impl<'a> Fringe<'a> {
     /// If the node is has cost smaller than f_limit and it is the goal node, a full path is returned instead.
     /// If no node was found and refreshing buckets failed, then no path can be found.
     pub fn act(&mut self) -> State {
+        self.act_with(|_, _| {})
+    }
+
+    /// Do one step like [`act`](Self::act) and call `reached` with every neighbor whose cost was improved and its new cost.
+    pub fn act_with(&mut self, mut reached: impl FnMut(Node, Cost)) -> State {
         match self.buckets.pop() {
             (None, true) => {
                 self.refresh_limit();
@@ -115,21 +212,24 @@ impl<'a> Fringe<'a> {
             }
             (None, false) => State::NotFound,
             (Some(node), from_now) => match self.estimation_check(node) {
-                Action::Finish(path) => return State::Finished(path),
+                Action::Finish(path) => State::Finished(path),
                 Action::Process(node) => {
+                    if let Some(reason) = self.limits.check(self.expansions) {
+                        return State::Aborted(reason);
+                    }
                     if !from_now {
                         self.buckets.remove_later_head();
                     }
-                    self.process_node(node);
-                    return State::Processing(node);
+                    self.process_node(node, &mut reached);
+                    State::Processing(node)
                 }
                 Action::ToLater(_) => {
                     self.buckets.keep_current();
-                    return State::Processing(node);
+                    State::Processing(node)
                 }
                 Action::Nothing => {
                     self.buckets.remove_later_head();
-                    return State::Processing(node);
+                    State::Processing(node)
                 }
                 _ => panic!("what"),
             },
@@ -2,44 +2,35 @@ use super::Bucket;
 use std::ops::Index;
 use std::ops::IndexMut;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Indexes([usize; Bucket::SIZE]);
+#[derive(Debug, Clone)]
+pub struct Indexes(Vec<usize>);
 
 impl Indexes {
     /// Array of indexes for accessing different buckets in Fringe
     /// ```
     /// # use fringe_vs_astar::algorithms::fringesearch::indexes::Indexes;
     /// # use fringe_vs_astar::algorithms::fringesearch::bucket::Bucket;
-    /// let indexes = Indexes::new(4);
-    /// assert_eq!(0, indexes[Bucket::Zero]);
-    /// assert_eq!(4, indexes[Bucket::One]);
-    /// assert_eq!(8, indexes[Bucket::Two]);
-    /// assert_eq!(12, indexes[Bucket::Three]);
-    /// assert_eq!(16, indexes[Bucket::Four]);
-    /// assert_eq!(20, indexes[Bucket::Five]);
-    /// assert_eq!(24, indexes[Bucket::Six]);
-    /// assert_eq!(28, indexes[Bucket::Seven]);
+    /// let indexes = Indexes::new(4, 8);
+    /// assert_eq!(0, indexes[Bucket::new(0, 8)]);
+    /// assert_eq!(4, indexes[Bucket::new(1, 8)]);
+    /// assert_eq!(8, indexes[Bucket::new(2, 8)]);
+    /// assert_eq!(12, indexes[Bucket::new(3, 8)]);
+    /// assert_eq!(16, indexes[Bucket::new(4, 8)]);
+    /// assert_eq!(20, indexes[Bucket::new(5, 8)]);
+    /// assert_eq!(24, indexes[Bucket::new(6, 8)]);
+    /// assert_eq!(28, indexes[Bucket::new(7, 8)]);
     /// ```
-    pub fn new(bucket_length: usize) -> Self {
-        Indexes([
-            0,
-            bucket_length,
-            2 * bucket_length,
-            3 * bucket_length,
-            4 * bucket_length,
-            5 * bucket_length,
-            6 * bucket_length,
-            7 * bucket_length,
-        ])
+    pub fn new(bucket_length: usize, count: usize) -> Self {
+        Indexes((0..count).map(|i| i * bucket_length).collect())
     }
 
     /// +1 to a bucket's index
     /// ```
     /// # use fringe_vs_astar::algorithms::fringesearch::indexes::Indexes;
     /// # use fringe_vs_astar::algorithms::fringesearch::bucket::Bucket;
-    /// let mut indexes = Indexes::new(4);
-    /// indexes.add(Bucket::Zero);
-    /// assert_eq!(1, indexes[Bucket::Zero]);
+    /// let mut indexes = Indexes::new(4, 8);
+    /// indexes.add(Bucket::new(0, 8));
+    /// assert_eq!(1, indexes[Bucket::new(0, 8)]);
     pub fn add(&mut self, bucket: Bucket) {
         self[bucket] += 1;
     }
@@ -48,12 +39,12 @@ impl Indexes {
     /// ```
     /// # use fringe_vs_astar::algorithms::fringesearch::indexes::Indexes;
     /// # use fringe_vs_astar::algorithms::fringesearch::bucket::Bucket;
-    /// let mut indexes = Indexes::new(4);
-    /// indexes.add(Bucket::Zero);
-    /// indexes.sub(Bucket::Zero);
-    /// assert_eq!(0, indexes[Bucket::Zero]);
-    /// indexes.sub(Bucket::Zero);
-    /// assert_eq!(0, indexes[Bucket::Zero]);
+    /// let mut indexes = Indexes::new(4, 8);
+    /// indexes.add(Bucket::new(0, 8));
+    /// indexes.sub(Bucket::new(0, 8));
+    /// assert_eq!(0, indexes[Bucket::new(0, 8)]);
+    /// indexes.sub(Bucket::new(0, 8));
+    /// assert_eq!(0, indexes[Bucket::new(0, 8)]);
     pub fn sub(&mut self, bucket: Bucket) {
         self[bucket] = self[bucket].saturating_sub(1);
     }
@@ -63,12 +54,12 @@ impl Index<Bucket> for Indexes {
     type Output = usize;
 
     fn index(&self, index: Bucket) -> &Self::Output {
-        &self.0[index as usize]
+        &self.0[usize::from(index)]
     }
 }
 
 impl IndexMut<Bucket> for Indexes {
     fn index_mut(&mut self, index: Bucket) -> &mut Self::Output {
-        &mut self.0[index as usize]
+        &mut self.0[usize::from(index)]
     }
 }
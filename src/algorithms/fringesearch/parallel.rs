@@ -0,0 +1,260 @@
+use crate::algorithms::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Waves smaller than this are processed on the calling thread, because splitting them costs more than it saves
+const SEQUENTIAL_WAVE: usize = 64;
+
+/// Cost and parent of a node packed into one `u64`, so that both can be updated with a single atomic minimum.
+/// Costs are never negative, so their bits are ordered like the costs themselves.
+fn pack(cost: Cost, parent: Node) -> u64 {
+    (u64::from(cost.to_bits()) << 32) | u64::from(parent)
+}
+
+fn unpack(value: u64) -> (Cost, Node) {
+    (f32::from_bits((value >> 32) as u32), value as Node)
+}
+
+/// Nodes found while processing a part of the Now-list.
+/// Nodes for later come with their buckets.
+#[derive(Default)]
+struct Found {
+    now: Vec<Node>,
+    later: Vec<(usize, Node)>,
+    expansions: usize,
+}
+
+impl Found {
+    fn join(mut self, other: Found) -> Found {
+        self.now.extend(other.now);
+        self.later.extend(other.later);
+        self.expansions += other.expansions;
+        self
+    }
+}
+
+/// Fringe search that splits the Now-list of each iteration across the threads of the current rayon pool.
+///
+/// Now-list is processed in waves: every node of a wave is handled in parallel, and the neighbors whose
+/// estimates are within `f_limit` form the next wave. Others go to Later, which is split into buckets
+/// by the integer part of the estimate like in [`Buckets`](super::Buckets).
+/// First bucket that is not empty becomes the Now-list of the next iteration,
+/// and the smallest estimate in it becomes the new `f_limit`.
+///
+/// Costs and parents are updated with atomic operations, so the cache can be shared between threads.
+/// A node can be added by many threads, but only one of them expands it with its current cost.
+/// Because every estimate left is at least `f_limit`, goal is finished as soon as its cost is within `f_limit`,
+/// which gives the same optimal length as [`FringeSearch`](super::FringeSearch).
+pub struct ParallelFringe<'a> {
+    values: Vec<AtomicU64>,
+    closed: Vec<AtomicBool>,
+    now: Vec<Node>,
+    later: Vec<Vec<Node>>,
+    pub f_limit: Cost,
+    limits: Limits,
+    pub expansions: usize,
+    heuristic: Heuristic,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> ParallelFringe<'a> {
+    /// Initialize the search with a start, goal and a graph to be acted upon.
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        let size = graph.map_size();
+        let values: Vec<AtomicU64> = (0..size)
+            .map(|_| AtomicU64::new(pack(f32::INFINITY, 0)))
+            .collect();
+        values[start as usize].store(pack(0.0, start), Ordering::Relaxed);
+        let heuristic = Heuristic::new(goal, graph.get_width());
+
+        ParallelFringe {
+            values,
+            closed: (0..size).map(|_| AtomicBool::new(false)).collect(),
+            now: vec![start],
+            later: vec![],
+            f_limit: heuristic.calc(start),
+            limits: Limits::default(),
+            expansions: 0,
+            heuristic,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached.
+    /// Limits are checked between waves, so a wave can go over the expansion limit
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Process one wave of the Now-list in parallel, or start a new iteration if it is empty.
+    /// [`State::Processing`] carries the first node of the wave.
+    pub fn progress(&mut self) -> State {
+        if self.get_cost(self.goal) <= self.f_limit {
+            return State::Finished(self.construct_path());
+        }
+        if self.now.is_empty() {
+            return self.refresh();
+        }
+        // Waves expand many nodes at once, so the clock is read for every wave
+        if let Some(reason) = self
+            .limits
+            .check(self.expansions)
+            .or_else(|| self.limits.check_now())
+        {
+            return State::Aborted(reason);
+        }
+
+        let wave = std::mem::take(&mut self.now);
+        let found = if wave.len() < SEQUENTIAL_WAVE {
+            wave.iter()
+                .fold(Found::default(), |found, node| self.process(*node, found))
+        } else {
+            wave.par_iter()
+                .fold(Found::default, |found, node| self.process(*node, found))
+                .reduce(Found::default, Found::join)
+        };
+
+        self.now = found.now;
+        for (bucket, node) in found.later {
+            if self.later.len() <= bucket {
+                self.later.resize(bucket + 1, vec![]);
+            }
+            self.later[bucket].push(node);
+        }
+        self.expansions += found.expansions;
+        State::Processing(wave[0])
+    }
+
+    /// Expand a node if its estimate is within `f_limit` and no other thread has expanded it
+    fn process(&self, node: Node, mut found: Found) -> Found {
+        let estimate = self.get_estimate(node);
+        if estimate > self.f_limit {
+            found.later.push((estimate as usize, node));
+            return found;
+        }
+        if self.closed[node as usize].swap(true, Ordering::AcqRel) {
+            return found;
+        }
+        // Cost is read only after closing, so an improvement made before it is not missed
+        let cost = self.get_cost(node);
+        found.expansions += 1;
+
+        for (child, w) in self.graph.neighbors(node) {
+            // Plain load first, because most neighbors are not improved and a failed atomic minimum is slow
+            if self.get_cost(*child) <= cost + w {
+                continue;
+            }
+            let new = pack(cost + w, node);
+            let old = self.values[*child as usize].fetch_min(new, Ordering::AcqRel);
+            if unpack(old).0 <= cost + w {
+                continue;
+            }
+            self.closed[*child as usize].store(false, Ordering::Release);
+            let estimate = cost + w + self.heuristic.calc(*child);
+            if estimate <= self.f_limit {
+                found.now.push(*child);
+            } else {
+                found.later.push((estimate as usize, *child));
+            }
+        }
+        found
+    }
+
+    /// Make the first bucket of Later that is not empty the new Now-list and raise `f_limit`
+    /// to the smallest estimate in it. Nodes that have been expanded with their current cost are dropped.
+    fn refresh(&mut self) -> State {
+        for bucket in &mut self.later {
+            let mut nodes = std::mem::take(bucket);
+            nodes.retain(|n| !self.closed[*n as usize].load(Ordering::Acquire));
+            if nodes.is_empty() {
+                continue;
+            }
+
+            self.f_limit = nodes
+                .iter()
+                .map(|n| self.get_estimate(*n))
+                .fold(f32::INFINITY, f32::min);
+            self.now = nodes;
+            return State::Internal;
+        }
+        State::NotFound
+    }
+
+    /// Follow parents from goal to start
+    fn construct_path(&self) -> (Path, Cost) {
+        let mut path = vec![self.goal];
+        while path[path.len() - 1] != self.start {
+            let (_, parent) =
+                unpack(self.values[path[path.len() - 1] as usize].load(Ordering::Acquire));
+            path.push(parent);
+        }
+        path.reverse();
+
+        (path, self.get_cost(self.goal))
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        (0..self.closed.len() as Node)
+            .filter(|n| self.closed[*n as usize].load(Ordering::Acquire))
+            .for_each(|n| print.add_inclosed(n));
+        self.later
+            .iter()
+            .flatten()
+            .for_each(|n| print.add_inlater(*n));
+        self.now.iter().for_each(|n| print.add_first(*n));
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print.add_header("f_limit", self.f_limit);
+        print.add_header("|Now|", self.now.len());
+        print.add_header("|Later|", self.later.iter().map(Vec::len).sum::<usize>());
+        print.add_header("Expanded", self.expansions);
+        print.add_spacing();
+
+        print
+    }
+
+    /// Get current cost of a node
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        unpack(self.values[node as usize].load(Ordering::Acquire)).0
+    }
+
+    /// Get current estimate of a node
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.get_cost(node) + self.heuristic.calc(node)
+    }
+
+    /// Get combined size of Now and Later, including duplicates
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.now.len() + self.later.iter().map(Vec::len).sum::<usize>()
+    }
+}
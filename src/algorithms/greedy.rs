@@ -0,0 +1,317 @@
+use super::astar::cache::Cache;
+use super::astar::frontier::Frontier;
+use super::{until_final, Heuristic, Landmarks, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::sync::Arc;
+
+/// Greedy best-first search.
+///
+/// Nodes are expanded in the order of their heuristic only, so search heads straight to the goal
+/// and expands few nodes, but paths can be much longer than optimal.
+/// Expanded nodes are never opened again. Shares [`Frontier`] and [`Cache`] with [`AStar`](super::AStar).
+pub struct GreedySearch<'a> {
+    frontier: Frontier,
+    cache: Cache,
+    closed: Vec<bool>,
+    limits: Limits,
+    pub expansions: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> GreedySearch<'a> {
+    /// Create solver of a problem for a graph
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        let size = graph.map_size();
+        let heuristic = Heuristic::new(goal, graph.get_width());
+
+        GreedySearch {
+            frontier: Frontier::new(start, size),
+            cache: Cache::new(start, heuristic, size),
+            closed: vec![false; size],
+            limits: Limits::default(),
+            expansions: 0,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Use the ALT heuristic with precomputed landmarks
+    #[must_use]
+    pub fn with_landmarks(mut self, landmarks: Arc<Landmarks>) -> Self {
+        let heuristic = Heuristic::new(self.goal, self.graph.get_width()).with_landmarks(landmarks);
+        self.cache = self.cache.with_heuristic(heuristic);
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        self.run()
+    }
+
+    /// Solve like [`GreedySearch::solve_limited`], but keep the search so that its expansions can be read afterwards
+    pub fn run(&mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        // Frontier may have duplicates of nodes whose cost was improved before they were expanded
+        let Some(node) =
+            std::iter::from_fn(|| self.frontier.pop()).find(|node| !self.closed[*node as usize])
+        else {
+            return State::NotFound;
+        };
+        self.closed[node as usize] = true;
+
+        if node == self.goal {
+            return State::Finished(construct_path(&self.cache, self.start, self.goal));
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let current_cost = self.cache.get_cost(node);
+        for (child, w) in self.graph.neighbors(node) {
+            if self.closed[*child as usize] {
+                continue;
+            }
+            if let Some((child, _)) = self.cache.check(*child, node, current_cost + w) {
+                let heuristic = self.cache.get_heuristic(child);
+                self.frontier.push(child, heuristic);
+            }
+        }
+        State::Processing(node)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, _) in self.closed.iter().enumerate().filter(|(_, c)| **c) {
+            print.add_inclosed(i.try_into().unwrap());
+        }
+        self.frontier
+            .iter()
+            .filter(|n| !self.closed[**n as usize])
+            .for_each(|n| print.add_infrontier(*n));
+
+        print.add_header("|Open|", self.frontier.size());
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.cache.get_cost(node)
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.cache.get_estimate(node)
+    }
+
+    /// Get size of [`Frontier`]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.frontier.size()
+    }
+}
+
+/// Beam search.
+///
+/// Search advances one layer at a time, like breadth-first search,
+/// but only the `width` children of a layer with the smallest estimate f = g + h form the next layer.
+/// Memory and time are bounded by the width, but the beam can prune every route to the goal,
+/// and then no path is found even though one exists.
+/// Nodes of earlier layers are never entered again. Shares [`Frontier`] and [`Cache`] with [`AStar`](super::AStar).
+pub struct BeamSearch<'a> {
+    /// Children of the current layer, ordered by estimate
+    frontier: Frontier,
+    cache: Cache,
+    layer: Vec<Node>,
+    closed: Vec<bool>,
+    width: usize,
+    limits: Limits,
+    pub expansions: usize,
+    pub layers: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> BeamSearch<'a> {
+    /// Create solver of a problem for a graph, keeping `width` nodes in every layer
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, width: usize) -> Self {
+        let size = graph.map_size();
+        let heuristic = Heuristic::new(goal, graph.get_width());
+
+        BeamSearch {
+            frontier: Frontier::new(start, size.min(8 * width)),
+            cache: Cache::new(start, heuristic, size),
+            layer: vec![],
+            closed: vec![false; size],
+            width,
+            limits: Limits::default(),
+            expansions: 0,
+            layers: 0,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Use the ALT heuristic with precomputed landmarks
+    #[must_use]
+    pub fn with_landmarks(mut self, landmarks: Arc<Landmarks>) -> Self {
+        let heuristic = Heuristic::new(self.goal, self.graph.get_width()).with_landmarks(landmarks);
+        self.cache = self.cache.with_heuristic(heuristic);
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        self.run()
+    }
+
+    /// Solve like [`BeamSearch::solve_limited`], but keep the search so that its expansions can be read afterwards
+    pub fn run(&mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step.
+    /// When the current layer has been expanded, the best children are chosen as the next layer.
+    pub fn progress(&mut self) -> State {
+        if self.layer.is_empty() {
+            self.next_layer();
+        }
+        let Some(node) = self.layer.pop() else {
+            return State::NotFound;
+        };
+
+        if node == self.goal {
+            return State::Finished(construct_path(&self.cache, self.start, self.goal));
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let current_cost = self.cache.get_cost(node);
+        for (child, w) in self.graph.neighbors(node) {
+            if self.closed[*child as usize] {
+                continue;
+            }
+            if let Some((child, estimate)) = self.cache.check(*child, node, current_cost + w) {
+                self.frontier.push(child, estimate);
+            }
+        }
+        State::Processing(node)
+    }
+
+    /// Move the best children to the layer and prune the rest
+    fn next_layer(&mut self) {
+        while self.layer.len() < self.width {
+            let Some(node) = self.frontier.pop() else {
+                break;
+            };
+            if !self.closed[node as usize] {
+                self.closed[node as usize] = true;
+                self.layer.push(node);
+            }
+        }
+        // Best node is expanded first, so that the goal is found as soon as it is in the layer
+        self.layer.reverse();
+        self.frontier.clear();
+        self.layers += 1;
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, _) in self.closed.iter().enumerate().filter(|(_, c)| **c) {
+            print.add_inclosed(i.try_into().unwrap());
+        }
+        self.layer.iter().for_each(|n| print.add_inopen(*n));
+        self.frontier.iter().for_each(|n| print.add_infrontier(*n));
+
+        print.add_header("Beam width", self.width);
+        print.add_header("Layer", self.layers);
+        print.add_header("|Layer|", self.layer.len());
+        print.add_header("|Children|", self.frontier.size());
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.cache.get_cost(node)
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.cache.get_estimate(node)
+    }
+
+    /// Get number of nodes left in the layer and children waiting for the next one
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.layer.len() + self.frontier.size()
+    }
+}
+
+/// Follow parents in the cache from goal back to start
+fn construct_path(cache: &Cache, start: Node, goal: Node) -> (Path, Cost) {
+    let mut path = vec![goal];
+    while path[path.len() - 1] != start {
+        path.push(cache[path[path.len() - 1]].parent);
+    }
+    path.reverse();
+    (path, cache.get_cost(goal))
+}
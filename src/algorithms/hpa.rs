@@ -0,0 +1,269 @@
+use self::abstraction::{Abstraction, ClusterField};
+use super::astar::weighted_cell::WeightedCell;
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use std::collections::BinaryHeap;
+
+/// Abstract graph of clusters and entrances
+pub mod abstraction;
+
+/// Value of an abstract node during a search
+#[derive(Clone, Copy)]
+struct Value {
+    parent: usize,
+    cost: Cost,
+    closed: bool,
+}
+
+impl Default for Value {
+    fn default() -> Self {
+        Value {
+            parent: usize::MAX,
+            cost: f32::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+/// HPA* (hierarchical path-finding A*) pathfinder.
+///
+/// Start and goal are connected to the abstract nodes of their clusters in an [`Abstraction`],
+/// after which A* searches the abstract graph. Found abstract path is refined back into a path of [`Node`]s,
+/// either by concatenating stored paths or by searching inside clusters, as chosen when building the abstraction.
+/// Paths are usually a bit longer than optimal, because they must pass through transitions.
+pub struct Hpa<'a> {
+    heap: BinaryHeap<WeightedCell>,
+    values: Vec<Value>,
+    start_field: ClusterField,
+    goal_field: ClusterField,
+    heuristic: Heuristic,
+    limits: Limits,
+    pub expansions: usize,
+    /// Number of paths inside clusters searched while refining
+    pub refined: usize,
+    start: Node,
+    goal: Node,
+    abstraction: &'a Abstraction,
+    graph: &'a Graph,
+}
+
+impl<'a> Hpa<'a> {
+    /// Create solver of a problem for a graph and its abstraction
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, abstraction: &'a Abstraction) -> Self {
+        let size = abstraction.node_count();
+        let mut values = vec![Value::default(); size + 2];
+        values[size].parent = size;
+        values[size].cost = 0.0;
+
+        let mut heap = BinaryHeap::new();
+        heap.push(WeightedCell::new(size as Node, 0.0));
+
+        Hpa {
+            heap,
+            values,
+            start_field: abstraction.search_cluster(graph, start, None),
+            goal_field: abstraction.search_cluster(graph, goal, None),
+            heuristic: Heuristic::new(goal, graph.get_width()),
+            limits: Limits::default(),
+            expansions: 0,
+            refined: 0,
+            start,
+            goal,
+            abstraction,
+            graph,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step.
+    /// [`State::Processing`] carries the cell of the expanded abstract node.
+    pub fn progress(&mut self) -> State {
+        // Heap may have outdated duplicates of already expanded nodes
+        let Some(i) = std::iter::from_fn(|| self.heap.pop())
+            .map(|c| c.node as usize)
+            .find(|i| !self.values[*i].closed)
+        else {
+            return State::NotFound;
+        };
+        self.values[i].closed = true;
+
+        if i == self.goal_index() {
+            return State::Finished(self.construct_path());
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let cost = self.values[i].cost;
+        for (child, w) in self.successors(i) {
+            if self.values[child].closed || cost + w >= self.values[child].cost {
+                continue;
+            }
+            self.values[child].cost = cost + w;
+            self.values[child].parent = i;
+            let estimate = cost + w + self.heuristic.calc(self.cell(child));
+            self.heap.push(WeightedCell::new(child as Node, estimate));
+        }
+        State::Processing(self.cell(i))
+    }
+
+    /// Abstract nodes reachable from an abstract node, including the temporary start and goal
+    fn successors(&self, i: usize) -> Vec<(usize, Cost)> {
+        let mut successors: Vec<(usize, Cost)> = if i == self.start_index() {
+            let cluster = self.abstraction.cluster_of(self.start);
+            self.abstraction
+                .cluster_nodes(cluster)
+                .iter()
+                .map(|j| (*j, self.start_field.get(self.abstraction.node(*j))))
+                .collect()
+        } else {
+            self.abstraction
+                .edges(i)
+                .iter()
+                .map(|e| (e.to, e.cost))
+                .collect()
+        };
+        successors.push((self.goal_index(), self.goal_field.get(self.cell(i))));
+        successors.retain(|(_, w)| w.is_finite());
+        successors
+    }
+
+    /// Index of the temporary abstract node of start
+    fn start_index(&self) -> usize {
+        self.abstraction.node_count()
+    }
+
+    /// Index of the temporary abstract node of goal
+    fn goal_index(&self) -> usize {
+        self.abstraction.node_count() + 1
+    }
+
+    /// Cell of an abstract node
+    fn cell(&self, i: usize) -> Node {
+        if i == self.start_index() {
+            self.start
+        } else if i == self.goal_index() {
+            self.goal
+        } else {
+            self.abstraction.node(i)
+        }
+    }
+
+    /// Refine the abstract path that was found
+    fn construct_path(&mut self) -> (Path, Cost) {
+        let mut abstract_path = vec![self.goal_index()];
+        while abstract_path[abstract_path.len() - 1] != self.start_index() {
+            abstract_path.push(self.values[abstract_path[abstract_path.len() - 1]].parent);
+        }
+        abstract_path.reverse();
+
+        let mut path = vec![self.start];
+        for pair in abstract_path.windows(2) {
+            let segment = self.refine(pair[0], pair[1]);
+            path.extend(segment);
+        }
+        (path, self.values[self.goal_index()].cost)
+    }
+
+    /// Nodes after the cell of `from`, up to and including the cell of `to`
+    fn refine(&mut self, from: usize, to: usize) -> Path {
+        let (from_cell, to_cell) = (self.cell(from), self.cell(to));
+        if from == self.start_index() {
+            return self.start_field.path_to(to_cell).unwrap()[1..].to_vec();
+        }
+        if to == self.goal_index() {
+            let mut path = self.goal_field.path_to(from_cell).unwrap();
+            path.reverse();
+            return path[1..].to_vec();
+        }
+
+        let edge = self
+            .abstraction
+            .edges(from)
+            .iter()
+            .find(|e| e.to == to)
+            .expect("Abstract path follows edges");
+        if edge.path.is_empty() {
+            self.refined += 1;
+            let field = self
+                .abstraction
+                .search_cluster(self.graph, from_cell, Some(to_cell));
+            field.path_to(to_cell).unwrap()[1..].to_vec()
+        } else {
+            edge.path.clone()
+        }
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, value) in self.values.iter().enumerate() {
+            if value.closed {
+                print.add_inclosed(self.cell(i));
+            } else if value.cost < f32::INFINITY {
+                print.add_infrontier(self.cell(i));
+            }
+        }
+
+        print.add_header("Abstract nodes", self.abstraction.node_count());
+        print.add_header("|Open|", self.heap.len());
+        print.add_header("Expanded", self.expansions);
+        print.add_header("Refined", self.refined);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a cell, if it is an abstract node
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.abstract_index(node)
+            .map_or(f32::INFINITY, |i| self.values[i].cost)
+    }
+
+    /// Get current estimate of a cell, if it is an abstract node
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.get_cost(node) + self.heuristic.calc(node)
+    }
+
+    fn abstract_index(&self, node: Node) -> Option<usize> {
+        if node == self.start {
+            Some(self.start_index())
+        } else if node == self.goal {
+            Some(self.goal_index())
+        } else {
+            self.abstraction.index_of(node)
+        }
+    }
+
+    /// Get size of the open list, including outdated entries
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+}
@@ -0,0 +1,350 @@
+use crate::algorithms::astar::weighted_cell::WeightedCell;
+use crate::structures::{Graph, Grid};
+use crate::{index_to_xy, xy_to_index, Cost, Node, Path};
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use std::collections::{BinaryHeap, HashMap};
+use std::mem::{size_of, size_of_val};
+
+/// Entrances at least this long get a transition at both ends instead of one in the middle
+const LONG_ENTRANCE: usize = 6;
+
+/// How abstract paths are turned back into paths of [`Node`]s
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Refinement {
+    /// Paths inside clusters are searched again when a path is refined
+    Lazy,
+    /// Paths inside clusters are stored while building, so refining only concatenates them
+    Full,
+}
+
+/// Edge of the abstract graph
+#[derive(Clone, Debug)]
+pub struct Edge {
+    pub to: usize,
+    pub cost: Cost,
+    /// Nodes after the source of the edge, up to and including its target.
+    /// Empty for edges inside a cluster when refinement is lazy.
+    pub path: Path,
+}
+
+/// Abstract graph of HPA*.
+///
+/// Map is split into square clusters of `cluster_size` cells. Every maximal run of passable cells along
+/// the border of two clusters is an entrance, and its transitions are cell pairs across the border.
+/// Cells of the transitions are nodes of the abstract graph. They are connected by a single step across
+/// the border and by shortest paths inside their cluster, which are precomputed while building.
+pub struct Abstraction {
+    nodes: Vec<Node>,
+    edges: Vec<Vec<Edge>>,
+    index: HashMap<Node, usize>,
+    cluster_nodes: Vec<Vec<usize>>,
+    cluster_size: usize,
+    clusters_x: usize,
+    width: usize,
+    height: usize,
+    refinement: Refinement,
+}
+
+impl Abstraction {
+    /// Build the abstraction of a graph. Clusters are handled in parallel.
+    #[must_use]
+    pub fn new(graph: &Graph, cluster_size: usize, refinement: Refinement) -> Self {
+        let (width, height) = (graph.get_width(), graph.get_height());
+        let clusters_x = width.div_ceil(cluster_size);
+        let clusters_y = height.div_ceil(cluster_size);
+
+        let mut abstraction = Abstraction {
+            nodes: vec![],
+            edges: vec![],
+            index: HashMap::new(),
+            cluster_nodes: vec![vec![]; clusters_x * clusters_y],
+            cluster_size,
+            clusters_x,
+            width,
+            height,
+            refinement,
+        };
+
+        for cluster in 0..abstraction.cluster_count() {
+            let (x0, y0, x1, y1) = abstraction.bounds(cluster);
+            if x1 < width {
+                let border =
+                    (y0..y1).map(|y| (xy_to_index(x1 - 1, y, width), xy_to_index(x1, y, width)));
+                abstraction.add_entrances(graph, border);
+            }
+            if y1 < height {
+                let border =
+                    (x0..x1).map(|x| (xy_to_index(x, y1 - 1, width), xy_to_index(x, y1, width)));
+                abstraction.add_entrances(graph, border);
+            }
+        }
+
+        let intra: Vec<Vec<(usize, Edge)>> = abstraction
+            .cluster_nodes
+            .par_iter()
+            .map(|members| abstraction.intra_edges(graph, members))
+            .collect();
+        for (from, edge) in intra.into_iter().flatten() {
+            abstraction.edges[from].push(edge);
+        }
+
+        abstraction
+    }
+
+    /// Add transitions of every entrance along a border.
+    /// `border` yields pairs of cells facing each other across the border.
+    fn add_entrances(&mut self, graph: &Graph, border: impl Iterator<Item = (Node, Node)>) {
+        let mut run = vec![];
+        for (a, b) in border.chain(std::iter::once((Node::MAX, Node::MAX))) {
+            if a != Node::MAX && graph.get(a as i32) && graph.get(b as i32) {
+                run.push((a, b));
+                continue;
+            }
+            if run.is_empty() {
+                continue;
+            }
+            let transitions = if run.len() < LONG_ENTRANCE {
+                vec![run[run.len() / 2]]
+            } else {
+                vec![run[0], run[run.len() - 1]]
+            };
+            for (a, b) in transitions {
+                let (i, j) = (self.add_node(a), self.add_node(b));
+                self.edges[i].push(Edge {
+                    to: j,
+                    cost: 1.0,
+                    path: vec![b],
+                });
+                self.edges[j].push(Edge {
+                    to: i,
+                    cost: 1.0,
+                    path: vec![a],
+                });
+            }
+            run.clear();
+        }
+    }
+
+    /// Get index of an abstract node for a cell, adding it if needed
+    fn add_node(&mut self, node: Node) -> usize {
+        if let Some(i) = self.index.get(&node) {
+            return *i;
+        }
+        let i = self.nodes.len();
+        self.nodes.push(node);
+        self.edges.push(vec![]);
+        self.index.insert(node, i);
+        let cluster = self.cluster_of(node);
+        self.cluster_nodes[cluster].push(i);
+        i
+    }
+
+    /// Shortest paths inside a cluster between every pair of its abstract nodes
+    fn intra_edges(&self, graph: &Graph, members: &[usize]) -> Vec<(usize, Edge)> {
+        let mut edges = vec![];
+        for &i in members {
+            let field = self.search_cluster(graph, self.nodes[i], None);
+            for &j in members.iter().filter(|j| **j != i) {
+                let cost = field.get(self.nodes[j]);
+                if !cost.is_finite() {
+                    continue;
+                }
+                let path = match self.refinement {
+                    Refinement::Full => field.path_to(self.nodes[j]).unwrap()[1..].to_vec(),
+                    Refinement::Lazy => vec![],
+                };
+                edges.push((i, Edge { to: j, cost, path }));
+            }
+        }
+        edges
+    }
+
+    /// Dijkstra's algorithm from `source` that stays inside the cluster of the source.
+    /// Search stops early when `goal` is expanded.
+    #[must_use]
+    pub fn search_cluster(&self, graph: &Graph, source: Node, goal: Option<Node>) -> ClusterField {
+        let (x0, y0, x1, y1) = self.bounds(self.cluster_of(source));
+        let mut field = ClusterField {
+            x0,
+            y0,
+            cluster_width: x1 - x0,
+            cluster_height: y1 - y0,
+            width: self.width,
+            distances: vec![f32::INFINITY; (x1 - x0) * (y1 - y0)],
+            parents: vec![Node::MAX; (x1 - x0) * (y1 - y0)],
+        };
+        let i = field.local(source).unwrap();
+        field.distances[i] = 0.0;
+        field.parents[i] = source;
+
+        let mut heap = BinaryHeap::new();
+        heap.push(WeightedCell::new(source, 0.0));
+        while let Some(WeightedCell { node, weight }) = heap.pop() {
+            if weight > field.get(node) {
+                continue;
+            }
+            if Some(node) == goal {
+                break;
+            }
+            for (child, w) in graph.neighbors(node) {
+                let Some(i) = field.local(*child) else {
+                    continue;
+                };
+                let cost = weight + w;
+                if cost < field.distances[i] {
+                    field.distances[i] = cost;
+                    field.parents[i] = node;
+                    heap.push(WeightedCell::new(*child, cost));
+                }
+            }
+        }
+        field
+    }
+
+    /// Cluster that a cell belongs to
+    #[must_use]
+    pub fn cluster_of(&self, node: Node) -> usize {
+        let (x, y) = index_to_xy(node, self.width);
+        x / self.cluster_size + (y / self.cluster_size) * self.clusters_x
+    }
+
+    /// Bounds of a cluster as `(x0, y0, x1, y1)`, where the ends are exclusive
+    fn bounds(&self, cluster: usize) -> (usize, usize, usize, usize) {
+        let x0 = (cluster % self.clusters_x) * self.cluster_size;
+        let y0 = (cluster / self.clusters_x) * self.cluster_size;
+        (
+            x0,
+            y0,
+            (x0 + self.cluster_size).min(self.width),
+            (y0 + self.cluster_size).min(self.height),
+        )
+    }
+
+    /// Abstract node of a cell, if it is one
+    #[must_use]
+    pub fn index_of(&self, node: Node) -> Option<usize> {
+        self.index.get(&node).copied()
+    }
+
+    /// Cell of an abstract node
+    #[must_use]
+    pub fn node(&self, i: usize) -> Node {
+        self.nodes[i]
+    }
+
+    /// Outgoing edges of an abstract node
+    #[must_use]
+    pub fn edges(&self, i: usize) -> &[Edge] {
+        &self.edges[i]
+    }
+
+    /// Abstract nodes inside a cluster
+    #[must_use]
+    pub fn cluster_nodes(&self, cluster: usize) -> &[usize] {
+        &self.cluster_nodes[cluster]
+    }
+
+    /// Number of abstract nodes
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of directed abstract edges
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.edges.iter().map(Vec::len).sum()
+    }
+
+    /// Number of clusters
+    #[must_use]
+    pub fn cluster_count(&self) -> usize {
+        self.cluster_nodes.len()
+    }
+
+    /// Get size of the clusters
+    #[must_use]
+    pub fn get_cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// Get the refinement that the abstraction was built for
+    #[must_use]
+    pub fn refinement(&self) -> Refinement {
+        self.refinement
+    }
+
+    /// Get width of the map
+    #[must_use]
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Approximate memory used by the abstraction
+    #[must_use]
+    pub fn size_in_bytes(&self) -> usize {
+        let edges: usize = self
+            .edges
+            .iter()
+            .flatten()
+            .map(|e| size_of::<Edge>() + size_of_val(e.path.as_slice()))
+            .sum();
+        let clusters: usize = self
+            .cluster_nodes
+            .iter()
+            .map(|c| size_of::<Vec<usize>>() + size_of_val(c.as_slice()))
+            .sum();
+        size_of_val(self.nodes.as_slice())
+            + self.edges.len() * size_of::<Vec<Edge>>()
+            + edges
+            + self.index.capacity() * (size_of::<Node>() + size_of::<usize>())
+            + clusters
+    }
+}
+
+/// Distances and parents of a search inside one cluster
+pub struct ClusterField {
+    x0: usize,
+    y0: usize,
+    cluster_width: usize,
+    cluster_height: usize,
+    width: usize,
+    distances: Vec<Cost>,
+    parents: Vec<Node>,
+}
+
+impl ClusterField {
+    /// Index of a cell inside the cluster
+    fn local(&self, node: Node) -> Option<usize> {
+        let (x, y) = index_to_xy(node, self.width);
+        let (x, y) = (x.checked_sub(self.x0)?, y.checked_sub(self.y0)?);
+        (x < self.cluster_width && y < self.cluster_height).then_some(x + y * self.cluster_width)
+    }
+
+    /// Distance from the source, infinite for unreached cells and cells outside the cluster
+    #[must_use]
+    pub fn get(&self, node: Node) -> Cost {
+        self.local(node)
+            .map_or(f32::INFINITY, |i| self.distances[i])
+    }
+
+    /// Shortest path inside the cluster from the source to a cell, if it has been reached
+    #[must_use]
+    pub fn path_to(&self, node: Node) -> Option<Path> {
+        let mut path = vec![node];
+        loop {
+            let parent = self.parents[self.local(path[path.len() - 1])?];
+            if parent == Node::MAX {
+                return None;
+            }
+            if parent == path[path.len() - 1] {
+                break;
+            }
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
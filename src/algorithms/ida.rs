@@ -0,0 +1,266 @@
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+
+/// One level of the depth-first search.
+/// `next` is the index of the next neighbor to be tried.
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    node: Node,
+    cost: Cost,
+    next: usize,
+}
+
+/// Iterative deepening A* pathfinder.
+///
+/// Depth-first search is repeated with a growing limit for the estimate.
+/// The search is done with an explicit stack, so it can be advanced one step at a time.
+///
+/// Optional transposition table remembers the lowest cost a [`Node`] has been reached with during current iteration.
+/// Without it the search revisits the same cells through every possible route, which is very slow on open maps.
+pub struct Ida<'a> {
+    stack: Vec<Frame>,
+    on_path: Vec<bool>,
+    table: Option<Vec<(u32, Cost)>>,
+    heuristic: Heuristic,
+    f_limit: Cost,
+    f_min: Cost,
+    iteration: u32,
+    limits: Limits,
+    /// Number of times the search has descended to a child, over every iteration
+    pub expansions: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> Ida<'a> {
+    /// Create solver of a problem for a graph. Transposition table is not used.
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        let heuristic = Heuristic::new(goal, graph.get_width());
+        let f_limit = heuristic.calc(start);
+        let mut on_path = vec![false; graph.map_size()];
+        on_path[start as usize] = true;
+
+        Ida {
+            stack: vec![Frame {
+                node: start,
+                cost: 0.0,
+                next: 0,
+            }],
+            on_path,
+            table: None,
+            heuristic,
+            f_limit,
+            f_min: f32::INFINITY,
+            iteration: 1,
+            limits: Limits::default(),
+            expansions: 0,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Use a transposition table for pruning already seen [`Node`]s
+    #[must_use]
+    pub fn with_table(mut self) -> Self {
+        let mut table = vec![(0, f32::INFINITY); self.graph.map_size()];
+        table[self.start as usize] = (self.iteration, 0.0);
+        self.table = Some(table);
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step.
+    ///
+    /// One step is either descending to a child of the deepest [`Node`] or backtracking from it.
+    /// When the whole iteration is exhausted, a new one is started with a higher limit.
+    pub fn progress(&mut self) -> State {
+        if self.start == self.goal {
+            return State::Finished((vec![self.start], 0.0));
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+
+        let Some(&Frame { node, cost, .. }) = self.stack.last() else {
+            return self.next_iteration();
+        };
+        let top = self.stack.len() - 1;
+
+        while let Some((child, move_cost)) = self.graph.neighbors(node).nth(self.stack[top].next) {
+            self.stack[top].next += 1;
+            let child = *child;
+            let child_cost = cost + move_cost;
+
+            if self.on_path[child as usize] || !self.check_table(child, child_cost) {
+                continue;
+            }
+
+            let estimate = child_cost + self.heuristic.calc(child);
+            if estimate > self.f_limit {
+                self.f_min = self.f_min.min(estimate);
+                continue;
+            }
+
+            if child == self.goal {
+                return State::Finished(self.construct_path(child_cost));
+            }
+
+            self.on_path[child as usize] = true;
+            self.expansions += 1;
+            self.stack.push(Frame {
+                node: child,
+                cost: child_cost,
+                next: 0,
+            });
+            return State::Processing(child);
+        }
+
+        // Every child was tried, backtrack
+        self.stack.pop();
+        self.on_path[node as usize] = false;
+        State::Processing(node)
+    }
+
+    /// Start a new iteration with the smallest estimate that exceeded the limit.
+    /// If no such estimate exists, no path can be found.
+    fn next_iteration(&mut self) -> State {
+        if self.f_min == f32::INFINITY {
+            return State::NotFound;
+        }
+        self.f_limit = self.f_min;
+        self.f_min = f32::INFINITY;
+        self.iteration += 1;
+
+        let iteration = self.iteration;
+        if let Some(table) = self.table.as_mut() {
+            table[self.start as usize] = (iteration, 0.0);
+        }
+        self.on_path[self.start as usize] = true;
+        self.stack.push(Frame {
+            node: self.start,
+            cost: 0.0,
+            next: 0,
+        });
+
+        State::Internal
+    }
+
+    /// Check if [`Node`] should be visited with given cost and record it to the transposition table.
+    /// Always true if the table is not used.
+    fn check_table(&mut self, node: Node, cost: Cost) -> bool {
+        let iteration = self.iteration;
+        match self.table.as_mut() {
+            Some(table) => {
+                let (seen, best) = table[node as usize];
+                if seen == iteration && best <= cost {
+                    false
+                } else {
+                    table[node as usize] = (iteration, cost);
+                    true
+                }
+            }
+            None => true,
+        }
+    }
+
+    /// Path is the current stack with the goal on top of it
+    fn construct_path(&self, cost: Cost) -> (Path, Cost) {
+        let mut path: Path = self.stack.iter().map(|f| f.node).collect();
+        path.push(self.goal);
+
+        (path, cost)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        if let Some(table) = &self.table {
+            table
+                .iter()
+                .enumerate()
+                .filter(|(_, (seen, _))| *seen == self.iteration)
+                .for_each(|(i, _)| print.add_inclosed(i.try_into().unwrap()));
+        }
+        self.stack.iter().for_each(|f| print.add_inopen(f.node));
+        if let Some(frame) = self.stack.last() {
+            print.add_first(frame.node);
+        }
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print.add_header("Iteration", self.iteration);
+        print.add_header("Depth", self.stack.len());
+        print.add_header("f_limit", self.f_limit);
+        print.add_header("f_min", self.f_min);
+        print.add_spacing();
+
+        print
+    }
+
+    /// Get current cost of a [`Node`].
+    /// Cost is known only for nodes in the current path or in the transposition table.
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        if let Some(frame) = self.stack.iter().find(|f| f.node == node) {
+            frame.cost
+        } else if let Some(table) = &self.table {
+            table[node as usize].1
+        } else {
+            f32::INFINITY
+        }
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.get_cost(node) + self.heuristic.calc(node)
+    }
+
+    /// Get depth of the current path
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Get the limit for the estimate in the current iteration
+    #[must_use]
+    pub fn f_limit(&self) -> Cost {
+        self.f_limit
+    }
+
+    /// Get the lowest estimate over the limit seen in the current iteration, limit of the next one
+    #[must_use]
+    pub fn f_min(&self) -> Cost {
+        self.f_min
+    }
+
+    /// Get the number of the current iteration, starting from 1
+    #[must_use]
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+}
@@ -0,0 +1,270 @@
+use super::astar::cache::Cache;
+use super::astar::frontier::Frontier;
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::structures::Grid;
+use crate::{index_to_xy, xy_to_index, Cost, Node, Path, DIAGONAL_COST};
+
+/// All eight directions of movement as `(dx, dy)`
+pub const DIRECTIONS: [(i32, i32); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Jump Point Search pathfinder.
+///
+/// A* that expands only jump points: cells where an optimal path may have to change its direction.
+/// Movement follows the same rules as [`Graph`](crate::structures::Graph):
+/// diagonal moves are allowed only if both of the orthogonal cells next to them are passable.
+/// Because of this, diagonal moves never have forced neighbors and straight moves have them only
+/// when a cell diagonally behind is blocked while the cell beside is passable.
+///
+/// Works with anything that implements [`Grid`], such as [`Map`](crate::structures::Map) or
+/// [`Graph`](crate::structures::Graph).
+pub struct Jps<'a, G: Grid> {
+    frontier: Frontier,
+    cache: Cache,
+    start: Node,
+    goal: Node,
+    goal_xy: (i32, i32),
+    grid: &'a G,
+    limits: Limits,
+    /// Number of jump points expanded
+    pub expansions: usize,
+}
+
+impl<'a, G: Grid> Jps<'a, G> {
+    /// Create solver of a problem for a grid
+    #[must_use]
+    pub fn new(start: Node, goal: Node, grid: &'a G) -> Self {
+        let size = grid.get_width() * grid.get_height();
+        let frontier = Frontier::new(start, size);
+
+        let heuristic = Heuristic::new(goal, grid.get_width());
+        let cache = Cache::new(start, heuristic, size);
+        let (x, y) = index_to_xy(goal, grid.get_width());
+
+        Jps {
+            frontier,
+            cache,
+            start,
+            goal,
+            goal_xy: (x as i32, y as i32),
+            grid,
+            limits: Limits::default(),
+            expansions: 0,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        if let Some(node) = self.frontier.pop() {
+            if node == self.goal {
+                return State::Finished(self.construct_path());
+            }
+            if let Some(reason) = self.limits.check(self.expansions) {
+                return State::Aborted(reason);
+            }
+            self.expansions += 1;
+            self.expand(node);
+            State::Processing(node)
+        } else {
+            State::NotFound
+        }
+    }
+
+    /// Jump to every direction that is not pruned and add found jump points to the frontier
+    fn expand(&mut self, node: Node) {
+        let current_cost = self.cache.get_cost(node);
+        let (x, y) = self.xy(node);
+
+        for (dx, dy) in self.directions(node) {
+            if let Some((jx, jy)) = self.jump(x, y, dx, dy) {
+                let jump_point = self.index(jx, jy);
+                let cost = current_cost + octile(jx - x, jy - y);
+                if let Some((jump_point, weight)) = self.cache.check(jump_point, node, cost) {
+                    self.frontier.push(jump_point, weight);
+                }
+            }
+        }
+    }
+
+    /// Directions that need to be searched from a [`Node`] given the direction it was reached from
+    fn directions(&self, node: Node) -> Vec<(i32, i32)> {
+        if node == self.start {
+            return DIRECTIONS.to_vec();
+        }
+        let (x, y) = self.xy(node);
+        let (px, py) = self.xy(self.cache[node].parent);
+        let (dx, dy) = ((x - px).signum(), (y - py).signum());
+
+        if dx != 0 && dy != 0 {
+            return vec![(dx, 0), (0, dy), (dx, dy)];
+        }
+
+        let mut directions = vec![(dx, dy)];
+        // Perpendicular directions, both of which have possibly a forced neighbor
+        for (sx, sy) in [(dy, dx), (-dy, -dx)] {
+            if self.grid.passable(x + sx, y + sy) && !self.grid.passable(x + sx - dx, y + sy - dy) {
+                directions.push((sx, sy));
+                directions.push((sx + dx, sy + dy));
+            }
+        }
+        directions
+    }
+
+    /// Move from `(x, y)` to the direction until a jump point, goal or an obstacle is found.
+    fn jump(&self, mut x: i32, mut y: i32, dx: i32, dy: i32) -> Option<(i32, i32)> {
+        let diagonal = dx != 0 && dy != 0;
+        loop {
+            if !self.grid.passable(x + dx, y + dy)
+                || diagonal && !(self.grid.passable(x + dx, y) && self.grid.passable(x, y + dy))
+            {
+                return None;
+            }
+            x += dx;
+            y += dy;
+
+            if (x, y) == self.goal_xy {
+                return Some((x, y));
+            }
+
+            if diagonal {
+                if self.jump(x, y, dx, 0).is_some() || self.jump(x, y, 0, dy).is_some() {
+                    return Some((x, y));
+                }
+            } else if self.forced(x, y, dx, dy) {
+                return Some((x, y));
+            }
+        }
+    }
+
+    /// Check if a straight move to `(x, y)` has forced neighbors
+    fn forced(&self, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+        [(dy, dx), (-dy, -dx)].iter().any(|(sx, sy)| {
+            self.grid.passable(x + sx, y + sy) && !self.grid.passable(x + sx - dx, y + sy - dy)
+        })
+    }
+
+    /// Reconstruct path that was found. Straight segments between jump points are filled in.
+    fn construct_path(&self) -> (Path, Cost) {
+        let mut jump_points = vec![self.goal];
+        while jump_points[jump_points.len() - 1] != self.start {
+            jump_points.push(self.cache[jump_points[jump_points.len() - 1]].parent);
+        }
+        jump_points.reverse();
+
+        let mut path = vec![self.start];
+        for pair in jump_points.windows(2) {
+            let (mut x, mut y) = self.xy(pair[0]);
+            let (tx, ty) = self.xy(pair[1]);
+            let (dx, dy) = ((tx - x).signum(), (ty - y).signum());
+            while (x, y) != (tx, ty) {
+                x += dx;
+                y += dy;
+                path.push(self.index(x, y));
+            }
+        }
+
+        (path, self.cache.get_cost(self.goal))
+    }
+
+    fn xy(&self, node: Node) -> (i32, i32) {
+        let (x, y) = index_to_xy(node, self.grid.get_width());
+        (x as i32, y as i32)
+    }
+
+    fn index(&self, x: i32, y: i32) -> Node {
+        xy_to_index(x as usize, y as usize, self.grid.get_width())
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        let mut closed = 0;
+        self.cache
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.cost != 0.0)
+            .for_each(|(i, _)| {
+                closed += 1;
+                print.add_inclosed(i.try_into().unwrap());
+            });
+
+        self.frontier.iter().for_each(|n| print.add_infrontier(*n));
+        let top3 = self.frontier.top3();
+        if let Some(first) = top3.0 {
+            print.add_first(first);
+        }
+        if let Some(second) = top3.1 {
+            print.add_second(second);
+        }
+        if let Some(third) = top3.2 {
+            print.add_third(third);
+        }
+        print.add_header("|Open|", self.frontier.size());
+        print.add_header("|Jump points|", closed);
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.cache.get_cost(node)
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.cache.get_estimate(node)
+    }
+
+    /// Get size of [`Frontier`]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.frontier.size()
+    }
+}
+
+/// Octile distance of a move by `(dx, dy)`
+/// ```
+/// # use fringe_vs_astar::algorithms::jps::octile;
+/// # use fringe_vs_astar::DIAGONAL_COST;
+/// assert_eq!(octile(-3, 0), 3.0);
+/// assert_eq!(octile(2, -2), 2.0 * DIAGONAL_COST);
+/// ```
+#[must_use]
+pub fn octile(dx: i32, dy: i32) -> Cost {
+    let (dx, dy) = (dx.abs(), dy.abs());
+    (dx - dy).abs() as Cost + DIAGONAL_COST * dx.min(dy) as Cost
+}
@@ -0,0 +1,235 @@
+use self::table::{direction_index, JumpTable};
+use super::astar::cache::Cache;
+use super::astar::frontier::Frontier;
+use super::jps::{octile, DIRECTIONS};
+use super::{until_final, Heuristic, Limits, State};
+use crate::printable::Printable;
+use crate::{index_to_xy, xy_to_index, Cost, Node, Path};
+
+/// Precomputed jump distances and their sidecar files
+pub mod table;
+
+/// JPS+ pathfinder.
+///
+/// Works like [`Jps`](super::Jps), but jumps are not scanned during the search.
+/// Instead every jump is a single lookup from a [`JumpTable`].
+/// Goal is found by checking if it lies inside a jump, which can create an extra jump point on a diagonal.
+pub struct JpsPlus<'a> {
+    frontier: Frontier,
+    cache: Cache,
+    start: Node,
+    goal: Node,
+    table: &'a JumpTable,
+    limits: Limits,
+    /// Number of jump points expanded
+    pub expansions: usize,
+}
+
+impl<'a> JpsPlus<'a> {
+    /// Create solver of a problem for a precomputed table
+    #[must_use]
+    pub fn new(start: Node, goal: Node, table: &'a JumpTable) -> Self {
+        let size = table.get_width() * table.get_height();
+        let frontier = Frontier::new(start, size);
+
+        let heuristic = Heuristic::new(goal, table.get_width());
+        let cache = Cache::new(start, heuristic, size);
+
+        JpsPlus {
+            frontier,
+            cache,
+            start,
+            goal,
+            table,
+            limits: Limits::default(),
+            expansions: 0,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        if let Some(node) = self.frontier.pop() {
+            if node == self.goal {
+                return State::Finished(self.construct_path());
+            }
+            if let Some(reason) = self.limits.check(self.expansions) {
+                return State::Aborted(reason);
+            }
+            self.expansions += 1;
+            self.expand(node);
+            State::Processing(node)
+        } else {
+            State::NotFound
+        }
+    }
+
+    /// Look up jumps to every direction that is not pruned and add found jump points to the frontier
+    fn expand(&mut self, node: Node) {
+        let current_cost = self.cache.get_cost(node);
+        let (x, y) = self.xy(node);
+        let (gx, gy) = self.xy(self.goal);
+
+        for (dx, dy) in self.directions(node) {
+            let distance = i32::from(self.table.get(node, direction_index(dx, dy)));
+            let reach = distance.abs();
+            // Steps to the goal along both axes, if it is ahead in this direction
+            let ahead = |d: i32, from: i32, to: i32| match d {
+                0 => Some(0),
+                d if (to - from) * d > 0 => Some((to - from).abs()),
+                _ => None,
+            };
+
+            let steps = match (ahead(dx, x, gx), ahead(dy, y, gy)) {
+                // Goal is on a straight line
+                (Some(sx), Some(sy)) if dx == 0 || dy == 0 => {
+                    let on_line = (dx == 0 && gx == x) || (dy == 0 && gy == y);
+                    if on_line && sx.max(sy) <= reach {
+                        Some(sx.max(sy))
+                    } else {
+                        None
+                    }
+                }
+                // Goal is in the general direction of a diagonal
+                (Some(sx), Some(sy)) if sx.min(sy) <= reach => Some(sx.min(sy)),
+                _ => None,
+            };
+
+            let Some(steps) = steps.or((distance > 0).then_some(distance)) else {
+                continue;
+            };
+
+            let (jx, jy) = (x + dx * steps, y + dy * steps);
+            let jump_point = xy_to_index(jx as usize, jy as usize, self.table.get_width());
+            let cost = current_cost + octile(jx - x, jy - y);
+            if let Some((jump_point, weight)) = self.cache.check(jump_point, node, cost) {
+                self.frontier.push(jump_point, weight);
+            }
+        }
+    }
+
+    /// Directions that need to be searched from a [`Node`] given the direction it was reached from
+    fn directions(&self, node: Node) -> Vec<(i32, i32)> {
+        if node == self.start {
+            return DIRECTIONS.to_vec();
+        }
+        let (x, y) = self.xy(node);
+        let (px, py) = self.xy(self.cache[node].parent);
+        let (dx, dy) = ((x - px).signum(), (y - py).signum());
+
+        if dx != 0 && dy != 0 {
+            return vec![(dx, 0), (0, dy), (dx, dy)];
+        }
+
+        // Zero distance in the table means that the first step is blocked
+        let previous = xy_to_index((x - dx) as usize, (y - dy) as usize, self.table.get_width());
+        let mut directions = vec![(dx, dy)];
+        // Perpendicular directions, both of which have possibly a forced neighbor
+        for (sx, sy) in [(dy, dx), (-dy, -dx)] {
+            let side = direction_index(sx, sy);
+            if self.table.get(node, side) != 0 && self.table.get(previous, side) == 0 {
+                directions.push((sx, sy));
+                directions.push((sx + dx, sy + dy));
+            }
+        }
+        directions
+    }
+
+    /// Reconstruct path that was found. Straight segments between jump points are filled in.
+    fn construct_path(&self) -> (Path, Cost) {
+        let mut jump_points = vec![self.goal];
+        while jump_points[jump_points.len() - 1] != self.start {
+            jump_points.push(self.cache[jump_points[jump_points.len() - 1]].parent);
+        }
+        jump_points.reverse();
+
+        let mut path = vec![self.start];
+        for pair in jump_points.windows(2) {
+            let (mut x, mut y) = self.xy(pair[0]);
+            let (tx, ty) = self.xy(pair[1]);
+            let (dx, dy) = ((tx - x).signum(), (ty - y).signum());
+            while (x, y) != (tx, ty) {
+                x += dx;
+                y += dy;
+                path.push(xy_to_index(x as usize, y as usize, self.table.get_width()));
+            }
+        }
+
+        (path, self.cache.get_cost(self.goal))
+    }
+
+    fn xy(&self, node: Node) -> (i32, i32) {
+        let (x, y) = index_to_xy(node, self.table.get_width());
+        (x as i32, y as i32)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        let mut closed = 0;
+        self.cache
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.cost != 0.0)
+            .for_each(|(i, _)| {
+                closed += 1;
+                print.add_inclosed(i.try_into().unwrap());
+            });
+
+        self.frontier.iter().for_each(|n| print.add_infrontier(*n));
+        let top3 = self.frontier.top3();
+        if let Some(first) = top3.0 {
+            print.add_first(first);
+        }
+        if let Some(second) = top3.1 {
+            print.add_second(second);
+        }
+        if let Some(third) = top3.2 {
+            print.add_third(third);
+        }
+        print.add_header("|Open|", self.frontier.size());
+        print.add_header("|Jump points|", closed);
+        print.add_header("Expanded", self.expansions);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.cache.get_cost(node)
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.cache.get_estimate(node)
+    }
+
+    /// Get size of [`Frontier`]
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.frontier.size()
+    }
+}
@@ -0,0 +1,234 @@
+use crate::algorithms::jps::DIRECTIONS;
+use crate::sidecar::{checksum, read_u32};
+use crate::structures::Grid;
+use crate::{xy_to_index, Node};
+
+use anyhow::{bail, Context};
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::mem::size_of_val;
+use std::path::{Path, PathBuf};
+
+/// Identifier in the beginning of a sidecar file
+const MAGIC: &[u8; 4] = b"JPS+";
+/// Version of the sidecar format
+const VERSION: u32 = 1;
+
+/// Precomputed jump distances for every cell and every direction of [`DIRECTIONS`].
+///
+/// Positive distance means that a jump point is found after that many steps.
+/// Zero or negative distance means that only `-distance` steps can be taken before an obstacle.
+///
+/// The table can be written to a binary sidecar file next to the map and loaded again later.
+/// Sidecar contains a checksum of the map, so a stale table is never used for a changed map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpTable {
+    distances: Vec<i16>,
+    width: usize,
+    height: usize,
+    checksum: u64,
+}
+
+impl JumpTable {
+    /// Precompute jump distances for a grid.
+    #[must_use]
+    pub fn new<G: Grid>(grid: &G) -> Self {
+        let (width, height) = (grid.get_width(), grid.get_height());
+        let mut table = JumpTable {
+            distances: vec![0; width * height * DIRECTIONS.len()],
+            width,
+            height,
+            checksum: checksum(grid),
+        };
+
+        // Straight directions first, because diagonal jumps stop where straight ones find something
+        for direction in (0..DIRECTIONS.len()).step_by(2) {
+            table.sweep(grid, direction);
+        }
+        for direction in (1..DIRECTIONS.len()).step_by(2) {
+            table.sweep(grid, direction);
+        }
+        table
+    }
+
+    /// Fill distances of one direction.
+    /// Cells are visited in such an order that the next cell in the direction is always already done.
+    fn sweep<G: Grid>(&mut self, grid: &G, direction: usize) {
+        let (dx, dy) = DIRECTIONS[direction];
+        let (w, h) = (self.width as i32, self.height as i32);
+        let xs: Vec<i32> = if dx > 0 {
+            (0..w).rev().collect()
+        } else {
+            (0..w).collect()
+        };
+        let ys: Vec<i32> = if dy > 0 {
+            (0..h).rev().collect()
+        } else {
+            (0..h).collect()
+        };
+
+        for &y in &ys {
+            for &x in &xs {
+                if !grid.passable(x, y) {
+                    continue;
+                }
+                let (nx, ny) = (x + dx, y + dy);
+                let can_move = grid.passable(nx, ny)
+                    && (dx == 0 || dy == 0 || grid.passable(nx, y) && grid.passable(x, ny));
+
+                let distance = if !can_move {
+                    0
+                } else if self.is_jump_point(grid, nx, ny, dx, dy) {
+                    1
+                } else {
+                    let next = self.distances[self.index(nx, ny, direction)];
+                    if next > 0 {
+                        next + 1
+                    } else {
+                        next - 1
+                    }
+                };
+                let index = self.index(x, y, direction);
+                self.distances[index] = distance;
+            }
+        }
+    }
+
+    /// Is `(x, y)` a jump point when it is entered by moving to `(dx, dy)`.
+    /// Straight directions have to be computed before diagonal ones.
+    fn is_jump_point<G: Grid>(&self, grid: &G, x: i32, y: i32, dx: i32, dy: i32) -> bool {
+        if dx != 0 && dy != 0 {
+            let horizontal = direction_index(dx, 0);
+            let vertical = direction_index(0, dy);
+            self.distances[self.index(x, y, horizontal)] > 0
+                || self.distances[self.index(x, y, vertical)] > 0
+        } else {
+            [(dy, dx), (-dy, -dx)].iter().any(|(sx, sy)| {
+                grid.passable(x + sx, y + sy) && !grid.passable(x + sx - dx, y + sy - dy)
+            })
+        }
+    }
+
+    fn index(&self, x: i32, y: i32, direction: usize) -> usize {
+        xy_to_index(x as usize, y as usize, self.width) as usize * DIRECTIONS.len() + direction
+    }
+
+    /// Jump distance from a [`Node`] to a direction of [`DIRECTIONS`]
+    #[must_use]
+    pub fn get(&self, node: Node, direction: usize) -> i16 {
+        self.distances[node as usize * DIRECTIONS.len() + direction]
+    }
+
+    /// Width of the grid this table was built for
+    #[must_use]
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the grid this table was built for
+    #[must_use]
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    /// Size of the distances in bytes
+    #[must_use]
+    pub fn size_in_bytes(&self) -> usize {
+        size_of_val(self.distances.as_slice())
+    }
+
+    /// Location of the sidecar file for a map file. `.jps` is appended to the name of the map.
+    /// ```
+    /// # use fringe_vs_astar::algorithms::jpsplus::table::JumpTable;
+    /// # use std::path::PathBuf;
+    /// let sidecar = JumpTable::sidecar(&PathBuf::from("maps/3x3.map"));
+    /// assert_eq!(PathBuf::from("maps/3x3.map.jps"), sidecar);
+    /// ```
+    #[must_use]
+    pub fn sidecar(map_file: &Path) -> PathBuf {
+        let mut name = OsString::from(map_file.as_os_str());
+        name.push(".jps");
+        PathBuf::from(name)
+    }
+
+    /// Load the table from sidecar of a map file if it is valid for the grid, otherwise build and save it.
+    /// Returns the table and `true` if it was built.
+    pub fn load_or_build<G: Grid>(map_file: &Path, grid: &G) -> anyhow::Result<(Self, bool)> {
+        let sidecar = Self::sidecar(map_file);
+        if let Ok(table) = Self::load(&sidecar, grid) {
+            return Ok((table, false));
+        }
+        let table = Self::new(grid);
+        table.save(&sidecar)?;
+        Ok((table, true))
+    }
+
+    /// Write the table to a file.
+    /// File is first written to a temporary file and then renamed, so a half written table is never read.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut temporary = OsString::from(path.as_os_str());
+        temporary.push(format!(".{}.tmp", std::process::id()));
+        let temporary = PathBuf::from(temporary);
+
+        let mut file = BufWriter::new(File::create(&temporary)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&VERSION.to_le_bytes())?;
+        file.write_all(&(self.width as u32).to_le_bytes())?;
+        file.write_all(&(self.height as u32).to_le_bytes())?;
+        file.write_all(&self.checksum.to_le_bytes())?;
+        for distance in &self.distances {
+            file.write_all(&distance.to_le_bytes())?;
+        }
+        file.flush()?;
+        drop(file);
+
+        fs::rename(&temporary, path)
+            .with_context(|| format!("Could not write jump table {}", path.display()))
+    }
+
+    /// Read the table from a file. Fails if the file is not a table for the supplied grid.
+    pub fn load<G: Grid>(path: &Path, grid: &G) -> anyhow::Result<Self> {
+        let mut file = BufReader::new(File::open(path)?);
+
+        let mut magic = [0; 4];
+        file.read_exact(&mut magic)?;
+        if &magic != MAGIC || read_u32(&mut file)? != VERSION {
+            bail!("{} is not a jump table", path.display());
+        }
+
+        let width = read_u32(&mut file)? as usize;
+        let height = read_u32(&mut file)? as usize;
+        let mut checksum_bytes = [0; 8];
+        file.read_exact(&mut checksum_bytes)?;
+        if width != grid.get_width()
+            || height != grid.get_height()
+            || u64::from_le_bytes(checksum_bytes) != checksum(grid)
+        {
+            bail!("Jump table {} is for a different map", path.display());
+        }
+
+        let mut bytes = vec![0; width * height * DIRECTIONS.len() * 2];
+        file.read_exact(&mut bytes)?;
+        let distances = bytes
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+
+        Ok(JumpTable {
+            distances,
+            width,
+            height,
+            checksum: u64::from_le_bytes(checksum_bytes),
+        })
+    }
+}
+
+/// Index of a direction in [`DIRECTIONS`]
+#[must_use]
+pub fn direction_index(dx: i32, dy: i32) -> usize {
+    DIRECTIONS
+        .iter()
+        .position(|d| *d == (dx, dy))
+        .expect("Not a direction")
+}
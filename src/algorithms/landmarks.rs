@@ -0,0 +1,370 @@
+use super::random::XorShift;
+use super::{Dijkstra, Heuristic};
+use crate::sidecar::{checksum, read_u32};
+use crate::structures::{DistanceField, Graph};
+use crate::{Cost, Node};
+
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use rayon::prelude::*;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::mem::size_of_val;
+use std::path::{Path, PathBuf};
+
+/// Identifier in the beginning of a sidecar file
+const MAGIC: &[u8; 4] = b"ALT\0";
+/// Version of the sidecar format
+const VERSION: u32 = 1;
+/// Bytes before the landmark nodes: magic, version, width, height, checksum, strategy and count
+const HEADER_SIZE: u64 = 32;
+/// Seed of the random number generator, so that the same landmarks are chosen every time
+const SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// How landmarks are placed on the map
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+pub enum Strategy {
+    /// Random passable cells
+    Random,
+    /// Every landmark is as far as possible from the ones chosen before it
+    Farthest,
+    /// Landmarks are placed where the current ones give the worst lower bounds
+    Avoid,
+}
+
+/// Landmarks and their precomputed distances for the ALT heuristic (A*, landmarks, triangle inequality).
+///
+/// For every landmark `L`, the triangle inequality gives `|d(L, goal) - d(L, n)|` as a lower bound
+/// of the distance from `n` to goal. The largest bound over all landmarks is used as the heuristic.
+///
+/// Like a [`JumpTable`](super::jpsplus::table::JumpTable), landmarks can be saved to a sidecar file next to the map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Landmarks {
+    nodes: Vec<Node>,
+    /// Distances of every node from every landmark. Landmarks of a node are next to each other.
+    distances: Vec<Cost>,
+    strategy: Strategy,
+    width: usize,
+    height: usize,
+    checksum: u64,
+}
+
+impl Landmarks {
+    /// Choose `count` landmarks with a strategy and compute distances from them
+    #[must_use]
+    pub fn new(graph: &Graph, count: usize, strategy: Strategy) -> Self {
+        let mut random = XorShift::new(SEED);
+        let fields = match strategy {
+            Strategy::Random => {
+                let mut nodes: Vec<Node> = vec![];
+                while nodes.len() < count.min(graph.size()) {
+                    let Some(node) = random.passable(graph) else {
+                        break;
+                    };
+                    if !nodes.contains(&node) {
+                        nodes.push(node);
+                    }
+                }
+                nodes
+                    .par_iter()
+                    .map(|n| Dijkstra::distance_field(*n, graph))
+                    .collect()
+            }
+            Strategy::Farthest => farthest(graph, count, &mut random),
+            Strategy::Avoid => avoid(graph, count, &mut random),
+        };
+        Self::from_fields(graph, &fields, strategy)
+    }
+
+    /// Collect distances from distance fields of the landmarks
+    fn from_fields(graph: &Graph, fields: &[DistanceField], strategy: Strategy) -> Self {
+        let nodes = fields.iter().map(DistanceField::source).collect();
+        let distances = (0..graph.map_size() as Node)
+            .flat_map(|n| fields.iter().map(move |f| f.get(n)))
+            .collect();
+
+        Landmarks {
+            nodes,
+            distances,
+            strategy,
+            width: graph.get_width(),
+            height: graph.get_height(),
+            checksum: checksum(graph),
+        }
+    }
+
+    /// Distances of a [`Node`] from every landmark
+    #[must_use]
+    pub fn distances(&self, node: Node) -> &[Cost] {
+        let k = self.nodes.len();
+        &self.distances[node as usize * k..(node as usize + 1) * k]
+    }
+
+    /// Lower bound of the distance between two nodes
+    /// ```
+    /// # use fringe_vs_astar::algorithms::landmarks::{Landmarks, Strategy};
+    /// # use fringe_vs_astar::structures::{Graph, Map};
+    /// # use std::path::PathBuf;
+    /// let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    /// let landmarks = Landmarks::new(&graph, 2, Strategy::Farthest);
+    ///
+    /// // Walls are not reached from landmarks, so they give no bound
+    /// assert_eq!(landmarks.lower_bound(0, 4), 0.0);
+    /// assert!(landmarks.lower_bound(0, 8) <= 4.0);
+    /// ```
+    #[must_use]
+    pub fn lower_bound(&self, from: Node, to: Node) -> Cost {
+        bound(self.distances(from), self.distances(to))
+    }
+
+    /// Landmarks as nodes
+    #[must_use]
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Strategy that placed the landmarks
+    #[must_use]
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Size of the distances in bytes
+    #[must_use]
+    pub fn size_in_bytes(&self) -> usize {
+        size_of_val(self.distances.as_slice())
+    }
+
+    /// Location of the sidecar file for a map file. `.alt` is appended to the name of the map.
+    /// ```
+    /// # use fringe_vs_astar::algorithms::landmarks::Landmarks;
+    /// # use std::path::PathBuf;
+    /// let sidecar = Landmarks::sidecar(&PathBuf::from("maps/3x3.map"));
+    /// assert_eq!(PathBuf::from("maps/3x3.map.alt"), sidecar);
+    /// ```
+    #[must_use]
+    pub fn sidecar(map_file: &Path) -> PathBuf {
+        let mut name = OsString::from(map_file.as_os_str());
+        name.push(".alt");
+        PathBuf::from(name)
+    }
+
+    /// Load landmarks from sidecar of a map file if they match the graph, count and strategy,
+    /// otherwise build and save them. Returns the landmarks and `true` if they were built.
+    pub fn load_or_build(
+        map_file: &Path,
+        graph: &Graph,
+        count: usize,
+        strategy: Strategy,
+    ) -> anyhow::Result<(Self, bool)> {
+        let sidecar = Self::sidecar(map_file);
+        if let Ok(landmarks) = Self::load(&sidecar, graph) {
+            if landmarks.nodes.len() == count && landmarks.strategy == strategy {
+                return Ok((landmarks, false));
+            }
+        }
+        let landmarks = Self::new(graph, count, strategy);
+        landmarks.save(&sidecar)?;
+        Ok((landmarks, true))
+    }
+
+    /// Write landmarks to a file.
+    /// File is first written to a temporary file and then renamed, so a half written file is never read.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut temporary = OsString::from(path.as_os_str());
+        temporary.push(format!(".{}.tmp", std::process::id()));
+        let temporary = PathBuf::from(temporary);
+
+        let mut file = BufWriter::new(File::create(&temporary)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&VERSION.to_le_bytes())?;
+        file.write_all(&(self.width as u32).to_le_bytes())?;
+        file.write_all(&(self.height as u32).to_le_bytes())?;
+        file.write_all(&self.checksum.to_le_bytes())?;
+        file.write_all(&(self.strategy as u32).to_le_bytes())?;
+        file.write_all(&(self.nodes.len() as u32).to_le_bytes())?;
+        for node in &self.nodes {
+            file.write_all(&node.to_le_bytes())?;
+        }
+        for distance in &self.distances {
+            file.write_all(&distance.to_le_bytes())?;
+        }
+        file.flush()?;
+        drop(file);
+
+        fs::rename(&temporary, path)
+            .with_context(|| format!("Could not write landmarks {}", path.display()))
+    }
+
+    /// Read landmarks from a file. Fails if the file is not for the supplied graph.
+    pub fn load(path: &Path, graph: &Graph) -> anyhow::Result<Self> {
+        let file = File::open(path)?;
+        let length = file.metadata()?.len();
+        let mut file = BufReader::new(file);
+
+        let mut magic = [0; 4];
+        file.read_exact(&mut magic)?;
+        if &magic != MAGIC || read_u32(&mut file)? != VERSION {
+            bail!("{} is not a landmark file", path.display());
+        }
+
+        let width = read_u32(&mut file)? as usize;
+        let height = read_u32(&mut file)? as usize;
+        let mut checksum_bytes = [0; 8];
+        file.read_exact(&mut checksum_bytes)?;
+        if width != graph.get_width()
+            || height != graph.get_height()
+            || u64::from_le_bytes(checksum_bytes) != checksum(graph)
+        {
+            bail!("Landmarks {} are for a different map", path.display());
+        }
+
+        let strategy = match read_u32(&mut file)? {
+            0 => Strategy::Random,
+            1 => Strategy::Farthest,
+            2 => Strategy::Avoid,
+            s => bail!("Unknown landmark strategy {s}"),
+        };
+        let count = read_u32(&mut file)? as usize;
+        if count > graph.size() {
+            bail!(
+                "Landmarks {} have {count} landmarks, but the map has only {} passable nodes",
+                path.display(),
+                graph.size()
+            );
+        }
+        // Header, landmark nodes and a distance from every landmark for every node
+        let expected = HEADER_SIZE + (count + width * height * count) as u64 * 4;
+        if length != expected {
+            bail!(
+                "Landmarks {} are {length} bytes long instead of {expected}",
+                path.display()
+            );
+        }
+        let nodes: Vec<Node> = (0..count)
+            .map(|_| read_u32(&mut file))
+            .collect::<anyhow::Result<_>>()?;
+        if let Some(node) = nodes.iter().find(|n| **n as usize >= graph.map_size()) {
+            bail!("Landmark {node} of {} is outside the map", path.display());
+        }
+
+        let mut bytes = vec![0; width * height * count * 4];
+        file.read_exact(&mut bytes)?;
+        let distances = bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+
+        Ok(Landmarks {
+            nodes,
+            distances,
+            strategy,
+            width,
+            height,
+            checksum: u64::from_le_bytes(checksum_bytes),
+        })
+    }
+}
+
+/// Largest lower bound given by the landmarks. Landmarks that don't reach both nodes are skipped.
+pub(crate) fn bound(from: &[Cost], to: &[Cost]) -> Cost {
+    from.iter()
+        .zip(to)
+        .filter(|(a, b)| a.is_finite() && b.is_finite())
+        .fold(0.0, |h, (a, b)| h.max((a - b).abs()))
+}
+
+/// Each landmark is the reachable node farthest from the landmarks chosen before it
+fn farthest(graph: &Graph, count: usize, random: &mut XorShift) -> Vec<DistanceField> {
+    let Some(first) = random.passable(graph) else {
+        return vec![];
+    };
+    let mut nearest: Vec<Cost> = Dijkstra::distance_field(first, graph)
+        .iter()
+        .copied()
+        .collect();
+    let mut fields: Vec<DistanceField> = vec![];
+
+    while fields.len() < count {
+        let Some(node) = farthest_node(&nearest) else {
+            break;
+        };
+        let field = Dijkstra::distance_field(node, graph);
+        for (n, d) in nearest.iter_mut().zip(field.iter()) {
+            *n = n.min(*d);
+        }
+        fields.push(field);
+    }
+    fields
+}
+
+/// Node with the largest finite distance, if it is above zero
+fn farthest_node(distances: &[Cost]) -> Option<Node> {
+    distances
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| d.is_finite() && **d > 0.0)
+        .max_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i as Node)
+}
+
+/// Avoid strategy of Goldberg and Harrelson.
+///
+/// A shortest path tree is grown from a random root. Every node is weighted by how much its distance
+/// from the root exceeds the current lower bound. Starting from the root, the child whose subtree
+/// has the largest total weight is followed down to a leaf, which becomes the next landmark.
+/// Subtrees that already contain a landmark are skipped.
+fn avoid(graph: &Graph, count: usize, random: &mut XorShift) -> Vec<DistanceField> {
+    let mut fields: Vec<DistanceField> = vec![];
+    let mut roots = 0;
+
+    // Roots are random, so a few of them may lead only to existing landmarks
+    while fields.len() < count && roots < 10 * count {
+        roots += 1;
+        let Some(root) = random.passable(graph) else {
+            break;
+        };
+        let tree = Dijkstra::distance_field(root, graph);
+        let octile = Heuristic::new(root, graph.get_width());
+
+        // Children are visited before their parents when the distances decrease
+        let mut order: Vec<Node> = (0..graph.map_size() as Node)
+            .filter(|n| tree.get(*n).is_finite())
+            .collect();
+        order.sort_by(|a, b| tree.get(*b).total_cmp(&tree.get(*a)));
+
+        let mut size = vec![0.0; graph.map_size()];
+        let mut has_landmark = vec![false; graph.map_size()];
+        let mut children = vec![vec![]; graph.map_size()];
+        for &node in &order {
+            let lower = fields
+                .iter()
+                .map(|f| (f.get(root), f.get(node)))
+                .filter(|(a, b)| a.is_finite() && b.is_finite())
+                .fold(octile.calc(node), |h, (a, b)| h.max((a - b).abs()));
+            size[node as usize] += tree.get(node) - lower;
+            has_landmark[node as usize] |= fields.iter().any(|f| f.source() == node);
+
+            let parent = tree.parent(node).unwrap();
+            if parent != node {
+                children[parent as usize].push(node);
+                has_landmark[parent as usize] |= has_landmark[node as usize];
+                size[parent as usize] += size[node as usize];
+            }
+        }
+
+        let mut node = root;
+        while let Some(child) = children[node as usize]
+            .iter()
+            .filter(|c| !has_landmark[**c as usize])
+            .max_by(|a, b| size[**a as usize].total_cmp(&size[**b as usize]))
+        {
+            node = *child;
+        }
+        if !has_landmark[node as usize] {
+            fields.push(Dijkstra::distance_field(node, graph));
+        }
+    }
+    fields
+}
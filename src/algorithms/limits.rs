@@ -0,0 +1,116 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Clock and cancellation flag are read only once in this many expansions,
+/// because reading the clock takes longer than an expansion
+const CHECK_INTERVAL: usize = 64;
+
+/// Why a search was stopped before it ended
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Abort {
+    /// Search expanded as many nodes as it was allowed to
+    Expansions,
+    /// Deadline passed
+    Deadline,
+    /// Cancellation flag was set
+    Cancelled,
+}
+
+impl Display for Abort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Abort::Expansions => write!(f, "expansion limit reached"),
+            Abort::Deadline => write!(f, "deadline passed"),
+            Abort::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Limits that stop a search early: maximum number of expansions, a deadline
+/// and a flag that another thread can set to cancel the search.
+///
+/// Searches check their limits before every expansion and end with [`State::Aborted`](super::State::Aborted)
+/// when one of them is reached. Deadline and flag are noticed within [`CHECK_INTERVAL`] expansions.
+/// ```
+/// # use fringe_vs_astar::algorithms::limits::{Abort, Limits};
+/// # use std::sync::atomic::{AtomicBool, Ordering};
+/// # use std::sync::Arc;
+/// # use std::time::Instant;
+/// let limits = Limits::default().with_expansions(100);
+/// assert_eq!(limits.check(99), None);
+/// assert_eq!(limits.check(100), Some(Abort::Expansions));
+///
+/// let cancel = Arc::new(AtomicBool::new(false));
+/// let limits = Limits::default().with_cancel(cancel.clone());
+/// assert_eq!(limits.check(0), None);
+/// cancel.store(true, Ordering::Relaxed);
+/// assert_eq!(limits.check(0), Some(Abort::Cancelled));
+///
+/// let limits = Limits::default().with_deadline(Instant::now());
+/// assert_eq!(limits.check(0), Some(Abort::Deadline));
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct Limits {
+    expansions: Option<usize>,
+    deadline: Option<Instant>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Limits {
+    /// Stop after `expansions` expansions
+    #[must_use]
+    pub fn with_expansions(mut self, expansions: usize) -> Self {
+        self.expansions = Some(expansions);
+        self
+    }
+
+    /// Stop when `deadline` has passed
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop when `timeout` has passed from now
+    #[must_use]
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Stop when `cancel` is set
+    #[must_use]
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Check if a search that has expanded `expansions` nodes must stop
+    #[must_use]
+    pub fn check(&self, expansions: usize) -> Option<Abort> {
+        if self.expansions.is_some_and(|max| expansions >= max) {
+            Some(Abort::Expansions)
+        } else if expansions % CHECK_INTERVAL == 0 {
+            self.check_now()
+        } else {
+            None
+        }
+    }
+
+    /// Check the deadline and the cancellation flag right away
+    #[must_use]
+    pub fn check_now(&self) -> Option<Abort> {
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+        {
+            Some(Abort::Cancelled)
+        } else if self.deadline.is_some_and(|d| Instant::now() >= d) {
+            Some(Abort::Deadline)
+        } else {
+            None
+        }
+    }
+}
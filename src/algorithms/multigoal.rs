@@ -0,0 +1,220 @@
+use super::astar::frontier::Frontier;
+use super::{until_final, Dijkstra, Heuristic, Limits, State};
+use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// A* to the nearest of many goals.
+///
+/// Heuristic is the smallest octile distance to any goal, which is admissible and consistent,
+/// so the first goal expanded is the nearest one.
+/// Each expansion computes a distance to every goal, so many goals make expansions slower.
+pub struct MultiGoal<'a> {
+    frontier: Frontier,
+    costs: Vec<Cost>,
+    parents: Vec<Node>,
+    closed: Vec<bool>,
+    heuristics: Vec<Heuristic>,
+    goals: HashSet<Node>,
+    limits: Limits,
+    pub expansions: usize,
+    start: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> MultiGoal<'a> {
+    /// Initialize the search with a start, goals and a graph to be acted upon.
+    #[must_use]
+    pub fn new(start: Node, goals: &[Node], graph: &'a Graph) -> Self {
+        let size = graph.map_size();
+        let width = graph.get_width();
+        let mut costs = vec![Cost::INFINITY; size];
+        costs[start as usize] = 0.0;
+
+        MultiGoal {
+            frontier: Frontier::new(start, size),
+            costs,
+            parents: vec![start; size],
+            closed: vec![false; size],
+            heuristics: goals.iter().map(|g| Heuristic::new(*g, width)).collect(),
+            goals: goals.iter().copied().collect(),
+            limits: Limits::default(),
+            expansions: 0,
+            start,
+            graph,
+        }
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Find the path to the nearest goal. The goal that was reached is the last [`Node`] of the path.
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        let Some(node) =
+            std::iter::from_fn(|| self.frontier.pop()).find(|node| !self.closed[*node as usize])
+        else {
+            return State::NotFound;
+        };
+        self.closed[node as usize] = true;
+
+        if self.goals.contains(&node) {
+            return State::Finished((self.construct_path(node), self.costs[node as usize]));
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let cost = self.costs[node as usize];
+        for (child, w) in self.graph.neighbors(node) {
+            let child_cost = cost + w;
+            if child_cost < self.costs[*child as usize] {
+                self.costs[*child as usize] = child_cost;
+                self.parents[*child as usize] = node;
+                self.frontier
+                    .push(*child, child_cost + self.get_heuristic(*child));
+            }
+        }
+        State::Processing(node)
+    }
+
+    /// Octile distance to the nearest goal
+    fn get_heuristic(&self, node: Node) -> Cost {
+        self.heuristics
+            .iter()
+            .map(|h| h.calc(node))
+            .fold(Cost::INFINITY, Cost::min)
+    }
+
+    /// Follow parents back to start
+    fn construct_path(&self, goal: Node) -> Path {
+        let mut path = vec![goal];
+        while path[path.len() - 1] != self.start {
+            path.push(self.parents[path[path.len() - 1] as usize]);
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Shortest distances from every source to every target.
+///
+/// Each row is one search of Dijkstra's algorithm from a source,
+/// and the search stops as soon as every target has been reached.
+/// Rows are computed in parallel. Unreachable targets are at an infinite distance.
+/// ```
+/// # use fringe_vs_astar::algorithms::multigoal::DistanceMatrix;
+/// # use fringe_vs_astar::structures::{Graph, Map};
+/// # use fringe_vs_astar::Cost;
+/// # use std::path::PathBuf;
+/// // ... | 012
+/// // .@. | 345
+/// // ... | 678
+/// let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+/// let matrix = DistanceMatrix::new(vec![0, 1], vec![2, 4, 8], &graph);
+///
+/// assert_eq!(matrix.row(0), &[2.0, Cost::INFINITY, 4.0]);
+/// assert_eq!(matrix.get(1, 0), 1.0);
+/// assert_eq!(matrix.get(1, 2), 3.0);
+/// ```
+pub struct DistanceMatrix {
+    sources: Vec<Node>,
+    targets: Vec<Node>,
+    distances: Vec<Cost>,
+}
+
+impl DistanceMatrix {
+    /// Compute distances between sources and targets of a graph
+    #[must_use]
+    pub fn new(sources: Vec<Node>, targets: Vec<Node>, graph: &Graph) -> Self {
+        let distances = sources
+            .par_iter()
+            .flat_map_iter(|source| Self::distances_from(*source, &targets, graph))
+            .collect();
+
+        DistanceMatrix {
+            sources,
+            targets,
+            distances,
+        }
+    }
+
+    /// Run Dijkstra's algorithm from a source until every target is expanded
+    fn distances_from(source: Node, targets: &[Node], graph: &Graph) -> Vec<Cost> {
+        let mut remaining: HashSet<Node> = targets.iter().copied().collect();
+        let mut dijkstra = Dijkstra::exhaustive(source, graph);
+        remaining.remove(&source);
+        while !remaining.is_empty() {
+            match dijkstra.progress() {
+                State::Processing(node) => {
+                    remaining.remove(&node);
+                }
+                _ => break,
+            }
+        }
+        targets.iter().map(|t| dijkstra.get_cost(*t)).collect()
+    }
+
+    /// Distance from a source to a target, by their indices
+    #[must_use]
+    pub fn get(&self, source: usize, target: usize) -> Cost {
+        self.distances[source * self.targets.len() + target]
+    }
+
+    /// Distances from a source to every target
+    #[must_use]
+    pub fn row(&self, source: usize) -> &[Cost] {
+        let width = self.targets.len();
+        &self.distances[source * width..(source + 1) * width]
+    }
+
+    /// Nodes of the sources
+    #[must_use]
+    pub fn sources(&self) -> &[Node] {
+        &self.sources
+    }
+
+    /// Nodes of the targets
+    #[must_use]
+    pub fn targets(&self) -> &[Node] {
+        &self.targets
+    }
+}
+
+impl Display for DistanceMatrix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for source in 0..self.sources.len() {
+            let row: Vec<String> = self
+                .row(source)
+                .iter()
+                .map(|d| {
+                    if d.is_finite() {
+                        format!("{d:.3}")
+                    } else {
+                        "∞".to_string()
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", row.join("\t"))?;
+        }
+        Ok(())
+    }
+}
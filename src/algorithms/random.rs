@@ -0,0 +1,69 @@
+use crate::structures::Graph;
+use crate::Node;
+
+/// Replaces a zero seed, which would only produce zeros
+const NONZERO_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Xorshift64* pseudorandom number generator
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// Initialize the generator. Zero seed is replaced, because it would only produce zeros.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        XorShift {
+            state: if seed == 0 { NONZERO_SEED } else { seed },
+        }
+    }
+
+    /// Next pseudorandom number
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Pseudorandom number in `0..n`
+    /// ```
+    /// # use fringe_vs_astar::algorithms::random::XorShift;
+    /// let mut random = XorShift::new(1);
+    /// assert!((0..100).all(|_| random.below(10) < 10));
+    /// ```
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Random [`Node`] that has neighbors, or `None` if no node has any.
+    /// After as many misses as there are nodes, the nodes are scanned from a random one onwards.
+    /// ```
+    /// # use fringe_vs_astar::algorithms::random::XorShift;
+    /// # use fringe_vs_astar::structures::{Graph, Map};
+    /// # use std::path::PathBuf;
+    /// let mut graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    /// let mut random = XorShift::new(1);
+    /// assert!(random.passable(&graph).is_some());
+    ///
+    /// (0..9).for_each(|n| graph.set_passable(n, false));
+    /// assert_eq!(random.passable(&graph), None);
+    /// ```
+    pub fn passable(&mut self, graph: &Graph) -> Option<Node> {
+        let size = graph.map_size();
+        if size == 0 {
+            return None;
+        }
+        let has_neighbors = |node: &Node| graph.neighbors(*node).len() > 0;
+        (0..size)
+            .map(|_| self.below(size) as Node)
+            .find(has_neighbors)
+            .or_else(|| {
+                let from = self.below(size);
+                (from..size)
+                    .chain(0..from)
+                    .map(|n| n as Node)
+                    .find(has_neighbors)
+            })
+    }
+}
@@ -0,0 +1,284 @@
+use super::{until_final, Limits, State};
+use crate::algorithms::astar::weighted_cell::WeightedCell;
+use crate::obstacles::Obstacles;
+use crate::printable::Printable;
+use crate::structures::Graph;
+use crate::{index_to_xy, xy_to_index, Cost, Node, Path};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Timesteps `(first, last)` when a cell is free of obstacles. Last is [`u32::MAX`] if the cell stays free.
+pub type Interval = (u32, u32);
+
+/// Safe interval of a cell that no obstacle ever visits
+const ALWAYS: [Interval; 1] = [(0, u32::MAX)];
+
+/// Arrival to a [`Node`] in one of its safe intervals
+#[derive(Clone, Copy)]
+struct Arrival {
+    node: Node,
+    interval: usize,
+    time: u32,
+    parent: u32,
+}
+
+/// Safe Interval Path Planning around moving obstacles.
+///
+/// Time is split into timesteps. Every move, straight or diagonal, takes one timestep, and so does waiting.
+/// Timesteps when no obstacle is in a cell form its safe intervals.
+/// A state is a [`Node`] and one of its safe intervals, and it is reached as early as possible,
+/// so waiting only happens to let an obstacle pass.
+/// Moves that would swap cells with an obstacle are not taken.
+///
+/// Arrival time at the goal is minimized, and the goal must be reached in its last safe interval,
+/// which never ends. Returned path has a cell for every timestep, so waits are repeated cells,
+/// and its cost is the length of the moves.
+pub struct Sipp<'a> {
+    intervals: HashMap<Node, Vec<Interval>>,
+    /// Moves of obstacles as `(from, to, timestep)`
+    moves: HashSet<(Node, Node, u32)>,
+    arrivals: Vec<Arrival>,
+    earliest: HashMap<(Node, usize), u32>,
+    heap: BinaryHeap<WeightedCell>,
+    current: Option<Arrival>,
+    obstacles: &'a Obstacles,
+    limits: Limits,
+    pub expansions: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> Sipp<'a> {
+    /// Compute safe intervals of every cell the obstacles visit and initialize the search
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph, obstacles: &'a Obstacles) -> Self {
+        let width = graph.get_width();
+        let mut occupied: HashMap<Node, Vec<u32>> = HashMap::new();
+        let mut moves = HashSet::new();
+        for trajectory in obstacles.iter() {
+            let mut previous: Option<(u32, Node)> = None;
+            for (time, node) in trajectory.nodes(width) {
+                occupied.entry(node).or_default().push(time);
+                if let Some((_, from)) = previous.filter(|(_, from)| *from != node) {
+                    moves.insert((from, node, time - 1));
+                }
+                previous = Some((time, node));
+            }
+        }
+        let intervals = occupied
+            .into_iter()
+            .map(|(node, times)| (node, safe_intervals(times)))
+            .collect();
+
+        let mut sipp = Sipp {
+            intervals,
+            moves,
+            arrivals: vec![],
+            earliest: HashMap::new(),
+            heap: BinaryHeap::new(),
+            current: None,
+            obstacles,
+            limits: Limits::default(),
+            expansions: 0,
+            start,
+            goal,
+            graph,
+        };
+        if let Some((0, _)) = sipp.intervals_of(start).first() {
+            sipp.arrive(start, 0, 0, 0);
+        }
+        sipp
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Expand the earliest arrival with the smallest estimate.
+    /// [`State::Processing`] carries the [`Node`] that was expanded.
+    pub fn progress(&mut self) -> State {
+        let Some(WeightedCell { node: index, .. }) = self.heap.pop() else {
+            return State::NotFound;
+        };
+        let arrival = self.arrivals[index as usize];
+        if self.earliest[&(arrival.node, arrival.interval)] < arrival.time {
+            return State::Internal;
+        }
+        self.current = Some(arrival);
+
+        let (_, leave_by) = self.intervals_of(arrival.node)[arrival.interval];
+        if arrival.node == self.goal && leave_by == u32::MAX {
+            return State::Finished(self.construct_path(index));
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let mut reached = vec![];
+        for (child, _) in self.graph.neighbors(arrival.node) {
+            for (i, (first, last)) in self.intervals_of(*child).iter().enumerate() {
+                // Departure is one timestep before arrival and must happen inside the current safe interval
+                let mut time = (arrival.time + 1).max(*first);
+                while time <= *last
+                    && time - 1 <= leave_by
+                    && self.moves.contains(&(*child, arrival.node, time - 1))
+                {
+                    time += 1;
+                }
+                if time > *last || time - 1 > leave_by {
+                    continue;
+                }
+                reached.push((*child, i, time));
+            }
+        }
+        for (child, interval, time) in reached {
+            self.arrive(child, interval, time, index);
+        }
+        State::Processing(arrival.node)
+    }
+
+    /// Record an arrival if it is the earliest one to the interval
+    fn arrive(&mut self, node: Node, interval: usize, time: u32, parent: u32) {
+        if self
+            .earliest
+            .get(&(node, interval))
+            .is_some_and(|t| *t <= time)
+        {
+            return;
+        }
+        self.earliest.insert((node, interval), time);
+        self.arrivals.push(Arrival {
+            node,
+            interval,
+            time,
+            parent,
+        });
+        self.heap.push(WeightedCell::new(
+            self.arrivals.len() as u32 - 1,
+            (time + self.steps(node)) as Cost,
+        ));
+    }
+
+    /// Safe intervals of a [`Node`] in the order of time
+    fn intervals_of(&self, node: Node) -> &[Interval] {
+        self.intervals.get(&node).map_or(&ALWAYS, Vec::as_slice)
+    }
+
+    /// Lower bound for the number of timesteps to goal, when every move takes one timestep
+    fn steps(&self, node: Node) -> u32 {
+        let (x, y) = index_to_xy(node, self.graph.get_width());
+        let (gx, gy) = index_to_xy(self.goal, self.graph.get_width());
+        x.abs_diff(gx).max(y.abs_diff(gy)) as u32
+    }
+
+    /// Follow parents to start and add a cell for every timestep spent waiting
+    fn construct_path(&self, mut index: u32) -> (Path, Cost) {
+        let mut arrivals = vec![self.arrivals[index as usize]];
+        while index != 0 {
+            index = self.arrivals[index as usize].parent;
+            arrivals.push(self.arrivals[index as usize]);
+        }
+        arrivals.reverse();
+
+        let mut path = vec![];
+        let mut cost = 0.0;
+        for pair in arrivals.windows(2) {
+            path.extend((pair[0].time..pair[1].time).map(|_| pair[0].node));
+            cost += self
+                .graph
+                .neighbors(pair[0].node)
+                .find(|(n, _)| *n == pair[1].node)
+                .map_or(0.0, |(_, w)| *w);
+        }
+        path.push(self.goal);
+
+        (path, cost)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for arrival in &self.arrivals {
+            print.add_inclosed(arrival.node);
+        }
+        for cell in &self.heap {
+            print.add_inopen(self.arrivals[cell.node as usize].node);
+        }
+
+        let time = self.current.map_or(0, |a| a.time);
+        let width = self.graph.get_width();
+        for (x, y) in self.obstacles.iter().filter_map(|o| o.position(time)) {
+            print.add_first(xy_to_index(x, y, width));
+        }
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print.add_header("Timestep", time);
+        print.add_header("Obstacles", self.obstacles.len());
+        print.add_header("Expanded", self.expansions);
+        print.add_header("Arrivals", self.arrivals.len());
+
+        print
+    }
+
+    /// Get arrival time to the [`Node`] that was expanded last
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        match self.current {
+            Some(arrival) if arrival.node == node => arrival.time as Cost,
+            _ => f32::INFINITY,
+        }
+    }
+
+    /// Get estimated arrival time to goal through the [`Node`] that was expanded last
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.get_cost(node) + self.steps(node) as Cost
+    }
+
+    /// Get size of the open list
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+/// Split time into intervals between the timesteps a cell is occupied
+/// ```
+/// # use fringe_vs_astar::algorithms::sipp::safe_intervals;
+/// assert_eq!(safe_intervals(vec![3, 4, 8]), vec![(0, 2), (5, 7), (9, u32::MAX)]);
+/// assert_eq!(safe_intervals(vec![0]), vec![(1, u32::MAX)]);
+/// ```
+#[must_use]
+pub fn safe_intervals(mut occupied: Vec<u32>) -> Vec<Interval> {
+    occupied.sort_unstable();
+    occupied.dedup();
+
+    let mut intervals = vec![];
+    let mut first = 0;
+    for time in occupied {
+        if time > first {
+            intervals.push((first, time - 1));
+        }
+        first = time + 1;
+    }
+    intervals.push((first, u32::MAX));
+    intervals
+}
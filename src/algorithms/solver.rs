@@ -1,18 +1,55 @@
-use super::{AStar, FringeSearch, State};
+use super::astar::open_list::{
+    BucketQueue, DaryHeap, Frontier, OpenList, PairingHeap, Queue, RadixHeap, TiedFrontier,
+};
+use super::cpd::database::PathDatabase;
+use super::dstarlite::Replan;
+use super::fringesearch::bucket::BucketLayout;
+use super::hpa::abstraction::Abstraction;
+use super::jpsplus::table::JumpTable;
+use super::Landmarks;
+use super::TieBreak;
+use super::{
+    AStar, Ara, BeamSearch, BidirectionalAStar, BidirectionalFringe, Cpd, DStarLite, Dijkstra,
+    FocalSearch, FringeSearch, GreedySearch, Heuristic, Hpa, Ida, Jps, JpsPlus, Limits,
+    ParallelFringe, Sipp, State, ThetaStar,
+};
+use crate::edits::{Edit, Edits};
+use crate::obstacles::Obstacles;
 use crate::printable::Printable;
 use crate::problem::Problem;
 use crate::structures::Graph;
+use crate::{Cost, Node, Path};
+use anyhow::ensure;
+use rayon::ThreadPool;
 use std::cmp::max;
+use std::convert::identity;
 use std::fmt;
 use std::fmt::Display;
-use std::mem::{size_of, size_of_val};
+use std::mem::size_of_val;
+use std::sync::Arc;
 use std::time::Instant;
 
 /// Different algorithms as enums
 #[derive(Clone, Copy)]
 pub enum Algorithm {
     AStar,
+    Greedy,
+    Beam { width: usize },
     Fringe,
+    ParallelFringe,
+    Ida { table: bool },
+    Jps,
+    JpsPlus,
+    BidirectionalAStar,
+    BidirectionalFringe,
+    Focal,
+    Ara,
+    Dijkstra,
+    Theta { lazy: bool },
+    DStarLite,
+    Hpa,
+    Cpd,
+    Sipp,
 }
 
 /// Represent what is wanted as the result of a solving process.
@@ -37,6 +74,18 @@ pub struct Solver<'a> {
     result: Result,
     problem: Problem,
     graph: &'a Graph,
+    jump_table: Option<&'a JumpTable>,
+    weight: f32,
+    edits: Option<&'a Edits>,
+    abstraction: Option<&'a Abstraction>,
+    landmarks: Option<Arc<Landmarks>>,
+    database: Option<&'a PathDatabase>,
+    pool: Option<&'a ThreadPool>,
+    obstacles: Option<&'a Obstacles>,
+    buckets: Option<BucketLayout>,
+    open_list: Queue,
+    tie_break: Option<(TieBreak, u64)>,
+    limits: Limits,
 }
 
 impl<'a> Solver<'a> {
@@ -48,37 +97,519 @@ impl<'a> Solver<'a> {
             result,
             problem,
             graph,
+            jump_table: None,
+            weight: 1.0,
+            edits: None,
+            abstraction: None,
+            landmarks: None,
+            database: None,
+            pool: None,
+            obstacles: None,
+            buckets: None,
+            open_list: Queue::Binary,
+            tie_break: None,
+            limits: Limits::default(),
         }
     }
 
+    /// Supply a precomputed [`JumpTable`] for JPS+
+    #[must_use]
+    pub fn with_jump_table(mut self, jump_table: &'a JumpTable) -> Self {
+        self.jump_table = Some(jump_table);
+        self
+    }
+
+    /// Set suboptimality bound for weighted A* and focal search, or the initial weight for ARA*.
+    /// Weighted A* can't use the radix open list, see [`AStar::with_weight`].
+    #[must_use]
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Supply changes to the map that are replayed by D* Lite
+    #[must_use]
+    pub fn with_edits(mut self, edits: &'a Edits) -> Self {
+        self.edits = Some(edits);
+        self
+    }
+
+    /// Supply a prebuilt [`Abstraction`] for HPA*
+    #[must_use]
+    pub fn with_abstraction(mut self, abstraction: &'a Abstraction) -> Self {
+        self.abstraction = Some(abstraction);
+        self
+    }
+
+    /// Supply [`Landmarks`] for the ALT heuristic of A* and Fringe search
+    #[must_use]
+    pub fn with_landmarks(mut self, landmarks: &Arc<Landmarks>) -> Self {
+        self.landmarks = Some(landmarks.clone());
+        self
+    }
+
+    /// Supply a precomputed [`PathDatabase`] for CPD queries
+    #[must_use]
+    pub fn with_database(mut self, database: &'a PathDatabase) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Supply the thread pool that parallel Fringe search runs in
+    #[must_use]
+    pub fn with_pool(mut self, pool: &'a ThreadPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Supply moving [`Obstacles`] that SIPP plans around
+    #[must_use]
+    pub fn with_obstacles(mut self, obstacles: &'a Obstacles) -> Self {
+        self.obstacles = Some(obstacles);
+        self
+    }
+
+    /// Supply the [`BucketLayout`] of Fringe search and bidirectional Fringe search
+    #[must_use]
+    pub fn with_buckets(mut self, layout: BucketLayout) -> Self {
+        self.buckets = Some(layout);
+        self
+    }
+
+    /// Select the open list of A*
+    #[must_use]
+    pub fn with_open_list(mut self, open_list: Queue) -> Self {
+        self.open_list = open_list;
+        self
+    }
+
+    /// Break ties of A* and order siblings of Fringe search by `tie_break`, with `seed` for random ties.
+    /// A* breaks ties only with the binary open list.
+    #[must_use]
+    pub fn with_tie_break(mut self, tie_break: TieBreak, seed: u64) -> Self {
+        self.tie_break = Some((tie_break, seed));
+        self
+    }
+
+    /// Stop every algorithm early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// Run the algorithm with wanted printing mode
-    pub fn run(self) {
+    ///
+    /// # Errors
+    ///
+    /// A* with the radix open list can't be weighted, because weighted estimates can decrease,
+    /// and only the binary open list of A* breaks ties.
+    pub fn run(self) -> anyhow::Result<()> {
+        if matches!(self.algorithm, Algorithm::AStar) {
+            ensure!(
+                self.open_list != Queue::Radix || self.weight <= 1.0,
+                "Radix open list needs estimates that never decrease, which weight {} breaks",
+                self.weight
+            );
+            ensure!(
+                self.open_list == Queue::Binary || self.tie_break.is_none(),
+                "Only the binary open list breaks ties, not {:?}",
+                self.open_list
+            );
+        }
         match (self.algorithm, self.result.clone()) {
-            (Algorithm::AStar, Result::EndState(p)) => self.full_astar(p, false),
-            (Algorithm::AStar, Result::Full(p)) => self.full_astar(p, true),
+            (Algorithm::AStar, Result::EndState(p)) => self.printed_astar(p, false),
+            (Algorithm::AStar, Result::Full(p)) => self.printed_astar(p, true),
             (Algorithm::AStar, Result::Time(p)) => self.timed_astar(p),
+            (Algorithm::Greedy, Result::EndState(p)) => self.printed_greedy(p, false),
+            (Algorithm::Greedy, Result::Full(p)) => self.printed_greedy(p, true),
+            (Algorithm::Greedy, Result::Time(p)) => self.timed_greedy(p),
+            (Algorithm::Beam { width }, Result::EndState(p)) => self.printed_beam(p, width, false),
+            (Algorithm::Beam { width }, Result::Full(p)) => self.printed_beam(p, width, true),
+            (Algorithm::Beam { width }, Result::Time(p)) => self.timed_beam(p, width),
             (Algorithm::Fringe, Result::EndState(p)) => self.printed_fringe(p, false),
             (Algorithm::Fringe, Result::Full(p)) => self.printed_fringe(p, true),
             (Algorithm::Fringe, Result::Time(p)) => self.timed_fringe(p),
+            (Algorithm::ParallelFringe, Result::EndState(p)) => {
+                self.printed_parallel_fringe(p, false);
+            }
+            (Algorithm::ParallelFringe, Result::Full(p)) => self.printed_parallel_fringe(p, true),
+            (Algorithm::ParallelFringe, Result::Time(p)) => self.timed_parallel_fringe(p),
+            (Algorithm::Ida { table }, Result::EndState(p)) => self.printed_ida(p, table, false),
+            (Algorithm::Ida { table }, Result::Full(p)) => self.printed_ida(p, table, true),
+            (Algorithm::Ida { table }, Result::Time(p)) => self.timed_ida(p, table),
+            (Algorithm::Jps, Result::EndState(p)) => self.printed_jps(p, false),
+            (Algorithm::Jps, Result::Full(p)) => self.printed_jps(p, true),
+            (Algorithm::Jps, Result::Time(p)) => self.timed_jps(p),
+            (Algorithm::JpsPlus, Result::EndState(p)) => self.printed_jps_plus(p, false),
+            (Algorithm::JpsPlus, Result::Full(p)) => self.printed_jps_plus(p, true),
+            (Algorithm::JpsPlus, Result::Time(p)) => self.timed_jps_plus(p),
+            (Algorithm::BidirectionalAStar, Result::EndState(p)) => {
+                self.printed_bidirectional(p, false);
+            }
+            (Algorithm::BidirectionalAStar, Result::Full(p)) => self.printed_bidirectional(p, true),
+            (Algorithm::BidirectionalAStar, Result::Time(p)) => self.timed_bidirectional(p),
+            (Algorithm::BidirectionalFringe, Result::EndState(p)) => {
+                self.printed_bidirectional_fringe(p, false);
+            }
+            (Algorithm::BidirectionalFringe, Result::Full(p)) => {
+                self.printed_bidirectional_fringe(p, true);
+            }
+            (Algorithm::BidirectionalFringe, Result::Time(p)) => {
+                self.timed_bidirectional_fringe(p);
+            }
+            (Algorithm::Focal, Result::EndState(p)) => self.printed_focal(p, false),
+            (Algorithm::Focal, Result::Full(p)) => self.printed_focal(p, true),
+            (Algorithm::Focal, Result::Time(p)) => self.timed_focal(p),
+            (Algorithm::Ara, Result::EndState(p)) => self.printed_ara(p, false),
+            (Algorithm::Ara, Result::Full(p)) => self.printed_ara(p, true),
+            (Algorithm::Ara, Result::Time(p)) => self.timed_ara(p),
+            (Algorithm::Dijkstra, Result::EndState(p)) => self.printed_dijkstra(p, false),
+            (Algorithm::Dijkstra, Result::Full(p)) => self.printed_dijkstra(p, true),
+            (Algorithm::Dijkstra, Result::Time(p)) => self.timed_dijkstra(p),
+            (Algorithm::Theta { lazy }, Result::EndState(p)) => self.printed_theta(p, lazy, false),
+            (Algorithm::Theta { lazy }, Result::Full(p)) => self.printed_theta(p, lazy, true),
+            (Algorithm::Theta { lazy }, Result::Time(p)) => self.timed_theta(p, lazy),
+            (Algorithm::DStarLite, Result::EndState(p)) => self.printed_dstar_lite(p, false),
+            (Algorithm::DStarLite, Result::Full(p)) => self.printed_dstar_lite(p, true),
+            (Algorithm::DStarLite, Result::Time(p)) => self.timed_dstar_lite(p),
+            (Algorithm::Hpa, Result::EndState(p)) => self.printed_hpa(p, false),
+            (Algorithm::Hpa, Result::Full(p)) => self.printed_hpa(p, true),
+            (Algorithm::Hpa, Result::Time(p)) => self.timed_hpa(p),
+            (Algorithm::Cpd, Result::EndState(p)) => self.printed_cpd(p, false),
+            (Algorithm::Cpd, Result::Full(p)) => self.printed_cpd(p, true),
+            (Algorithm::Cpd, Result::Time(p)) => self.timed_cpd(p),
+            (Algorithm::Sipp, Result::EndState(p)) => self.printed_sipp(p, false),
+            (Algorithm::Sipp, Result::Full(p)) => self.printed_sipp(p, true),
+            (Algorithm::Sipp, Result::Time(p)) => self.timed_sipp(p),
         }
+        Ok(())
     }
 
     /// Run A* search and add path and timing to [`Printable`] before printing it.
-    fn timed_astar(self, mut printable: Printable) {
-        let start = self.problem.start;
-        let goal = self.problem.goal;
+    fn timed_astar(self, printable: Printable) {
+        // Only the binary open list breaks ties, which is checked by run
+        match (self.open_list, self.tie_break) {
+            (Queue::Binary, Some((tie_break, seed))) => {
+                self.timed_astar_with(printable, move |astar: AStar<TiedFrontier>| {
+                    astar.with_tie_break(tie_break, seed)
+                });
+            }
+            (Queue::Binary, None) => self.timed_astar_with::<Frontier>(printable, identity),
+            (Queue::Dary, _) => self.timed_astar_with::<DaryHeap<4>>(printable, identity),
+            (Queue::Pairing, _) => self.timed_astar_with::<PairingHeap>(printable, identity),
+            (Queue::Radix, _) => self.timed_astar_with::<RadixHeap>(printable, identity),
+            (Queue::Buckets, _) => self.timed_astar_with::<BucketQueue>(printable, identity),
+        }
+    }
+
+    /// `tied` adds tie-breaking to open lists that have it
+    fn timed_astar_with<O: OpenList>(
+        self,
+        printable: Printable,
+        tied: impl FnOnce(AStar<'a, O>) -> AStar<'a, O>,
+    ) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let (weight, landmarks, limits) = (self.weight, self.landmarks, self.limits);
+        Self::timed(printable, || {
+            let astar = AStar::<O>::with_open_list(start, goal, graph)
+                .with_weight(weight)
+                .with_limits(limits);
+            let astar = tied(astar);
+            match landmarks {
+                Some(landmarks) => astar.with_landmarks(landmarks).solve_limited(),
+                None => astar.solve_limited(),
+            }
+        });
+    }
+
+    /// Run greedy best-first search and add path and timing to [`Printable`] before printing it.
+    fn timed_greedy(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let (landmarks, limits) = (self.landmarks, self.limits);
+        Self::timed(printable, || {
+            let greedy = GreedySearch::new(start, goal, graph).with_limits(limits);
+            match landmarks {
+                Some(landmarks) => greedy.with_landmarks(landmarks).solve_limited(),
+                None => greedy.solve_limited(),
+            }
+        });
+    }
+
+    /// Run beam search and add path and timing to [`Printable`] before printing it.
+    fn timed_beam(self, printable: Printable, width: usize) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let (landmarks, limits) = (self.landmarks, self.limits);
+        Self::timed(printable, || {
+            let beam = BeamSearch::new(start, goal, graph, width).with_limits(limits);
+            match landmarks {
+                Some(landmarks) => beam.with_landmarks(landmarks).solve_limited(),
+                None => beam.solve_limited(),
+            }
+        });
+    }
+
+    /// Run Fringe search and add path and timing to [`Printable`] before printing it.
+    fn timed_fringe(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let (landmarks, buckets, tie_break) = (self.landmarks, self.buckets, self.tie_break);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            let mut heuristic = Heuristic::new(goal, graph.get_width());
+            if let Some(landmarks) = landmarks {
+                heuristic = heuristic.with_landmarks(landmarks);
+            }
+            let mut fringe =
+                FringeSearch::with_heuristic(start, goal, graph, heuristic).with_limits(limits);
+            if let Some(layout) = buckets {
+                fringe = fringe.with_buckets(layout);
+            }
+            if let Some((tie_break, seed)) = tie_break {
+                fringe = fringe.with_tie_break(tie_break, seed);
+            }
+            fringe.solve_limited()
+        });
+    }
+
+    /// Run parallel Fringe search in the thread pool and add path and timing to [`Printable`] before printing it.
+    fn timed_parallel_fringe(self, printable: Printable) {
+        let pool = self
+            .pool
+            .expect("Parallel Fringe search needs a thread pool");
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        pool.install(|| {
+            Self::timed(printable, || {
+                ParallelFringe::new(start, goal, graph)
+                    .with_limits(limits)
+                    .solve_limited()
+            });
+        });
+    }
+
+    /// Run IDA* search and add path and timing to [`Printable`] before printing it.
+    fn timed_ida(self, printable: Printable, table: bool) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            let ida = Ida::new(start, goal, graph).with_limits(limits);
+            if table {
+                ida.with_table().solve_limited()
+            } else {
+                ida.solve_limited()
+            }
+        });
+    }
+
+    /// Run Jump Point Search and add path and timing to [`Printable`] before printing it.
+    fn timed_jps(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            Jps::new(start, goal, graph)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run JPS+ and add path and timing to [`Printable`] before printing it.
+    fn timed_jps_plus(self, printable: Printable) {
+        let (start, goal) = (self.problem.start, self.problem.goal);
+        let table = self.jump_table.expect("JPS+ needs a jump table");
+        let limits = self.limits;
+        Self::timed(printable, || {
+            JpsPlus::new(start, goal, table)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run bidirectional A* and add path and timing to [`Printable`] before printing it.
+    fn timed_bidirectional(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            BidirectionalAStar::new(start, goal, graph)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run bidirectional Fringe search and add path and timing to [`Printable`] before printing it.
+    fn timed_bidirectional_fringe(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let (buckets, limits) = (self.buckets, self.limits);
+        Self::timed(printable, || {
+            let search = BidirectionalFringe::new(start, goal, graph).with_limits(limits);
+            match buckets {
+                Some(layout) => search.with_buckets(layout).solve_limited(),
+                None => search.solve_limited(),
+            }
+        });
+    }
+
+    /// Run focal search and add path and timing to [`Printable`] before printing it.
+    fn timed_focal(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let (weight, limits) = (self.weight, self.limits);
+        Self::timed(printable, || {
+            FocalSearch::new(start, goal, graph, weight)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run Dijkstra's algorithm and add path and timing to [`Printable`] before printing it.
+    fn timed_dijkstra(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            Dijkstra::new(start, goal, graph)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run Theta* and add path and timing to [`Printable`] before printing it.
+    fn timed_theta(self, printable: Printable, lazy: bool) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            let theta = ThetaStar::new(start, goal, graph).with_limits(limits);
+            if lazy {
+                theta.lazy().solve_limited()
+            } else {
+                theta.solve_limited()
+            }
+        });
+    }
+
+    /// Run HPA* and add path and timing to [`Printable`] before printing it.
+    fn timed_hpa(self, printable: Printable) {
+        let abstraction = self.abstraction.expect("HPA* needs an abstraction");
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let limits = self.limits;
+        Self::timed(printable, || {
+            Hpa::new(start, goal, graph, abstraction)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run CPD queries and add path and timing to [`Printable`] before printing it.
+    fn timed_cpd(self, printable: Printable) {
+        let (start, goal) = (self.problem.start, self.problem.goal);
+        let database = self.database.expect("CPD needs a path database");
+        let limits = self.limits;
+        Self::timed(printable, || {
+            Cpd::new(start, goal, database)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run SIPP and add path and timing to [`Printable`] before printing it.
+    fn timed_sipp(self, printable: Printable) {
+        let (start, goal, graph) = (self.problem.start, self.problem.goal, self.graph);
+        let obstacles = self.obstacles.expect("SIPP needs obstacles");
+        let limits = self.limits;
+        Self::timed(printable, || {
+            Sipp::new(start, goal, graph, obstacles)
+                .with_limits(limits)
+                .solve_limited()
+        });
+    }
+
+    /// Run D* Lite, replay edits and add every replan, final path and timing to [`Printable`] before printing it.
+    fn timed_dstar_lite(self, mut printable: Printable) {
+        let mut dstar = DStarLite::new(self.problem.start, self.problem.goal, self.graph)
+            .with_limits(self.limits);
+
         let now = Instant::now();
+        let state = dstar.replan_limited();
+        printable.add_header("Duration", format!("{:?}", now.elapsed()));
+        printable.add_header("Expanded", dstar.expansions);
+        if let State::Aborted(reason) = state {
+            printable.add_header("Stopped", reason);
+        }
+        let mut solution = state.solution();
+        if let Some((_, length)) = &solution {
+            printable.add_header("Length", length);
+        }
 
-        let astar = AStar::new(start, goal, self.graph);
-        let solution = astar.solve();
+        if let Some(edits) = self.edits {
+            dstar.replay(edits, |_, replan| {
+                Self::add_replan(&mut printable, &replan);
+                solution = replan.solution;
+            });
+            for edit in edits.iter() {
+                match (edit, edit.node(self.graph.get_width())) {
+                    (Edit::Block(..), Some(node)) => printable.add_wall(node),
+                    (Edit::Free(..), Some(node)) => printable.add_open(node),
+                    _ => {}
+                }
+            }
+        }
 
-        let done = Instant::now();
-        let duration = done.checked_duration_since(now);
+        if let Some((path, _)) = solution {
+            printable.add_path(path);
+        }
+        printable.add_start(self.problem.start);
+        printable.add_goal(self.problem.goal);
+        println!("{printable}");
+    }
 
+    /// Add work done by a replan of D* Lite to [`Printable`]
+    fn add_replan(printable: &mut Printable, replan: &Replan) {
+        let length = replan
+            .solution
+            .as_ref()
+            .map_or_else(|| "no path".to_string(), |(_, l)| format!("length {l}"));
         printable.add_header(
-            "Duration",
-            duration.map_or_else(|| "Error in timing".to_string(), |d| format!("{d:?}")),
+            format!("Replan {}", replan.number),
+            format!(
+                "{} changed, {} expanded in {:?}, {length}",
+                replan.changed_cells, replan.expansions, replan.duration
+            ),
         );
+    }
+
+    /// Run ARA* and add every improved solution, final path and timing to [`Printable`] before printing it.
+    fn timed_ara(self, mut printable: Printable) {
+        let mut ara = Ara::new(
+            self.problem.start,
+            self.problem.goal,
+            self.graph,
+            self.weight,
+        )
+        .with_limits(self.limits);
+        let mut solution = None;
+
+        printable.add_header("Solutions", "");
+        let now = Instant::now();
+        loop {
+            let ((path, length), bound) = match ara.progress() {
+                State::Improved(solution, bound) => (solution, bound),
+                State::Finished(solution) => (solution, 1.0),
+                State::Aborted(reason) => {
+                    printable.add_header("Stopped", reason);
+                    break;
+                }
+                State::NotFound => break,
+                State::Processing(_) | State::Internal => continue,
+            };
+            let duration = now.elapsed();
+            printable.add_header(
+                format!("  {length}"),
+                format!("≤ {bound} ⨉ optimal in {duration:?}"),
+            );
+            solution = Some((path, length));
+            if bound <= 1.0 {
+                break;
+            }
+        }
+        printable.add_header("Duration", format!("{:?}", now.elapsed()));
+
         if let Some((path, length)) = solution {
             printable.add_path(path);
             printable.add_header("Length", length);
@@ -86,14 +617,11 @@ impl<'a> Solver<'a> {
         println!("{printable}");
     }
 
-    /// Run Fringe search and add path and timing to [`Printable`] before printing it.
-    fn timed_fringe(self, mut printable: Printable) {
-        let start = self.problem.start;
-        let goal = self.problem.goal;
+    /// Time the solving function and add path and timing to [`Printable`] before printing it.
+    fn timed(mut printable: Printable, solve: impl FnOnce() -> State) {
         let now = Instant::now();
 
-        let fringe = FringeSearch::new(start, goal, self.graph);
-        let solution = fringe.solve();
+        let state = solve();
 
         let done = Instant::now();
         let duration = done.checked_duration_since(now);
@@ -103,74 +631,377 @@ impl<'a> Solver<'a> {
             duration.map_or_else(|| "Error in timing".to_string(), |d| format!("{d:?}")),
         );
 
-        if let Some((path, length)) = solution {
+        if let State::Aborted(reason) = state {
+            printable.add_header("Stopped", reason);
+        }
+        if let Some((path, length)) = state.solution() {
             printable.add_path(path);
             printable.add_header("Length", length);
         }
         println!("{printable}");
     }
 
+    /// Run A* search with the selected open list and print its inner state
+    fn printed_astar(self, printable: Printable, full: bool) {
+        // Only the binary open list breaks ties, which is checked by run
+        match (self.open_list, self.tie_break) {
+            (Queue::Binary, Some((tie_break, seed))) => {
+                self.full_astar(printable, full, move |astar: AStar<TiedFrontier>| {
+                    astar.with_tie_break(tie_break, seed)
+                });
+            }
+            (Queue::Binary, None) => self.full_astar::<Frontier>(printable, full, identity),
+            (Queue::Dary, _) => self.full_astar::<DaryHeap<4>>(printable, full, identity),
+            (Queue::Pairing, _) => self.full_astar::<PairingHeap>(printable, full, identity),
+            (Queue::Radix, _) => self.full_astar::<RadixHeap>(printable, full, identity),
+            (Queue::Buckets, _) => self.full_astar::<BucketQueue>(printable, full, identity),
+        }
+    }
+
     /// Run A* search and collect statistics and inner state.
     /// `full` indicates if every state of solving process should be printed.
-    fn full_astar(self, printable: Printable, full: bool) {
-        let mut astar = AStar::new(self.problem.start, self.problem.goal, self.graph);
-        let mut operations = 0;
-        let mut max_open = 0;
-        let mut durations = vec![];
+    /// `tied` adds tie-breaking to open lists that have it.
+    fn full_astar<O: OpenList>(
+        self,
+        printable: Printable,
+        full: bool,
+        tied: impl FnOnce(AStar<'a, O>) -> AStar<'a, O>,
+    ) {
+        let astar = AStar::<O>::with_open_list(self.problem.start, self.problem.goal, self.graph)
+            .with_weight(self.weight);
+        let mut astar = tied(astar);
+        if let Some(landmarks) = self.landmarks {
+            astar = astar.with_landmarks(landmarks);
+        }
+        astar = astar.with_limits(self.limits.clone());
 
-        println!("{printable}");
+        let hooks = Hooks::new(AStar::progress, AStar::add_to_printable, |astar, node| {
+            (astar.get_cost(node), astar.get_estimate(node))
+        })
+        .with_size("Max |Open|", AStar::size);
+        Self::printed(&printable, full, &mut astar, hooks);
+    }
 
-        loop {
-            operations += 1;
-            let earlier = Instant::now();
-            let state = astar.progress();
-            let now = Instant::now();
-            let duration = now.duration_since(earlier);
-            durations.push(duration);
-            match state {
-                State::Processing(node) => {
-                    max_open = max(max_open, astar.size());
-                    if full {
-                        let mut print = printable.clone();
-                        print.add_header("Operations", operations);
-                        print = astar.add_to_printable(print);
-                        print.add_current(Some((
-                            node,
-                            astar.get_cost(node),
-                            astar.get_estimate(node),
-                        )));
-                        print.add_spacing();
-                        print.add_timing(durations.clone());
-                        println!("{print}");
-                    }
-                }
-                State::Finished((path, cost)) => {
-                    let mut print = printable.clone();
-                    print.add_header("Operations", operations);
-                    print = astar.add_to_printable(print);
-                    print.add_path(path);
-                    print.add_header("Length", cost);
-                    print.add_spacing();
-                    print.add_header("Max |Open|", max_open);
-                    print.add_spacing();
-                    print.add_final_timing(durations.clone());
-                    println!("{print}");
-                    break;
-                }
-                State::NotFound => {
-                    println!("Path not found");
-                    break;
-                }
-                State::Internal => todo!(),
-            }
+    /// Run greedy best-first search and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_greedy(self, printable: Printable, full: bool) {
+        let mut greedy = GreedySearch::new(self.problem.start, self.problem.goal, self.graph);
+        if let Some(landmarks) = self.landmarks {
+            greedy = greedy.with_landmarks(landmarks);
         }
+        greedy = greedy.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            GreedySearch::progress,
+            GreedySearch::add_to_printable,
+            |greedy, node| (greedy.get_cost(node), greedy.get_estimate(node)),
+        )
+        .with_size("Max |Open|", GreedySearch::size)
+        .with_suboptimality(self.problem.length);
+        Self::printed(&printable, full, &mut greedy, hooks);
+    }
+
+    /// Run beam search and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_beam(self, printable: Printable, width: usize, full: bool) {
+        let mut beam = BeamSearch::new(self.problem.start, self.problem.goal, self.graph, width);
+        if let Some(landmarks) = self.landmarks {
+            beam = beam.with_landmarks(landmarks);
+        }
+        beam = beam.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            BeamSearch::progress,
+            BeamSearch::add_to_printable,
+            |beam, node| (beam.get_cost(node), beam.get_estimate(node)),
+        )
+        .with_size("Max |Open|", BeamSearch::size)
+        .with_suboptimality(self.problem.length)
+        .with_not_found("Path not found, beam pruned every route to goal");
+        Self::printed(&printable, full, &mut beam, hooks);
     }
 
     /// Run Fringe search and collect statistics and inner state.
     /// `full` indicates if every state of solving process should be printed.
     fn printed_fringe(self, printable: Printable, full: bool) {
-        let mut fringe = FringeSearch::new(self.problem.start, self.problem.goal, self.graph);
+        let (start, goal) = (self.problem.start, self.problem.goal);
+        let mut heuristic = Heuristic::new(goal, self.graph.get_width());
+        if let Some(landmarks) = self.landmarks {
+            heuristic = heuristic.with_landmarks(landmarks);
+        }
+        let mut fringe = FringeSearch::with_heuristic(start, goal, self.graph, heuristic);
+        if let Some(layout) = self.buckets {
+            fringe = fringe.with_buckets(layout);
+        }
+        if let Some((tie_break, seed)) = self.tie_break {
+            fringe = fringe.with_tie_break(tie_break, seed);
+        }
+        fringe = fringe.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            FringeSearch::progress,
+            FringeSearch::add_to_printable,
+            |fringe, node| (fringe.get_cost(node), fringe.get_estimate(node)),
+        )
+        .with_memory(|fringe| size_of_val(&fringe.fringe().cache));
+        Self::printed(&printable, full, &mut fringe, hooks);
+    }
+
+    /// Run parallel Fringe search in the thread pool and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_parallel_fringe(self, printable: Printable, full: bool) {
+        let pool = self
+            .pool
+            .expect("Parallel Fringe search needs a thread pool");
+        let mut fringe = ParallelFringe::new(self.problem.start, self.problem.goal, self.graph);
+        fringe = fringe.with_limits(self.limits.clone());
+
+        pool.install(|| {
+            let hooks = Hooks::new(
+                ParallelFringe::progress,
+                ParallelFringe::add_to_printable,
+                |fringe, node| (fringe.get_cost(node), fringe.get_estimate(node)),
+            );
+            Self::printed(&printable, full, &mut fringe, hooks);
+        });
+    }
+
+    /// Run IDA* search and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_ida(self, printable: Printable, table: bool, full: bool) {
+        let mut ida = Ida::new(self.problem.start, self.problem.goal, self.graph);
+        if table {
+            ida = ida.with_table();
+        }
+        ida = ida.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(Ida::progress, Ida::add_to_printable, |ida, node| {
+            (ida.get_cost(node), ida.get_estimate(node))
+        })
+        .with_size("Max depth", Ida::size);
+        Self::printed(&printable, full, &mut ida, hooks);
+    }
+
+    /// Run Jump Point Search and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_jps(self, printable: Printable, full: bool) {
+        let mut jps = Jps::new(self.problem.start, self.problem.goal, self.graph);
+        jps = jps.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(Jps::progress, Jps::add_to_printable, |jps, node| {
+            (jps.get_cost(node), jps.get_estimate(node))
+        })
+        .with_size("Max |Open|", Jps::size);
+        Self::printed(&printable, full, &mut jps, hooks);
+    }
+
+    /// Run JPS+ and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_jps_plus(self, printable: Printable, full: bool) {
+        let table = self.jump_table.expect("JPS+ needs a jump table");
+        let mut jps = JpsPlus::new(self.problem.start, self.problem.goal, table);
+        jps = jps.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(JpsPlus::progress, JpsPlus::add_to_printable, |jps, node| {
+            (jps.get_cost(node), jps.get_estimate(node))
+        })
+        .with_size("Max |Open|", JpsPlus::size);
+        Self::printed(&printable, full, &mut jps, hooks);
+    }
+
+    /// Run bidirectional A* and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_bidirectional(self, printable: Printable, full: bool) {
+        let mut search = BidirectionalAStar::new(self.problem.start, self.problem.goal, self.graph);
+        search = search.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            BidirectionalAStar::progress,
+            BidirectionalAStar::add_to_printable,
+            |search, node| (search.get_cost(node), search.get_estimate(node)),
+        )
+        .with_size("Max |Open|", BidirectionalAStar::size);
+        Self::printed(&printable, full, &mut search, hooks);
+    }
+
+    /// Run bidirectional Fringe search and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_bidirectional_fringe(self, printable: Printable, full: bool) {
+        let mut search =
+            BidirectionalFringe::new(self.problem.start, self.problem.goal, self.graph);
+        if let Some(layout) = self.buckets {
+            search = search.with_buckets(layout);
+        }
+        search = search.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            BidirectionalFringe::progress,
+            BidirectionalFringe::add_to_printable,
+            |search, node| (search.get_cost(node), search.get_estimate(node)),
+        );
+        Self::printed(&printable, full, &mut search, hooks);
+    }
+
+    /// Run focal search and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_focal(self, printable: Printable, full: bool) {
+        let mut focal = FocalSearch::new(
+            self.problem.start,
+            self.problem.goal,
+            self.graph,
+            self.weight,
+        );
+        focal = focal.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            FocalSearch::progress,
+            FocalSearch::add_to_printable,
+            |focal, node| (focal.get_cost(node), focal.get_estimate(node)),
+        )
+        .with_size("Max |Open|", FocalSearch::size);
+        Self::printed(&printable, full, &mut focal, hooks);
+    }
+
+    /// Run ARA* and collect statistics and inner state.
+    /// Every improved solution is listed when the search ends.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_ara(self, printable: Printable, full: bool) {
+        let mut ara = Ara::new(
+            self.problem.start,
+            self.problem.goal,
+            self.graph,
+            self.weight,
+        );
+        ara = ara.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(Ara::progress, Ara::add_to_printable, |ara, node| {
+            (ara.get_cost(node), ara.get_estimate(node))
+        })
+        .with_size("Max |Open|", Ara::size)
+        .with_solutions();
+        Self::printed(&printable, full, &mut ara, hooks);
+    }
+
+    /// Run Dijkstra's algorithm and collect statistics and inner state.
+    /// Distances found are shown when the goal is reached.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_dijkstra(self, printable: Printable, full: bool) {
+        let mut dijkstra = Dijkstra::new(self.problem.start, self.problem.goal, self.graph);
+        dijkstra = dijkstra.with_limits(self.limits.clone());
+
+        let goal = self.problem.goal;
+        let hooks = Hooks::new(
+            Dijkstra::progress,
+            Dijkstra::add_to_printable,
+            |dijkstra, node| (dijkstra.get_cost(node), dijkstra.get_estimate(node)),
+        )
+        .with_size("Max |Open|", Dijkstra::size)
+        .with_finished(move |dijkstra, print, _, _| {
+            print.add_distance_field(dijkstra.field());
+            print.add_goal(goal);
+        });
+        Self::printed(&printable, full, &mut dijkstra, hooks);
+    }
+
+    /// Run Theta* and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_theta(self, printable: Printable, lazy: bool, full: bool) {
+        let mut theta = ThetaStar::new(self.problem.start, self.problem.goal, self.graph);
+        if lazy {
+            theta = theta.lazy();
+        }
+        theta = theta.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            ThetaStar::progress,
+            ThetaStar::add_to_printable,
+            |theta, node| (theta.get_cost(node), theta.get_estimate(node)),
+        )
+        .with_size("Max |Open|", ThetaStar::size)
+        .with_finished(|_, print, path, _| print.add_header("Waypoints", path.len()));
+        Self::printed(&printable, full, &mut theta, hooks);
+    }
+
+    /// Run D* Lite and collect statistics and inner state. Edits are replayed after the first path is found.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_dstar_lite(self, printable: Printable, full: bool) {
+        let mut dstar = DStarLite::new(self.problem.start, self.problem.goal, self.graph);
+        dstar = dstar.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(
+            DStarLite::progress,
+            DStarLite::add_to_printable,
+            |dstar, node| (dstar.get_cost(node), dstar.get_estimate(node)),
+        )
+        .with_size("Max |Open|", DStarLite::size);
+        Self::printed(&printable, full, &mut dstar, hooks);
+
+        if let Some(edits) = self.edits {
+            dstar.replay(edits, |dstar, replan| {
+                let mut print = dstar.add_to_printable(printable.clone());
+                Self::add_replan(&mut print, &replan);
+                if let Some((path, _)) = replan.solution {
+                    print.add_path(path);
+                }
+                println!("{print}");
+            });
+        }
+    }
+
+    /// Run HPA* and collect statistics and inner state of the abstract search.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_hpa(self, printable: Printable, full: bool) {
+        let abstraction = self.abstraction.expect("HPA* needs an abstraction");
+        let mut hpa = Hpa::new(
+            self.problem.start,
+            self.problem.goal,
+            self.graph,
+            abstraction,
+        );
+        hpa = hpa.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(Hpa::progress, Hpa::add_to_printable, |hpa, node| {
+            (hpa.get_cost(node), hpa.get_estimate(node))
+        })
+        .with_size("Max |Open|", Hpa::size);
+        Self::printed(&printable, full, &mut hpa, hooks);
+    }
+
+    /// Run CPD queries and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_cpd(self, printable: Printable, full: bool) {
+        let database = self.database.expect("CPD needs a path database");
+        let mut cpd = Cpd::new(self.problem.start, self.problem.goal, database);
+        cpd = cpd.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(Cpd::progress, Cpd::add_to_printable, |cpd, node| {
+            (cpd.get_cost(node), cpd.get_estimate(node))
+        });
+        Self::printed(&printable, full, &mut cpd, hooks);
+    }
+
+    /// Run SIPP and collect statistics and inner state.
+    /// `full` indicates if every state of solving process should be printed.
+    fn printed_sipp(self, printable: Printable, full: bool) {
+        let obstacles = self.obstacles.expect("SIPP needs obstacles");
+        let mut sipp = Sipp::new(self.problem.start, self.problem.goal, self.graph, obstacles);
+        sipp = sipp.with_limits(self.limits.clone());
+
+        let hooks = Hooks::new(Sipp::progress, Sipp::add_to_printable, |sipp, node| {
+            (sipp.get_cost(node), sipp.get_estimate(node))
+        })
+        .with_size("Max |Open|", Sipp::size)
+        .with_finished(|_, print, path, _| print.add_header("Arrival", path.len() - 1));
+        Self::printed(&printable, full, &mut sipp, hooks);
+    }
+
+    /// Run a search step by step with its `hooks` and print its inner state.
+    /// `full` indicates if every state of solving process should be printed, otherwise only the end is.
+    /// Improved solutions are listed when the search ends.
+    fn printed<S>(printable: &Printable, full: bool, search: &mut S, hooks: Hooks<S>) {
         let mut operations = 0;
+        let mut max_size = 0;
+        let mut solutions = vec![];
         let mut durations = vec![];
 
         println!("{printable}");
@@ -178,22 +1009,47 @@ impl<'a> Solver<'a> {
         loop {
             operations += 1;
             let earlier = Instant::now();
-            let state = fringe.progress();
+            let state = (hooks.progress)(search);
             let now = Instant::now();
             let duration = now.duration_since(earlier);
             durations.push(duration);
 
             match state {
                 State::Processing(node) => {
+                    if let Some((_, size)) = hooks.size {
+                        max_size = max(max_size, size(search));
+                    }
+                    if full {
+                        let (cost, estimate) = (hooks.current)(search, node);
+                        let mut print = printable.clone();
+                        print.add_header("Operations", operations);
+                        print = (hooks.add_to_printable)(search, print);
+                        print.add_current(Some((node, cost, estimate)));
+                        print.add_spacing();
+                        print.add_timing(durations.clone());
+                        println!("{print}");
+                    }
+                }
+                State::Internal => {
+                    if full {
+                        let mut print = printable.clone();
+                        print.add_header("Operations", operations);
+                        print = (hooks.add_to_printable)(search, print);
+                        print.add_current(None);
+                        print.add_spacing();
+                        print.add_timing(durations.clone());
+                        println!("{print}");
+                    }
+                }
+                State::Improved((path, cost), bound) => {
+                    solutions.push((operations, cost, bound));
                     if full {
                         let mut print = printable.clone();
                         print.add_header("Operations", operations);
-                        print = fringe.add_to_printable(print);
-                        print.add_current(Some((
-                            node,
-                            fringe.get_cost(node),
-                            fringe.get_estimate(node),
-                        )));
+                        print = (hooks.add_to_printable)(search, print);
+                        print.add_path(path);
+                        print.add_header("Length", cost);
+                        print.add_header("Bound", bound);
                         print.add_spacing();
                         print.add_timing(durations.clone());
                         println!("{print}");
@@ -202,35 +1058,135 @@ impl<'a> Solver<'a> {
                 State::Finished((path, cost)) => {
                     let mut print = printable.clone();
                     print.add_header("Operations", operations);
-                    print = fringe.add_to_printable(print);
+                    print = (hooks.add_to_printable)(search, print);
+                    (hooks.finished)(search, &mut print, &path, cost);
                     print.add_path(path);
                     print.add_header("Length", cost);
+                    if let Some(expected) = hooks.suboptimality {
+                        print.add_header("Suboptimality", cost / expected.max(Cost::EPSILON));
+                    }
                     print.add_spacing();
-                    print.add_spacing();
-                    print.add_final_timing(durations.clone());
-                    print.add_header(
-                        "Memory:",
-                        format!("{} bytes", size_of_val(&fringe.fringe().cache)),
-                    );
+                    if let Some((label, _)) = hooks.size {
+                        print.add_header(label, max_size);
+                    }
+                    if hooks.solutions {
+                        solutions.push((operations, cost, 1.0));
+                        print.add_header("Solutions", "");
+                        for (operations, length, bound) in &solutions {
+                            print.add_header(
+                                format!("  {length}"),
+                                format!("≤ {bound} ⨉ optimal after {operations} operations"),
+                            );
+                        }
+                    }
+                    // Sizes are set apart from timing, and so is memory printed after it
+                    if hooks.size.is_some() || hooks.solutions || hooks.memory.is_some() {
+                        print.add_spacing();
+                    }
+                    print.add_final_timing(durations);
+                    if let Some(memory) = hooks.memory {
+                        print.add_header("Memory:", format!("{} bytes", memory(search)));
+                    }
                     println!("{print}");
                     break;
                 }
                 State::NotFound => {
-                    println!("Path not found");
+                    println!("{}", hooks.not_found);
                     break;
                 }
-                State::Internal => {
-                    if full {
-                        let mut print = printable.clone();
-                        print.add_header("Operations", operations);
-                        print = fringe.add_to_printable(print);
-                        print.add_current(None);
-                        print.add_spacing();
-                        print.add_timing(durations.clone());
-                        println!("{print}");
-                    }
+                State::Aborted(reason) => {
+                    println!("Search stopped: {reason}");
+                    break;
                 }
             }
         }
     }
 }
+
+/// Size of a search, like the length of its open list or bytes it uses
+type Size<S> = fn(&S) -> usize;
+/// Hook that adds what a search shows about a found path and its length
+type Finished<S> = Box<dyn FnOnce(&S, &mut Printable, &Path, Cost)>;
+
+/// What [`Solver::printed`] needs to run a search and look into it.
+/// Every algorithm gives its own, and adds only what it prints differently.
+struct Hooks<S> {
+    /// Advance the search by one step
+    progress: fn(&mut S) -> State,
+    /// Add inner state of the search
+    add_to_printable: fn(&S, Printable) -> Printable,
+    /// Cost and estimate of the current node
+    current: fn(&mut S, Node) -> (Cost, Cost),
+    /// Size of the open list, largest of which is printed under the label when a path is found
+    size: Option<(&'static str, Size<S>)>,
+    /// Add what the algorithm shows about a found path, before the path itself
+    finished: Finished<S>,
+    /// Expected length, compared to the length of a found path
+    suboptimality: Option<Cost>,
+    /// List every improved solution and its bound when a path is found
+    solutions: bool,
+    /// Memory used by the search, printed last when a path is found
+    memory: Option<Size<S>>,
+    /// Printed when the search ends without a path
+    not_found: &'static str,
+}
+
+impl<S> Hooks<S> {
+    /// Hooks that every search has. Nothing more is printed when a path is found.
+    fn new(
+        progress: fn(&mut S) -> State,
+        add_to_printable: fn(&S, Printable) -> Printable,
+        current: fn(&mut S, Node) -> (Cost, Cost),
+    ) -> Self {
+        Hooks {
+            progress,
+            add_to_printable,
+            current,
+            size: None,
+            finished: Box::new(|_, _, _, _| {}),
+            suboptimality: None,
+            solutions: false,
+            memory: None,
+            not_found: "Path not found",
+        }
+    }
+
+    /// Track the largest size of the open list and print it under `label`
+    fn with_size(mut self, label: &'static str, size: Size<S>) -> Self {
+        self.size = Some((label, size));
+        self
+    }
+
+    /// Add what the algorithm shows about a found path
+    fn with_finished(
+        mut self,
+        finished: impl FnOnce(&S, &mut Printable, &Path, Cost) + 'static,
+    ) -> Self {
+        self.finished = Box::new(finished);
+        self
+    }
+
+    /// Print how many times longer than `expected` a found path is, if it is known
+    fn with_suboptimality(mut self, expected: Option<Cost>) -> Self {
+        self.suboptimality = expected;
+        self
+    }
+
+    /// List every improved solution and its bound when a path is found
+    fn with_solutions(mut self) -> Self {
+        self.solutions = true;
+        self
+    }
+
+    /// Print how many bytes the search uses when a path is found
+    fn with_memory(mut self, memory: Size<S>) -> Self {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// Print `not_found` instead when the search ends without a path
+    fn with_not_found(mut self, not_found: &'static str) -> Self {
+        self.not_found = not_found;
+        self
+    }
+}
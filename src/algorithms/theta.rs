@@ -0,0 +1,242 @@
+use super::astar::weighted_cell::WeightedCell;
+use super::{until_final, Limits, State};
+use crate::printable::Printable;
+use crate::structures::{Graph, Grid};
+use crate::{index_to_xy, Cost, Node, Path};
+use std::collections::BinaryHeap;
+
+/// Theta* cache value
+#[derive(Clone, Copy)]
+struct Value {
+    parent: Node,
+    cost: Cost,
+    closed: bool,
+}
+
+impl Default for Value {
+    fn default() -> Self {
+        Value {
+            parent: 0,
+            cost: f32::INFINITY,
+            closed: false,
+        }
+    }
+}
+
+/// Theta* any-angle pathfinder.
+///
+/// Works like A* over the neighbors of [`Graph`], but a [`Node`] may take the parent of the node
+/// it was reached from as its own parent, if there is a line of sight between them.
+/// Paths are lists of waypoints connected by straight lines, and their lengths are Euclidean.
+///
+/// Lazy Theta* assumes that the line of sight exists when a [`Node`] is reached,
+/// and checks it only once the node is expanded. This saves most of the line of sight checks.
+pub struct ThetaStar<'a> {
+    heap: BinaryHeap<WeightedCell>,
+    values: Vec<Value>,
+    lazy: bool,
+    limits: Limits,
+    pub expansions: usize,
+    pub sight_checks: usize,
+    start: Node,
+    goal: Node,
+    graph: &'a Graph,
+}
+
+impl<'a> ThetaStar<'a> {
+    /// Create solver of a problem for a graph
+    #[must_use]
+    pub fn new(start: Node, goal: Node, graph: &'a Graph) -> Self {
+        let mut values = vec![Value::default(); graph.map_size()];
+        values[start as usize].parent = start;
+        values[start as usize].cost = 0.0;
+
+        let mut heap = BinaryHeap::with_capacity(graph.map_size());
+        heap.push(WeightedCell::new(start, 0.0));
+
+        ThetaStar {
+            heap,
+            values,
+            lazy: false,
+            limits: Limits::default(),
+            expansions: 0,
+            sight_checks: 0,
+            start,
+            goal,
+            graph,
+        }
+    }
+
+    /// Check lines of sight only when nodes are expanded, as Lazy Theta* does
+    #[must_use]
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Stop the search early when one of `limits` is reached
+    #[must_use]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Try to solve the problem
+    #[must_use]
+    pub fn solve(self) -> Option<(Path, Cost)> {
+        self.solve_limited().solution()
+    }
+
+    /// Solve and tell how the search ended: [`State::Finished`], [`State::NotFound`] or [`State::Aborted`]
+    #[must_use]
+    pub fn solve_limited(mut self) -> State {
+        until_final(|| self.progress())
+    }
+
+    /// Advance solving by one step
+    pub fn progress(&mut self) -> State {
+        // Heap may have outdated duplicates of already expanded nodes
+        let Some(node) = std::iter::from_fn(|| self.heap.pop())
+            .map(|c| c.node)
+            .find(|node| !self.values[*node as usize].closed)
+        else {
+            return State::NotFound;
+        };
+        self.values[node as usize].closed = true;
+
+        if self.lazy {
+            self.set_vertex(node);
+        }
+        if node == self.goal {
+            return State::Finished(self.construct_path());
+        }
+        if let Some(reason) = self.limits.check(self.expansions) {
+            return State::Aborted(reason);
+        }
+        self.expansions += 1;
+
+        let parent = self.values[node as usize].parent;
+        let graph = self.graph;
+        for (child, w) in graph.neighbors(node) {
+            let child = *child;
+            if self.values[child as usize].closed {
+                continue;
+            }
+
+            // Lazy variant always tries the parent, and fixes it later if there is no line of sight
+            let (parent, cost) = if self.lazy || self.sees(parent, child) {
+                (
+                    parent,
+                    self.values[parent as usize].cost + self.distance(parent, child),
+                )
+            } else {
+                (node, self.values[node as usize].cost + w)
+            };
+
+            if cost < self.values[child as usize].cost {
+                self.values[child as usize].cost = cost;
+                self.values[child as usize].parent = parent;
+                let estimate = cost + self.distance(child, self.goal);
+                self.heap.push(WeightedCell::new(child, estimate));
+            }
+        }
+        State::Processing(node)
+    }
+
+    /// Make sure that an expanded [`Node`] sees its parent.
+    /// If not, the best expanded neighbor becomes the parent.
+    fn set_vertex(&mut self, node: Node) {
+        let parent = self.values[node as usize].parent;
+        if self.sees(parent, node) {
+            return;
+        }
+
+        let (parent, cost) = self
+            .graph
+            .neighbors(node)
+            .filter(|(n, _)| self.values[*n as usize].closed)
+            .map(|(n, w)| (*n, self.values[*n as usize].cost + w))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("Node was reached from an expanded neighbor");
+        self.values[node as usize].parent = parent;
+        self.values[node as usize].cost = cost;
+    }
+
+    /// Check line of sight between two nodes
+    fn sees(&mut self, from: Node, to: Node) -> bool {
+        self.sight_checks += 1;
+        self.graph.line_of_sight(self.xy(from), self.xy(to))
+    }
+
+    fn xy(&self, node: Node) -> (i32, i32) {
+        let (x, y) = index_to_xy(node, self.graph.get_width());
+        (x as i32, y as i32)
+    }
+
+    /// Straight line distance between two nodes
+    fn distance(&self, from: Node, to: Node) -> Cost {
+        let ((fx, fy), (tx, ty)) = (self.xy(from), self.xy(to));
+        euclidean(tx - fx, ty - fy)
+    }
+
+    /// Reconstruct waypoints of the path that was found
+    fn construct_path(&self) -> (Path, Cost) {
+        let mut path = vec![self.goal];
+        while path[path.len() - 1] != self.start {
+            path.push(self.values[path[path.len() - 1] as usize].parent);
+        }
+        path.reverse();
+
+        (path, self.values[self.goal as usize].cost)
+    }
+
+    /// Add current state to Printable
+    #[must_use]
+    pub fn add_to_printable(&self, mut print: Printable) -> Printable {
+        for (i, value) in self.values.iter().enumerate() {
+            if value.closed {
+                print.add_inclosed(i.try_into().unwrap());
+            } else if value.cost < f32::INFINITY {
+                print.add_infrontier(i.try_into().unwrap());
+            }
+        }
+
+        print.add_header("|Open|", self.heap.len());
+        print.add_header("Expanded", self.expansions);
+        print.add_header("LOS checks", self.sight_checks);
+
+        print.add_start(self.start);
+        print.add_goal(self.goal);
+
+        print
+    }
+
+    /// Get current cost of a [`Node`]
+    #[must_use]
+    pub fn get_cost(&self, node: Node) -> Cost {
+        self.values[node as usize].cost
+    }
+
+    /// Get current estimate of a [`Node`]
+    #[must_use]
+    pub fn get_estimate(&self, node: Node) -> Cost {
+        self.values[node as usize].cost + self.distance(node, self.goal)
+    }
+
+    /// Get size of the open list, including outdated entries
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.heap.len()
+    }
+}
+
+/// Euclidean length of a move by `(dx, dy)`
+/// ```
+/// # use fringe_vs_astar::algorithms::theta::euclidean;
+/// assert_eq!(euclidean(3, -4), 5.0);
+/// assert_eq!(euclidean(0, 2), 2.0);
+/// ```
+#[must_use]
+pub fn euclidean(dx: i32, dy: i32) -> Cost {
+    (dx as Cost).hypot(dy as Cost)
+}
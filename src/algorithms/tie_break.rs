@@ -0,0 +1,65 @@
+use super::random::XorShift;
+use crate::Cost;
+use clap::ValueEnum;
+
+/// How nodes with equal estimates are ordered
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum, Debug)]
+pub enum TieBreak {
+    /// Node with the larger cost so far, which is usually closer to the goal
+    HighG,
+    /// Node with the smaller cost so far
+    LowG,
+    /// Node that was found last
+    Lifo,
+    /// Node that was found first
+    Fifo,
+    /// Random node, reproducible with a seed
+    Random,
+}
+
+/// Keys that order nodes with equal estimates by a [`TieBreak`]. Larger keys go first.
+/// ```
+/// # use fringe_vs_astar::algorithms::tie_break::{TieBreak, Ties};
+/// let mut ties = Ties::new(TieBreak::HighG, 0);
+/// assert!(ties.key(3.0) > ties.key(2.5));
+///
+/// let mut ties = Ties::new(TieBreak::Fifo, 0);
+/// let first = ties.key(1.0);
+/// assert!(first > ties.key(1.0));
+/// ```
+pub struct Ties {
+    tie_break: TieBreak,
+    counter: u32,
+    random: XorShift,
+}
+
+impl Ties {
+    /// Break ties by `tie_break`. `seed` is used only by [`TieBreak::Random`].
+    #[must_use]
+    pub fn new(tie_break: TieBreak, seed: u64) -> Self {
+        Ties {
+            tie_break,
+            counter: 0,
+            random: XorShift::new(seed),
+        }
+    }
+
+    /// Key of a node that is found with `cost`.
+    /// Bits of a non-negative float are ordered like the float itself, so costs can be keys.
+    pub fn key(&mut self, cost: Cost) -> u32 {
+        self.counter = self.counter.wrapping_add(1);
+        match self.tie_break {
+            TieBreak::HighG => cost.to_bits(),
+            TieBreak::LowG => !cost.to_bits(),
+            TieBreak::Lifo => self.counter,
+            TieBreak::Fifo => !self.counter,
+            TieBreak::Random => (self.random.next_u64() >> 32) as u32,
+        }
+    }
+
+    /// Policy of these ties
+    #[must_use]
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+}
@@ -0,0 +1,149 @@
+use super::astar::cache::Value as AStarValue;
+use super::astar::open_list::pairing::Entry as PairingEntry;
+use super::fringesearch::bucket::BucketLayout;
+use super::fringesearch::buckets::Buckets;
+use super::fringesearch::cache::Value as FringeValue;
+
+/// Buffers of [`AStar`](super::AStar), its open lists and [`FringeSearch`](super::FringeSearch) that are reused between searches.
+///
+/// A new search allocates and initializes a value for every node of the graph,
+/// which costs more than the search itself when the path is short and the map is large.
+/// Searches started with a workspace take its buffers and reset them in time relative to what the previous search touched,
+/// and `solve_in` gives them back when the search is done.
+/// A buffer of the wrong size, or one that was not given back, is replaced with a new one.
+/// ```
+/// # use fringe_vs_astar::algorithms::{AStar, FringeSearch, Workspace};
+/// # use fringe_vs_astar::structures::{Graph, Map};
+/// # use std::path::PathBuf;
+/// let graph = Graph::new(Map::new(PathBuf::from("maps/6x6.map")));
+/// let mut workspace = Workspace::default();
+/// for goal in [5, 30, 35, 14] {
+///     let astar = AStar::in_workspace(0, goal, &graph, &mut workspace);
+///     let a = astar.solve_in(&mut workspace).solution().unwrap();
+///     let fringe = FringeSearch::in_workspace(0, goal, &graph, &mut workspace);
+///     let f = fringe.solve_in(&mut workspace).solution().unwrap();
+///     assert_eq!(a.1, f.1);
+/// }
+/// ```
+#[derive(Default)]
+pub struct Workspace {
+    pub(crate) astar: Buffer<AStarValue>,
+    pub(crate) pairing: Buffer<PairingEntry>,
+    pub(crate) fringe: Buffer<FringeValue>,
+    pub(crate) buckets: Option<Buckets>,
+    pub(crate) layout: Option<BucketLayout>,
+}
+
+impl Workspace {
+    /// Lay out buckets of Fringe search by `layout` instead of the one laid out for the graph
+    #[must_use]
+    pub fn with_buckets(mut self, layout: BucketLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Take the A* buffer for a graph of `size` nodes
+    pub(crate) fn take_astar(&mut self, size: usize) -> Buffer<AStarValue> {
+        Buffer::take_sized(&mut self.astar, size)
+    }
+
+    /// Take the buffer of [`PairingHeap`](super::astar::open_list::PairingHeap) for a graph of `size` nodes
+    pub(crate) fn take_pairing(&mut self, size: usize) -> Buffer<PairingEntry> {
+        Buffer::take_sized(&mut self.pairing, size)
+    }
+
+    /// Take the Fringe search buffer for a graph of `size` nodes
+    pub(crate) fn take_fringe(&mut self, size: usize) -> Buffer<FringeValue> {
+        Buffer::take_sized(&mut self.fringe, size)
+    }
+}
+
+/// Value for every node, reset in time relative to the number of values written since the last reset.
+///
+/// The first write of a value after a reset goes through [`Buffer::first_mut`],
+/// which remembers the index so that [`Buffer::reset`] only has to make those values default again.
+/// Reads and later writes index the values directly.
+/// ```
+/// # use fringe_vs_astar::algorithms::workspace::Buffer;
+/// let mut buffer: Buffer<u32> = Buffer::new(4);
+/// *buffer.first_mut(2) = 7;
+/// *buffer.get_mut(2) += 1;
+/// assert_eq!(*buffer.get(2), 8);
+///
+/// buffer.reset();
+/// assert_eq!(*buffer.get(2), 0);
+/// ```
+pub struct Buffer<V> {
+    values: Vec<V>,
+    written: Vec<usize>,
+}
+
+impl<V: Copy + Default> Buffer<V> {
+    /// Buffer of `size` default values
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Buffer {
+            values: vec![V::default(); size],
+            written: vec![],
+        }
+    }
+
+    /// Take the buffer out of `slot` and reset it, or make a new one if it has a different size
+    fn take_sized(slot: &mut Self, size: usize) -> Self {
+        let mut buffer = std::mem::take(slot);
+        if buffer.len() == size {
+            buffer.reset();
+            buffer
+        } else {
+            Buffer::new(size)
+        }
+    }
+
+    /// Make every value written since the last reset default again
+    pub fn reset(&mut self) {
+        for index in self.written.drain(..) {
+            self.values[index] = V::default();
+        }
+    }
+
+    /// Value of a node
+    #[must_use]
+    pub fn get(&self, index: usize) -> &V {
+        &self.values[index]
+    }
+
+    /// Value of a node for writing. Only for values that were already written through [`Buffer::first_mut`].
+    pub fn get_mut(&mut self, index: usize) -> &mut V {
+        &mut self.values[index]
+    }
+
+    /// Value of a node for writing, remembered to be made default by the next reset.
+    /// Writing the same value through it twice only costs a little more on reset.
+    pub fn first_mut(&mut self, index: usize) -> &mut V {
+        self.written.push(index);
+        &mut self.values[index]
+    }
+
+    /// Values of every node
+    pub fn iter(&self) -> impl Iterator<Item = &V> {
+        self.values.iter()
+    }
+
+    /// Number of values
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if there are no values
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<V: Copy + Default> Default for Buffer<V> {
+    fn default() -> Self {
+        Buffer::new(0)
+    }
+}
@@ -1,4 +1,9 @@
-use clap::{Parser, ValueEnum};
+use crate::algorithms::astar::open_list::Queue;
+use crate::algorithms::hpa::abstraction::Refinement;
+use crate::algorithms::landmarks::Strategy;
+use crate::algorithms::TieBreak;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
 use std::path::{Path, PathBuf};
 
 /// Pathfinders for gridmaps
@@ -11,10 +16,61 @@ pub struct Cli {
     ///
     /// print prints the map with problems
     ///
-    /// a-star solves using A*
+    /// a-star solves using A*, weighted by --weight
+    ///
+    /// greedy solves using greedy best-first search, which orders nodes by heuristic only
+    ///
+    /// beam solves using beam search that keeps --beam-width nodes in every layer
     ///
     /// fringe solves using Fringe Search
     ///
+    /// parallel-fringe solves using Fringe Search that splits the Now-list across --threads threads
+    ///
+    /// With --landmarks, a-star, greedy, beam and fringe use the ALT heuristic and also count expansions with octile distance
+    ///
+    /// ida solves using IDA*
+    ///
+    /// jps solves using Jump Point Search
+    ///
+    /// jps-plus solves using JPS+. Jump table is loaded from MAP FILE.jps or built and saved there
+    ///
+    /// bidirectional-a-star solves using A* from both ends at the same time
+    ///
+    /// bidirectional-fringe solves using Fringe search from both ends at the same time
+    ///
+    /// focal solves using focal search (A*ε) with the bound given by --weight
+    ///
+    /// ara solves using ARA*, starting with --weight and printing every improved solution
+    ///
+    /// dijkstra solves using Dijkstra's algorithm and shows distances from the start
+    ///
+    /// verify checks expected lengths of problems against shortest distances found by Dijkstra's algorithm
+    ///
+    /// theta solves using Theta*. Paths are waypoints connected by straight lines
+    ///
+    /// lazy-theta solves using Lazy Theta*, which checks lines of sight only when needed
+    ///
+    /// d-star-lite solves using D* Lite. Changes to the map from --edits are replayed after the first path is found
+    ///
+    /// hpa solves using HPA*. Map is split into clusters of --cluster-size and paths are refined by --refinement
+    ///
+    /// cpd solves by looking up first moves from a compressed path database. Database is loaded from MAP FILE.cpd or built and saved there
+    ///
+    /// k-shortest finds the --routes shortest loopless paths with Yen's algorithm
+    ///
+    /// alternatives finds --routes different paths by multiplying weights of used edges with --penalty
+    ///
+    /// nearest finds the nearest target of --points from every source.
+    /// Without sources in the file, starts of the problems are used
+    ///
+    /// matrix computes distances from every source of --points to every target.
+    /// Without sources in the file, distances between the targets are computed
+    ///
+    /// sipp solves using Safe Interval Path Planning, waiting for and avoiding the moving obstacles of --obstacles
+    ///
+    /// cbs plans paths for the first --agents problems together with Conflict-Based Search,
+    /// so that no two agents are in the same cell or swap cells at the same timestep
+    ///
     /// compare compares a-star and fringe
     #[arg(value_enum)]
     pub mode: Mode,
@@ -45,6 +101,153 @@ pub struct Cli {
     /// It is recommended to pipe this to less or some other pager or file because this produces a long print.
     #[arg(short, long, default_value_t = 0, action = clap::ArgAction::Count)]
     pub silent: u8,
+
+    /// Use a transposition table with IDA*
+    #[arg(short, long)]
+    pub transposition_table: bool,
+
+    /// Path to a file with changes to the map, used by D* Lite.
+    ///
+    /// Each line is `block x y`, `free x y`, `move x y` or `replan`.
+    /// Lines starting with # are skipped.
+    #[arg(short, long, value_name = "EDITS FILE", value_parser = edits_exist)]
+    pub edits: Option<PathBuf>,
+
+    /// Path to a file with trajectories of moving obstacles, used by SIPP.
+    ///
+    /// Each trajectory starts with a line `obstacle`, followed by waypoints `t x y`, one per line.
+    /// Obstacle stays at a waypoint until the next one and disappears after the last one.
+    /// Lines starting with # are skipped.
+    #[arg(short, long, value_name = "OBSTACLES FILE", value_parser = obstacles_exist)]
+    pub obstacles: Option<PathBuf>,
+
+    /// Path to a file with sources and targets, used by nearest and matrix.
+    ///
+    /// Each line is `source x y` or `target x y`.
+    /// Lines starting with # are skipped.
+    #[arg(long, value_name = "POINTS FILE", value_parser = points_exist)]
+    pub points: Option<PathBuf>,
+
+    /// Width and height of the clusters of HPA*
+    #[arg(short, long, default_value_t = 16, value_parser = cluster_size_valid)]
+    pub cluster_size: usize,
+
+    /// How HPA* refines abstract paths.
+    /// Full stores paths inside clusters while building, lazy searches them again when they are needed.
+    #[arg(short, long, value_enum, default_value_t = Refinement::Full)]
+    pub refinement: Refinement,
+
+    /// Open list of A*
+    #[arg(long, value_enum, default_value_t = Queue::Binary)]
+    pub open_list: Queue,
+
+    /// Order of nodes with equal estimates in A*, and of siblings in the Now-list of Fringe search.
+    /// Default leaves ties to the open list. Only the binary open list of A* breaks ties.
+    #[arg(long, value_enum)]
+    pub tie_break: Option<TieBreak>,
+
+    /// Seed of random tie-breaking
+    #[arg(long, default_value_t = 1)]
+    pub seed: u64,
+
+    /// Number of buckets that Fringe search splits Later into.
+    /// Default is the fewest buckets that hold the largest growth of estimates on the map.
+    /// Too few buckets would put nodes to wrong buckets, so they are rejected.
+    #[arg(long, value_name = "COUNT", value_parser = clap::value_parser!(u16).range(3..))]
+    pub buckets: Option<u16>,
+
+    /// Range of estimates in one bucket of Fringe search. Default is 1.
+    #[arg(long, value_name = "WIDTH", value_parser = bucket_width_valid)]
+    pub bucket_width: Option<f32>,
+
+    /// Number of landmarks for the ALT heuristic of A*, greedy, beam and Fringe search
+    #[arg(short, long, value_name = "COUNT", value_parser = clap::value_parser!(u16).range(1..))]
+    pub landmarks: Option<u16>,
+
+    /// How landmarks are placed
+    #[arg(long, value_enum, default_value_t = Strategy::Avoid)]
+    pub landmark_strategy: Strategy,
+
+    /// Load landmarks from MAP FILE.alt, or build and save them there
+    #[arg(long)]
+    pub cache_landmarks: bool,
+
+    /// Number of threads used by parallel-fringe. Default is the number of logical cores.
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub threads: Option<u16>,
+
+    /// Number of nodes beam search keeps in every layer. Narrow beams can prune every route to the goal.
+    #[arg(short, long, default_value_t = 64, value_parser = clap::value_parser!(u16).range(1..))]
+    pub beam_width: u16,
+
+    /// Number of paths found by k-shortest and alternatives
+    #[arg(short = 'k', long, default_value_t = 3, value_parser = clap::value_parser!(u16).range(1..))]
+    pub routes: u16,
+
+    /// Factor that alternatives multiplies weights of used edges with
+    #[arg(long, default_value_t = 1.5, value_parser = penalty_valid)]
+    pub penalty: f32,
+
+    /// Number of agents planned by cbs. Problems of the scenario are used as agents in their order.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    pub agents: u16,
+
+    /// Solve a whole scenario in a batch of JOBS threads, each of them solving one problem at a time.
+    /// Results are still printed in the order of the scenario.
+    /// Details of single solves are not printed in a batch. Compare, cbs, k-shortest, alternatives, nearest and matrix
+    /// are never solved in a batch, and ignore JOBS.
+    #[arg(short, long, value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: Option<u16>,
+
+    /// Stop every search after it has expanded COUNT nodes.
+    /// Stopped searches are reported as timeouts instead of solutions.
+    #[arg(long, value_name = "COUNT")]
+    pub max_expansions: Option<usize>,
+
+    /// Stop every search after MILLISECONDS have passed since it started.
+    /// Stopped searches are reported as timeouts instead of solutions.
+    #[arg(long, value_name = "MILLISECONDS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: Option<u64>,
+
+    /// Suboptimality bound. Heuristic of A* is multiplied by this and focal search uses it as its bound.
+    /// ARA* starts with this weight and decreases it.
+    /// Paths found are at most WEIGHT times longer than optimal.
+    #[arg(short, long, default_value_t = 1.0, value_parser = weight_valid)]
+    pub weight: f32,
+}
+
+impl Cli {
+    /// Check combinations of arguments that can't be checked while parsing them.
+    ///
+    /// # Errors
+    ///
+    /// Radix open list with a weight above 1, because it needs estimates that never decrease,
+    /// and tie-breaking with an open list other than binary, because only it breaks ties.
+    pub fn check(&self) -> Result<(), clap::Error> {
+        if self.open_list == Queue::Radix && self.weight > 1.0 {
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--open-list radix needs estimates that never decrease, which --weight {} breaks",
+                    self.weight
+                ),
+            ));
+        }
+        if self.tie_break.is_some() && self.open_list != Queue::Binary {
+            let open_list = self
+                .open_list
+                .to_possible_value()
+                .expect("Open lists are not skipped");
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--tie-break needs the binary open list, --open-list {} doesn't break ties",
+                    open_list.get_name()
+                ),
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Different modes for executing the program
@@ -53,7 +256,30 @@ pub enum Mode {
     Print,
     PrintMap,
     AStar,
+    Greedy,
+    Beam,
     Fringe,
+    ParallelFringe,
+    Ida,
+    Jps,
+    JpsPlus,
+    BidirectionalAStar,
+    BidirectionalFringe,
+    Focal,
+    Ara,
+    Dijkstra,
+    Verify,
+    Theta,
+    LazyTheta,
+    DStarLite,
+    Hpa,
+    Cpd,
+    KShortest,
+    Alternatives,
+    Nearest,
+    Matrix,
+    Sipp,
+    Cbs,
     Compare,
 }
 
@@ -74,3 +300,66 @@ fn problem_exists(s: &str) -> Result<PathBuf, String> {
         Err("Supplied problem file does not exist".to_string())
     }
 }
+
+/// Make sure that specified edits file exists
+fn edits_exist(s: &str) -> Result<PathBuf, String> {
+    if let Ok(true) = Path::new(s).try_exists() {
+        Ok(Path::new(s).to_path_buf())
+    } else {
+        Err("Supplied edits file does not exist".to_string())
+    }
+}
+
+/// Make sure that specified obstacles file exists
+fn obstacles_exist(s: &str) -> Result<PathBuf, String> {
+    if let Ok(true) = Path::new(s).try_exists() {
+        Ok(Path::new(s).to_path_buf())
+    } else {
+        Err("Supplied obstacles file does not exist".to_string())
+    }
+}
+
+/// Make sure that specified points file exists
+fn points_exist(s: &str) -> Result<PathBuf, String> {
+    if let Ok(true) = Path::new(s).try_exists() {
+        Ok(Path::new(s).to_path_buf())
+    } else {
+        Err("Supplied points file does not exist".to_string())
+    }
+}
+
+/// Make sure that clusters can have an entrance on every side
+fn cluster_size_valid(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(size) if size >= 2 => Ok(size),
+        Ok(_) => Err("Cluster size must be at least 2".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Make sure that buckets hold some estimates
+fn bucket_width_valid(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(width) if width > 0.0 && width.is_finite() => Ok(width),
+        Ok(_) => Err("Bucket width must be positive".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Make sure that penalty makes used edges longer
+fn penalty_valid(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(penalty) if penalty > 1.0 && penalty.is_finite() => Ok(penalty),
+        Ok(_) => Err("Penalty must be more than 1".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Make sure that weight does not make heuristic smaller
+fn weight_valid(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(weight) if weight >= 1.0 && weight.is_finite() => Ok(weight),
+        Ok(_) => Err("Weight must be at least 1".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
@@ -1,10 +1,34 @@
-use crate::algorithms::{AStar, Algorithm, FringeSearch, Result, Solver};
+use crate::algorithms::alternatives::{add_routes_to_printable, Route};
+use crate::algorithms::astar::open_list::{
+    BucketQueue, DaryHeap, Frontier, OpenList, PairingHeap, Queue, RadixHeap, TiedFrontier,
+};
+use crate::algorithms::cbs::Plan;
+use crate::algorithms::cpd::database::PathDatabase;
+use crate::algorithms::fringesearch::bucket::BucketLayout;
+use crate::algorithms::hpa::abstraction::Abstraction;
+use crate::algorithms::jpsplus::table::JumpTable;
+use crate::algorithms::Landmarks;
+use crate::algorithms::{
+    AStar, Abort, Algorithm, Ara, BeamSearch, BidirectionalAStar, BidirectionalFringe, Cbs, Cpd,
+    DStarLite, Dijkstra, DistanceMatrix, FocalSearch, FringeSearch, GreedySearch, Heuristic, Hpa,
+    Ida, Jps, JpsPlus, Limits, MultiGoal, ParallelFringe, Penalty, Result, Sipp, Solver, State,
+    ThetaStar, TieBreak, Workspace, Yen,
+};
 use crate::cli::{Cli, Mode};
+use crate::edits::Edits;
+use crate::obstacles::Obstacles;
+use crate::points::Points;
 use crate::printable::Printable;
 use crate::problem::{Problem, Problems};
-use crate::structures::{Graph, Map};
-use crate::Node;
+use crate::structures::{DistanceField, Graph, Map};
+use crate::{index_to_xy, Cost, Node, Path};
 
+use clap::ValueEnum;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::convert::identity;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::time::Instant;
 
@@ -15,6 +39,32 @@ pub struct Context {
     mode: Mode,
     printable: Printable,
     print_level: usize,
+    transposition_table: bool,
+    jump_table: Option<JumpTable>,
+    abstraction: Option<Abstraction>,
+    landmarks: Option<Arc<Landmarks>>,
+    database: Option<PathDatabase>,
+    pool: Option<ThreadPool>,
+    batch: Option<ThreadPool>,
+    buckets: Option<BucketLayout>,
+    open_list: Queue,
+    tie_break: Option<TieBreak>,
+    seed: u64,
+    agents: usize,
+    beam_width: usize,
+    routes: usize,
+    penalty: f32,
+    expansions: [AtomicUsize; 2],
+    max_expansions: Option<usize>,
+    timeout: Option<Duration>,
+    /// Stops every search in progress when set
+    cancel: Arc<AtomicBool>,
+    /// Buffers of finished searches, reused by the next ones. Each thread of a batch takes its own.
+    workspaces: Mutex<Vec<Workspace>>,
+    weight: f32,
+    edits: Option<Edits>,
+    obstacles: Option<Obstacles>,
+    points: Option<Points>,
 }
 
 impl Context {
@@ -44,7 +94,7 @@ impl Context {
         if cli.silent <= 2 {
             println!("Loading map {map_name}");
         }
-        let map = Map::new(cli.map_file);
+        let map = Map::new(cli.map_file.clone());
         let mut printable = Printable::new(&map);
         printable.add_header("Map", map_name);
         printable.add_header(
@@ -69,12 +119,259 @@ impl Context {
         printable.add_header("Graph size", graph.size());
         printable.add_header("Branching", graph.average_branching());
 
+        let jump_table = if matches!(cli.mode, Mode::JpsPlus) {
+            let now = Instant::now();
+            let (table, built) = JumpTable::load_or_build(&cli.map_file, &graph)
+                .expect("Error preparing jump table");
+            let duration = now.elapsed();
+
+            let action = if built { "built" } else { "loaded" };
+            if cli.silent <= 2 {
+                println!(
+                    "Jump table {action} in {duration:?}, {} bytes",
+                    table.size_in_bytes()
+                );
+            }
+            printable.add_header("Preprocessing", format!("{duration:?} ({action})"));
+            printable.add_header("Table size", format!("{} bytes", table.size_in_bytes()));
+            Some(table)
+        } else {
+            None
+        };
+
+        let abstraction = if matches!(cli.mode, Mode::Hpa) {
+            let now = Instant::now();
+            let abstraction = Abstraction::new(&graph, cli.cluster_size, cli.refinement);
+            let duration = now.elapsed();
+
+            let size = format!(
+                "{} nodes, {} edges, {} bytes",
+                abstraction.node_count(),
+                abstraction.edge_count(),
+                abstraction.size_in_bytes()
+            );
+            if cli.silent <= 2 {
+                println!(
+                    "Abstraction of {} clusters built in {duration:?}, {size}",
+                    abstraction.cluster_count()
+                );
+            }
+            printable.add_header("Preprocessing", format!("{duration:?}"));
+            printable.add_header("Abstraction", size);
+            Some(abstraction)
+        } else {
+            None
+        };
+
+        let landmarks = match (cli.landmarks, cli.mode) {
+            (
+                Some(count),
+                Mode::AStar | Mode::Greedy | Mode::Beam | Mode::Fringe | Mode::Compare,
+            ) => {
+                let (count, strategy) = (usize::from(count), cli.landmark_strategy);
+                let now = Instant::now();
+                let (landmarks, action) = if cli.cache_landmarks {
+                    let (landmarks, built) =
+                        Landmarks::load_or_build(&cli.map_file, &graph, count, strategy)
+                            .expect("Error preparing landmarks");
+                    (landmarks, if built { "built" } else { "loaded" })
+                } else {
+                    (Landmarks::new(&graph, count, strategy), "built")
+                };
+                let duration = now.elapsed();
+
+                let size = format!(
+                    "{} ({strategy:?}), {} bytes",
+                    landmarks.nodes().len(),
+                    landmarks.size_in_bytes()
+                );
+                if cli.silent <= 2 {
+                    println!("Landmarks {action} in {duration:?}, {size}");
+                }
+                printable.add_header("Preprocessing", format!("{duration:?} ({action})"));
+                printable.add_header("Landmarks", size);
+                Some(Arc::new(landmarks))
+            }
+            _ => None,
+        };
+
+        let database = if matches!(cli.mode, Mode::Cpd) {
+            let now = Instant::now();
+            let (database, built) = PathDatabase::load_or_build(&cli.map_file, &graph)
+                .expect("Error preparing path database");
+            let duration = now.elapsed();
+
+            let action = if built { "built" } else { "loaded" };
+            let size = format!(
+                "{} runs, {} bytes ({:.2} % of uncompressed)",
+                database.run_count(),
+                database.size_in_bytes(),
+                100.0 * database.size_in_bytes() as f32
+                    / database.uncompressed_size_in_bytes().max(1) as f32
+            );
+            if cli.silent <= 2 {
+                println!("Path database {action} in {duration:?}, {size}");
+            }
+            printable.add_header("Preprocessing", format!("{duration:?} ({action})"));
+            printable.add_header("Database", size);
+            Some(database)
+        } else {
+            None
+        };
+
+        let pool = if matches!(cli.mode, Mode::ParallelFringe) {
+            let mut builder = ThreadPoolBuilder::new();
+            if let Some(threads) = cli.threads {
+                builder = builder.num_threads(usize::from(threads));
+            }
+            let pool = builder.build().expect("Error creating thread pool");
+            if cli.silent <= 2 {
+                println!("Using {} threads", pool.current_num_threads());
+            }
+            printable.add_header("Threads", pool.current_num_threads());
+            Some(pool)
+        } else {
+            None
+        };
+
+        let batch = match cli.jobs {
+            Some(_)
+                if matches!(
+                    cli.mode,
+                    Mode::Compare
+                        | Mode::Cbs
+                        | Mode::KShortest
+                        | Mode::Alternatives
+                        | Mode::Nearest
+                        | Mode::Matrix
+                ) =>
+            {
+                if cli.silent <= 2 {
+                    let mode = cli.mode.to_possible_value().expect("Modes are not skipped");
+                    println!(
+                        "Ignoring --jobs, {} is not solved in a batch",
+                        mode.get_name()
+                    );
+                }
+                None
+            }
+            Some(jobs) if problems.len() > 1 => {
+                let batch = ThreadPoolBuilder::new()
+                    .num_threads(usize::from(jobs))
+                    .build()
+                    .expect("Error creating thread pool");
+                if cli.silent <= 2 {
+                    println!("Solving in a batch of {jobs} jobs");
+                }
+                printable.add_header("Jobs", jobs);
+                Some(batch)
+            }
+            _ => None,
+        };
+
+        let buckets = match (cli.buckets, cli.bucket_width) {
+            (None, None) => None,
+            _ if !matches!(
+                cli.mode,
+                Mode::Fringe | Mode::BidirectionalFringe | Mode::Compare
+            ) =>
+            {
+                None
+            }
+            (count, width) => {
+                let width = width.unwrap_or(1.0);
+                let layout = match count {
+                    Some(count) => BucketLayout::new(usize::from(count), width, &graph)
+                        .expect("Error in bucket layout"),
+                    None => BucketLayout::for_graph(&graph, width),
+                };
+                if cli.silent <= 2 {
+                    println!("Using {} buckets of width {width}", layout.count());
+                }
+                printable.add_header("Buckets", format!("{} ⨉ {width}", layout.count()));
+                Some(layout)
+            }
+        };
+
+        if let Some(tie_break) = cli.tie_break {
+            printable.add_header("Tie-breaking", format!("{tie_break:?}"));
+        }
+        if let Some(expansions) = cli.max_expansions {
+            printable.add_header("Max expansions", expansions);
+        }
+        if let Some(timeout) = cli.timeout {
+            printable.add_header("Timeout", format!("{timeout} ms"));
+        }
+
+        let edits = cli.edits.map(|file| {
+            let edits = Edits::from_file(&file).expect("Error loading edits");
+            edits
+                .check_bounds(graph.get_width(), graph.get_height())
+                .expect("Error loading edits");
+            edits
+        });
+
+        let obstacles = if matches!(cli.mode, Mode::Sipp) {
+            let obstacles = match &cli.obstacles {
+                Some(file) => Obstacles::from_file(file).expect("Error loading obstacles"),
+                None => Obstacles::from(vec![]),
+            };
+            obstacles
+                .check_bounds(graph.get_width(), graph.get_height())
+                .expect("Error loading obstacles");
+            if cli.silent <= 2 {
+                println!("Loaded {} moving obstacles", obstacles.len());
+            }
+            printable.add_header("Obstacles", obstacles.len());
+            Some(obstacles)
+        } else {
+            None
+        };
+
+        let points = if matches!(cli.mode, Mode::Nearest | Mode::Matrix) {
+            let file = cli
+                .points
+                .as_ref()
+                .expect("Points file is needed, give one with --points");
+            let points = Points::from_file(file).expect("Error loading points");
+            points
+                .check_bounds(graph.get_width(), graph.get_height())
+                .expect("Error loading points");
+            Some(points)
+        } else {
+            None
+        };
+
         Some(Context {
             graph,
             problems,
             mode: cli.mode,
             print_level: cli.silent as usize,
             printable,
+            transposition_table: cli.transposition_table,
+            jump_table,
+            abstraction,
+            landmarks,
+            database,
+            pool,
+            batch,
+            buckets,
+            open_list: cli.open_list,
+            tie_break: cli.tie_break,
+            seed: cli.seed,
+            agents: usize::from(cli.agents),
+            beam_width: usize::from(cli.beam_width),
+            routes: usize::from(cli.routes),
+            penalty: cli.penalty,
+            expansions: Default::default(),
+            max_expansions: cli.max_expansions,
+            timeout: cli.timeout.map(Duration::from_millis),
+            cancel: Arc::default(),
+            workspaces: Mutex::new(vec![]),
+            weight: cli.weight,
+            edits,
+            obstacles,
+            points,
         })
     }
 
@@ -85,31 +382,133 @@ impl Context {
         BareContext {
             graph: self.graph,
             bare_problems,
+            jump_table: self.jump_table,
+            beam_width: self.beam_width,
+            workspace: Mutex::default(),
+        }
+    }
+
+    /// Flag that stops every search in progress when it is set, for example from another thread.
+    /// Stopped searches are reported as timeouts.
+    #[must_use]
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancel.clone()
+    }
+
+    /// Limits of a search that starts now
+    fn limits(&self) -> Limits {
+        let mut limits = Limits::default().with_cancel(self.cancel.clone());
+        if let Some(expansions) = self.max_expansions {
+            limits = limits.with_expansions(expansions);
+        }
+        if let Some(timeout) = self.timeout {
+            limits = limits.with_timeout(timeout);
         }
+        limits
     }
 
     /// Create self from CLI and run commands as specified.
-    pub fn run(self) {
+    ///
+    /// # Errors
+    ///
+    /// Solving a whole scenario fails if a solution exceeds the bound set by `weight`.
+    pub fn run(self) -> anyhow::Result<()> {
         match self.mode {
             Mode::Print => {
                 self.print_mode();
             }
             Mode::PrintMap => {}
+            Mode::Verify => {
+                self.verify();
+            }
             _ => {
-                self.solve_mode();
+                self.solve_mode()?;
             }
         }
+        Ok(())
     }
 
-    fn solve_mode(mut self) {
+    fn solve_mode(mut self) -> anyhow::Result<()> {
         if self.print_level <= 2 {
             match self.mode {
                 Mode::AStar => {
                     println!("Solving using A*");
                 }
+                Mode::Greedy => {
+                    println!("Solving using greedy best-first search");
+                }
+                Mode::Beam => {
+                    println!("Solving using beam search of width {}", self.beam_width);
+                }
                 Mode::Fringe => {
                     println!("Solving using Fringe search");
                 }
+                Mode::ParallelFringe => {
+                    println!("Solving using parallel Fringe search");
+                }
+                Mode::Ida => {
+                    println!("Solving using IDA*");
+                }
+                Mode::Jps => {
+                    println!("Solving using Jump Point Search");
+                }
+                Mode::JpsPlus => {
+                    println!("Solving using JPS+");
+                }
+                Mode::BidirectionalAStar => {
+                    println!("Solving using bidirectional A*");
+                }
+                Mode::BidirectionalFringe => {
+                    println!("Solving using bidirectional Fringe search");
+                }
+                Mode::Focal => {
+                    println!("Solving using focal search with bound {}", self.weight);
+                }
+                Mode::Ara => {
+                    println!("Solving using ARA* starting from weight {}", self.weight);
+                }
+                Mode::Dijkstra => {
+                    println!("Solving using Dijkstra's algorithm");
+                }
+                Mode::Theta => {
+                    println!("Solving using Theta*");
+                }
+                Mode::LazyTheta => {
+                    println!("Solving using Lazy Theta*");
+                }
+                Mode::DStarLite => {
+                    println!("Solving using D* Lite");
+                }
+                Mode::Hpa => {
+                    println!("Solving using HPA*");
+                }
+                Mode::Cpd => {
+                    println!("Solving using a compressed path database");
+                }
+                Mode::KShortest => {
+                    println!(
+                        "Finding {} shortest paths using Yen's algorithm",
+                        self.routes
+                    );
+                }
+                Mode::Alternatives => {
+                    println!(
+                        "Finding {} alternative routes with penalty {}",
+                        self.routes, self.penalty
+                    );
+                }
+                Mode::Nearest => {
+                    println!("Finding nearest targets");
+                }
+                Mode::Matrix => {
+                    println!("Computing a distance matrix");
+                }
+                Mode::Sipp => {
+                    println!("Solving using Safe Interval Path Planning");
+                }
+                Mode::Cbs => {
+                    println!("Solving using Conflict-Based Search");
+                }
                 Mode::Compare => {
                     println!("Comparing A* and Fringe search");
                 }
@@ -117,17 +516,26 @@ impl Context {
             }
         }
 
-        if self.problems.is_empty() {
+        if self.mode == Mode::Nearest {
+            let _ = self.nearest_targets();
+        } else if self.mode == Mode::Matrix {
+            let _ = self.distance_matrix();
+        } else if self.problems.is_empty() {
             panic!("No problems to solve")
+        } else if self.mode == Mode::Cbs {
+            let _ = self.plan_agents();
+        } else if matches!(self.mode, Mode::KShortest | Mode::Alternatives) {
+            self.route_mode();
         } else if let Some(problem) = self.problems.single_problem() {
             if self.mode == Mode::Compare {
                 let _ = self.solve(problem);
             } else {
-                self.use_solver(problem);
+                self.use_solver(problem)?;
             }
         } else {
-            self.solve_full();
+            self.solve_full()?;
         }
+        Ok(())
     }
 
     fn print_mode(self) {
@@ -149,20 +557,113 @@ impl Context {
         }
     }
 
-    fn use_solver(self, problem: Problem) {
+    fn use_solver(self, problem: Problem) -> anyhow::Result<()> {
         let mut printable = self.printable.clone();
         printable.add_problem(&problem);
         printable.add_spacing();
 
         let algorithm = match self.mode {
             Mode::AStar => {
-                printable.add_header("Algorithm", "A*");
+                if self.weight == 1.0 {
+                    printable.add_header("Algorithm", "A*");
+                } else {
+                    printable.add_header("Algorithm", format!("Weighted A* (w = {})", self.weight));
+                }
+                if self.open_list != Queue::Binary {
+                    printable.add_header("Open list", format!("{:?}", self.open_list));
+                }
                 Algorithm::AStar
             }
+            Mode::Greedy => {
+                printable.add_header("Algorithm", "Greedy best-first search");
+                Algorithm::Greedy
+            }
+            Mode::Beam => {
+                printable.add_header(
+                    "Algorithm",
+                    format!("Beam search (width {})", self.beam_width),
+                );
+                Algorithm::Beam {
+                    width: self.beam_width,
+                }
+            }
             Mode::Fringe => {
                 printable.add_header("Algorithm", "Fringe search");
                 Algorithm::Fringe
             }
+            Mode::ParallelFringe => {
+                printable.add_header("Algorithm", "Parallel Fringe search");
+                Algorithm::ParallelFringe
+            }
+            Mode::Ida => {
+                printable.add_header("Algorithm", "IDA*");
+                Algorithm::Ida {
+                    table: self.transposition_table,
+                }
+            }
+            Mode::Jps => {
+                printable.add_header("Algorithm", "Jump Point Search");
+                Algorithm::Jps
+            }
+            Mode::JpsPlus => {
+                printable.add_header("Algorithm", "JPS+");
+                Algorithm::JpsPlus
+            }
+            Mode::BidirectionalAStar => {
+                printable.add_header("Algorithm", "Bidirectional A*");
+                Algorithm::BidirectionalAStar
+            }
+            Mode::BidirectionalFringe => {
+                printable.add_header("Algorithm", "Bidirectional Fringe search");
+                Algorithm::BidirectionalFringe
+            }
+            Mode::Focal => {
+                printable.add_header("Algorithm", format!("Focal search (w = {})", self.weight));
+                Algorithm::Focal
+            }
+            Mode::Ara => {
+                printable.add_header("Algorithm", format!("ARA* (w = {})", self.weight));
+                Algorithm::Ara
+            }
+            Mode::Dijkstra => {
+                printable.add_header("Algorithm", "Dijkstra");
+                Algorithm::Dijkstra
+            }
+            Mode::Theta => {
+                printable.add_header("Algorithm", "Theta*");
+                Algorithm::Theta { lazy: false }
+            }
+            Mode::LazyTheta => {
+                printable.add_header("Algorithm", "Lazy Theta*");
+                Algorithm::Theta { lazy: true }
+            }
+            Mode::DStarLite => {
+                printable.add_header("Algorithm", "D* Lite");
+                Algorithm::DStarLite
+            }
+            Mode::Hpa => {
+                let abstraction = self
+                    .abstraction
+                    .as_ref()
+                    .expect("HPA* needs an abstraction");
+                let size = abstraction.get_cluster_size();
+                printable.add_header(
+                    "Algorithm",
+                    format!(
+                        "HPA* ({size} ⨉ {size} clusters, {:?} refinement)",
+                        abstraction.refinement()
+                    ),
+                );
+                Algorithm::Hpa
+            }
+            Mode::Cpd => {
+                printable.add_header("Algorithm", "CPD");
+                Algorithm::Cpd
+            }
+            Mode::Sipp => {
+                printable.add_header("Algorithm", "SIPP");
+                Algorithm::Sipp
+            }
             _ => panic!("use_solver does not support this mode of operation"),
         };
 
@@ -184,103 +685,953 @@ impl Context {
             }
         };
 
-        let solver = Solver::new(algorithm, result, problem, &self.graph);
-        solver.run();
+        let mut solver =
+            Solver::new(algorithm, result, problem, &self.graph).with_weight(self.weight);
+        if let Some(table) = &self.jump_table {
+            solver = solver.with_jump_table(table);
+        }
+        if let Some(edits) = &self.edits {
+            solver = solver.with_edits(edits);
+        }
+        if let Some(abstraction) = &self.abstraction {
+            solver = solver.with_abstraction(abstraction);
+        }
+        if let Some(landmarks) = &self.landmarks {
+            solver = solver.with_landmarks(landmarks);
+        }
+        if let Some(database) = &self.database {
+            solver = solver.with_database(database);
+        }
+        if let Some(pool) = &self.pool {
+            solver = solver.with_pool(pool);
+        }
+        if let Some(obstacles) = &self.obstacles {
+            solver = solver.with_obstacles(obstacles);
+        }
+        if let Some(layout) = self.buckets {
+            solver = solver.with_buckets(layout);
+        }
+        solver = solver
+            .with_open_list(self.open_list)
+            .with_limits(self.limits());
+        if let Some(tie_break) = self.tie_break {
+            solver = solver.with_tie_break(tie_break, self.seed);
+        }
+        solver.run()
     }
 
     /// Read `n`th (INDEXING STARTS FROM 1!!!) problem from file to the struct.
-    pub fn solve_full(&mut self) -> f32 {
+    /// Every length is checked to be at most `weight` times the expected length.
+    /// Returns the average error of the lengths.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the number of solutions that exceed the bound.
+    pub fn solve_full(&mut self) -> anyhow::Result<f32> {
         let mut error = 0.0;
         let mut count = 0.0;
+        let mut exceeded = 0;
+        let mut failed = 0;
+        let mut timeouts = vec![];
+        let mut suboptimality: Vec<f32> = vec![];
 
         if self.print_level <= 2 {
             println!("Solving {} problems...", self.problems.len());
         }
 
-        for problem in self.problems.iter() {
-            let result = self
-                .solve(*problem)
-                .unwrap_or_else(|| panic!("Could not find solution for:\n{problem}"));
-            let expected = problem.length;
-            count += expected.map_or_else(|| 0.0, |_| 1.0);
+        let now = Instant::now();
+        let outcomes = self.outcomes();
+        let duration = now.elapsed();
+        // Baseline is solved after the timing, so it does not slow down the throughput
+        if self.compares_expansions() {
+            self.count_baseline_expansions();
+        }
+
+        for (problem, (result, abort)) in self.problems.iter().zip(outcomes) {
+            let Some(result) = result else {
+                if let Some(reason) = abort {
+                    timeouts.push(reason);
+                    continue;
+                }
+                // Beam search can prune every route to goal, others fail only when there is none
+                if self.mode != Mode::Beam && self.print_level <= 2 {
+                    println!("Problem {} has no path", problem.number);
+                }
+                failed += 1;
+                continue;
+            };
             if let Some(expected) = problem.length {
                 count += 1.0;
                 error += (result - expected).abs();
+                if expected > 0.0 {
+                    suboptimality.push(result / expected);
+                }
+
+                // Compare mode returns a difference instead of a length,
+                // HPA*, greedy and beam search have no bound and SIPP minimizes arrival time instead of length
+                if !matches!(
+                    self.mode,
+                    Mode::Compare | Mode::Hpa | Mode::Greedy | Mode::Beam | Mode::Sipp
+                ) && result > self.weight * expected + 0.001
+                {
+                    exceeded += 1;
+                    if self.print_level <= 2 {
+                        println!(
+                            "Problem {} exceeds the bound: {result} > {} ⨉ {expected}",
+                            problem.number, self.weight
+                        );
+                    }
+                }
             }
         }
         let average = error / count;
         if self.print_level <= 2 {
+            println!(
+                "Solved {} problems in {duration:?}, {:.1} problems per second",
+                self.problems.len(),
+                self.problems.len() as f64 / duration.as_secs_f64()
+            );
             println!("Average error: {average}");
+            if !timeouts.is_empty() {
+                let reasons: Vec<String> = [Abort::Expansions, Abort::Deadline, Abort::Cancelled]
+                    .iter()
+                    .map(|reason| (reason, timeouts.iter().filter(|r| *r == reason).count()))
+                    .filter(|(_, count)| *count > 0)
+                    .map(|(reason, count)| format!("{count} {reason}"))
+                    .collect();
+                println!(
+                    "Timeouts: {} of {} ({})",
+                    timeouts.len(),
+                    self.problems.len(),
+                    reasons.join(", ")
+                );
+            }
+            if matches!(
+                self.mode,
+                Mode::Hpa | Mode::Greedy | Mode::Beam | Mode::Sipp
+            ) {
+                let worst = suboptimality.iter().fold(1.0, |a: f32, b| a.max(*b));
+                let mean = suboptimality.iter().sum::<f32>() / suboptimality.len().max(1) as f32;
+                println!("Suboptimality: {mean} on average, {worst} at worst");
+            }
+            if self.mode == Mode::Beam {
+                println!(
+                    "Failures: {failed} of {} ({:.1} %) with beam width {}",
+                    self.problems.len(),
+                    100.0 * failed as f32 / self.problems.len() as f32,
+                    self.beam_width
+                );
+            } else if failed > 0 {
+                println!("Failures: {failed} of {}", self.problems.len());
+            }
+            if self.compares_expansions() {
+                let (first, second) = (
+                    self.expansions[0].load(Ordering::Relaxed),
+                    self.expansions[1].load(Ordering::Relaxed),
+                );
+                println!(
+                    "Expansions: {} ({:.1} %)",
+                    self.expansions_compared(first, second),
+                    100.0 * first as f32 / second.max(1) as f32
+                );
+            }
+            if matches!(
+                self.mode,
+                Mode::BidirectionalAStar | Mode::BidirectionalFringe
+            ) {
+                println!(
+                    "Expansions: {} forward, {} backward",
+                    self.expansions[0].load(Ordering::Relaxed),
+                    self.expansions[1].load(Ordering::Relaxed)
+                );
+            }
         }
-        average
+        // Scenario lengths can be checked with verify if they seem to be wrong
+        anyhow::ensure!(
+            exceeded == 0,
+            "{exceeded} solutions exceed the bound of {} ⨉ expected length",
+            self.weight
+        );
+        Ok(average)
     }
 
-    /// Solve currently loaded problem.
+    /// Plan the first problems together as agents with Conflict-Based Search.
+    /// At print level 0, positions of the agents are printed for every timestep.
     #[must_use]
-    pub fn solve(&self, problem: Problem) -> Option<f32> {
-        if self.print_level <= 1 {
-            println!("{problem}");
+    pub fn plan_agents(&self) -> Option<Plan> {
+        let agents: Vec<&Problem> = self.problems.iter().take(self.agents).collect();
+        if self.print_level <= 2 {
+            println!("Planning {} agents...", agents.len());
         }
 
-        match self.mode {
-            Mode::AStar => {
-                let (solution, duration) = self.timed_astar(&problem);
-                self.print_solution(solution, problem, duration)
+        let mut cbs = Cbs::new(
+            agents.iter().map(|p| (p.start, p.goal)).collect(),
+            &self.graph,
+        )
+        .with_limits(self.limits());
+        let now = Instant::now();
+        let plan = cbs.solve_limited();
+        let duration = now.elapsed();
+
+        let plan = match plan {
+            State::Finished(plan) => plan,
+            State::Aborted(reason) => {
+                println!("Search stopped: {reason}");
+                return None;
             }
-            Mode::Fringe => {
-                let (solution, duration) = self.timed_fringe(&problem);
-                self.print_solution(solution, problem, duration)
+            _ => {
+                println!("No plan without conflicts found");
+                return None;
             }
-            Mode::Compare => {
-                println!("Solving using A*");
-                let (a_solution, a_duration) = self.timed_astar(&problem);
-                self.print_timing(a_duration);
+        };
+
+        if self.print_level == 0 {
+            for frame in plan.render(&self.printable) {
+                println!("{frame}\n");
+            }
+        }
+        if self.print_level <= 1 {
+            for (agent, problem) in agents.iter().enumerate() {
+                println!(
+                    "Agent {agent}: {}\n\tcost {}, {} timesteps",
+                    problem.coordinates(),
+                    plan.costs[agent],
+                    plan.paths[agent].len() - 1
+                );
+            }
+        }
+        if self.print_level <= 2 {
+            println!("Planned in {duration:?}");
+            println!("Sum of costs: {}", plan.sum_of_costs());
+            println!("Makespan: {}", plan.makespan());
+            println!(
+                "Expansions: {} constraint tree nodes, {} by space-time A*",
+                cbs.expansions, cbs.low_level_expansions
+            );
+        }
+        Some(plan)
+    }
 
-                println!("Solving using Fringe search");
-                let (f_solution, f_duration) = self.timed_fringe(&problem);
-                self.print_timing(f_duration);
+    /// Find the nearest target of the points from every source, or from the start of every problem
+    /// if there are no sources. Results are in the order of the sources.
+    #[must_use]
+    pub fn nearest_targets(&self) -> Vec<Option<(Path, Cost)>> {
+        let points = self.points.as_ref().expect("Nearest targets need points");
+        let width = self.graph.get_width();
+        let targets = points.targets(width);
+        let mut sources = points.sources(width);
+        if sources.is_empty() {
+            sources = self.problems.iter().map(|p| p.start).collect();
+        }
+        if self.print_level <= 2 {
+            println!(
+                "Searching {} targets from {} sources...",
+                targets.len(),
+                sources.len()
+            );
+        }
 
-                match (a_duration, f_duration) {
-                    (Some(a), Some(f)) if a < f => {
-                        println!("A* was {:?} faster than Fringe search", f - a);
+        let mut expansions = 0;
+        let mut results = vec![];
+        let now = Instant::now();
+        for source in &sources {
+            let mut search =
+                MultiGoal::new(*source, &targets, &self.graph).with_limits(self.limits());
+            let mut result = None;
+            loop {
+                match search.progress() {
+                    State::Finished(solution) => {
+                        result = Some(solution);
+                        break;
                     }
-                    (Some(a), Some(f)) if f < a => {
-                        println!("Fringe search was {:?} faster than A*", a - f);
+                    State::NotFound => break,
+                    State::Aborted(reason) => {
+                        println!("Search stopped: {reason}");
+                        break;
                     }
-                    _ => {
-                        println!("Error in timing");
+                    _ => {}
+                }
+            }
+            expansions += search.expansions;
+
+            let (x, y) = index_to_xy(*source, width);
+            match &result {
+                Some((path, cost)) if self.print_level <= 1 => {
+                    let (tx, ty) = index_to_xy(path[path.len() - 1], width);
+                    println!(
+                        "Source ({x}, {y}): nearest target ({tx}, {ty}) at {cost}, {} expansions",
+                        search.expansions
+                    );
+                    if self.print_level == 0 {
+                        let mut printable = self.printable.clone();
+                        printable.add_path(path.clone());
+                        for target in &targets {
+                            printable.add_goal(*target);
+                        }
+                        printable.add_start(*source);
+                        println!("{printable}\n");
                     }
                 }
-                Some((a_solution.unwrap().1 - f_solution.unwrap().1).abs())
+                None if self.print_level <= 1 => println!("Source ({x}, {y}): no target reachable"),
+                _ => {}
+            }
+            results.push(result);
+        }
+        let duration = now.elapsed();
+
+        if self.print_level <= 2 {
+            println!(
+                "Found nearest targets of {} sources in {duration:?}, {expansions} expansions",
+                sources.len()
+            );
+        }
+        results
+    }
+
+    /// Compute distances from every source of the points to every target,
+    /// or between every pair of targets if there are no sources
+    #[must_use]
+    pub fn distance_matrix(&self) -> DistanceMatrix {
+        let points = self.points.as_ref().expect("Distance matrix needs points");
+        let width = self.graph.get_width();
+        let targets = points.targets(width);
+        let mut sources = points.sources(width);
+        if sources.is_empty() {
+            sources.clone_from(&targets);
+        }
+
+        let now = Instant::now();
+        let matrix = DistanceMatrix::new(sources, targets, &self.graph);
+        let duration = now.elapsed();
+
+        if self.print_level == 0 {
+            let mut printable = self.printable.clone();
+            for target in matrix.targets() {
+                printable.add_goal(*target);
+            }
+            for source in matrix.sources() {
+                printable.add_start(*source);
+            }
+            println!("{printable}\n");
+        }
+        if self.print_level <= 1 {
+            let coordinates = |nodes: &[Node]| {
+                nodes
+                    .iter()
+                    .map(|n| format!("{:?}", index_to_xy(*n, width)))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            println!("Sources: {}", coordinates(matrix.sources()));
+            println!("Targets: {}", coordinates(matrix.targets()));
+            println!("{matrix}");
+        }
+        if self.print_level <= 2 {
+            println!(
+                "Computed {} ⨉ {} distances in {duration:?}",
+                matrix.sources().len(),
+                matrix.targets().len()
+            );
+        }
+        matrix
+    }
+
+    /// Find routes for every problem and compare the alternatives with the shortest route
+    fn route_mode(&self) {
+        let mut count = 0;
+        let mut stretch = 0.0;
+        let mut overlap = 0.0;
+
+        let now = Instant::now();
+        for problem in self.problems.iter() {
+            if self.print_level <= 1 {
+                println!("{problem}");
+            }
+            let routes = self.find_routes(problem);
+            if routes.is_empty() {
+                println!("No path found");
+                continue;
+            }
+
+            if self.print_level == 0 {
+                let mut printable = self.printable.clone();
+                printable.add_start(problem.start);
+                printable.add_goal(problem.goal);
+                println!("{}\n", add_routes_to_printable(&routes, printable));
+            } else if self.print_level == 1 {
+                for (i, route) in routes.iter().enumerate() {
+                    println!(
+                        "Route {i}:\n\tlength {}, {:.1} % shared",
+                        route.cost,
+                        100.0 * route.overlap
+                    );
+                }
+            }
+            for route in &routes[1..] {
+                count += 1;
+                stretch += route.cost / routes[0].cost.max(Cost::EPSILON);
+                overlap += route.overlap;
+            }
+        }
+        let duration = now.elapsed();
+
+        if self.print_level <= 2 {
+            println!(
+                "Found routes for {} problems in {duration:?}",
+                self.problems.len()
+            );
+            if count > 0 {
+                println!(
+                    "Alternatives: {count}, {:.3} ⨉ shortest and {:.1} % shared on average",
+                    stretch / count as Cost,
+                    100.0 * overlap / count as Cost
+                );
+            }
+        }
+    }
+
+    /// Find routes of a problem by the mode: k shortest paths or alternatives by penalties
+    #[must_use]
+    pub fn find_routes(&self, problem: &Problem) -> Vec<Route> {
+        if self.mode == Mode::Alternatives {
+            Penalty::new(problem.start, problem.goal, &self.graph, self.penalty).routes(self.routes)
+        } else {
+            Yen::new(problem.start, problem.goal, &self.graph).routes(self.routes)
+        }
+    }
+
+    /// Solve every problem and return the lengths in the order of the scenario.
+    /// With a batch, problems are spread across its threads and every thread uses solvers of its own.
+    /// Results are printed only after every problem has been solved, so they stay in order.
+    /// Searches stopped by the limits have no length.
+    #[must_use]
+    pub fn solve_all(&self) -> Vec<Option<f32>> {
+        self.outcomes()
+            .into_iter()
+            .map(|(length, _)| length)
+            .collect()
+    }
+
+    /// Solve every problem like [`Context::solve_all`], and tell why the searches without a length were stopped
+    fn outcomes(&self) -> Vec<(Option<f32>, Option<Abort>)> {
+        let Some(batch) = &self.batch else {
+            return self.problems.iter().map(|p| self.outcome(*p)).collect();
+        };
+
+        let problems: Vec<&Problem> = self.problems.iter().collect();
+        let solutions: Vec<_> = batch.install(|| {
+            problems
+                .par_iter()
+                .map(|problem| self.timed_solution(problem))
+                .collect()
+        });
+
+        problems
+            .into_iter()
+            .zip(solutions)
+            .map(|(problem, (solution, duration))| {
+                if self.print_level <= 1 {
+                    println!("{problem}");
+                }
+                self.print_solution(solution, *problem, duration)
+            })
+            .collect()
+    }
+
+    /// Solve currently loaded problem.
+    #[must_use]
+    pub fn solve(&self, problem: Problem) -> Option<f32> {
+        self.outcome(problem).0
+    }
+
+    /// Solve a problem and tell why the search was stopped, if it was
+    fn outcome(&self, problem: Problem) -> (Option<f32>, Option<Abort>) {
+        if self.print_level <= 1 {
+            println!("{problem}");
+        }
+
+        if self.mode != Mode::Compare {
+            let (solution, duration) = self.timed_solution(&problem);
+            return self.print_solution(solution, problem, duration);
+        }
+
+        println!("Solving using A*");
+        let (a_state, a_duration) = self.timed_astar(&problem);
+        self.print_timing(a_duration);
+
+        println!("Solving using Fringe search");
+        let (f_state, f_duration) = self.timed_fringe(&problem);
+        self.print_timing(f_duration);
+
+        match (a_duration, f_duration) {
+            (Some(a), Some(f)) if a < f => {
+                println!("A* was {:?} faster than Fringe search", f - a);
+            }
+            (Some(a), Some(f)) if f < a => {
+                println!("Fringe search was {:?} faster than A*", a - f);
             }
+            _ => {
+                println!("Error in timing");
+            }
+        }
+        match (a_state, f_state) {
+            (State::Aborted(reason), _) | (_, State::Aborted(reason)) => {
+                println!("Search stopped: {reason}");
+                (None, Some(reason))
+            }
+            (a_state, f_state) => match (a_state.solution(), f_state.solution()) {
+                (Some((_, a_length)), Some((_, f_length))) => {
+                    (Some((a_length - f_length).abs()), None)
+                }
+                _ => {
+                    println!("No path found");
+                    (None, None)
+                }
+            },
+        }
+    }
+
+    /// Solve a problem with the algorithm of the mode without printing the solution
+    fn timed_solution(&self, problem: &Problem) -> (State, Option<Duration>) {
+        match self.mode {
+            Mode::AStar => self.timed_astar(problem),
+            Mode::Greedy => self.timed_greedy(problem),
+            Mode::Beam => self.timed_beam(problem),
+            Mode::Fringe => self.timed_fringe(problem),
+            Mode::ParallelFringe => self.timed_parallel_fringe(problem),
+            Mode::Ida => self.timed_ida(problem),
+            Mode::Jps => self.timed_jps(problem),
+            Mode::JpsPlus => self.timed_jps_plus(problem),
+            Mode::BidirectionalAStar => self.timed_bidirectional(problem),
+            Mode::BidirectionalFringe => self.timed_bidirectional_fringe(problem),
+            Mode::Focal => self.timed_focal(problem),
+            Mode::Ara => self.timed_ara(problem),
+            Mode::Dijkstra => self.timed_dijkstra(problem),
+            Mode::Theta | Mode::LazyTheta => self.timed_theta(problem),
+            Mode::DStarLite => self.timed_dstar_lite(problem),
+            Mode::Hpa => self.timed_hpa(problem),
+            Mode::Cpd => self.timed_cpd(problem),
+            Mode::Sipp => self.timed_sipp(problem),
             _ => {
                 panic!("We can't solve in print mode");
             }
         }
     }
 
-    fn timed_astar(&self, problem: &Problem) -> (Option<(Vec<Node>, f32)>, Option<Duration>) {
-        let now = Instant::now();
+    /// Details of a single solve are printed only when problems are solved one at a time,
+    /// because details of a batch would be mixed between problems
+    fn print_details(&self) -> bool {
+        self.print_level <= 1 && self.batch.is_none()
+    }
 
-        let astar = AStar::new(problem.start, problem.goal, &self.graph);
-        let solution = astar.solve();
+    fn timed_astar(&self, problem: &Problem) -> (State, Option<Duration>) {
+        // Only the binary open list breaks ties, which is checked by Cli::check
+        match (self.open_list, self.tie_break) {
+            (Queue::Binary, Some(tie_break)) => self
+                .timed_astar_with(problem, |astar: AStar<TiedFrontier>| {
+                    astar.with_tie_break(tie_break, self.seed)
+                }),
+            (Queue::Binary, None) => self.timed_astar_with::<Frontier>(problem, identity),
+            (Queue::Dary, _) => self.timed_astar_with::<DaryHeap<4>>(problem, identity),
+            (Queue::Pairing, _) => self.timed_astar_with::<PairingHeap>(problem, identity),
+            (Queue::Radix, _) => self.timed_astar_with::<RadixHeap>(problem, identity),
+            (Queue::Buckets, _) => self.timed_astar_with::<BucketQueue>(problem, identity),
+        }
+    }
 
-        let done = Instant::now();
-        let duration = done.checked_duration_since(now);
+    /// Solve with A* and the open list `O`. `tied` adds tie-breaking to open lists that have it.
+    fn timed_astar_with<'a, O: OpenList>(
+        &'a self,
+        problem: &Problem,
+        tied: impl Fn(AStar<'a, O>) -> AStar<'a, O>,
+    ) -> (State, Option<Duration>) {
+        let mut expansions = 0;
+        let result = self.in_workspace(|workspace| {
+            Self::timed(|| {
+                let astar = AStar::<O>::with_open_list_in(
+                    problem.start,
+                    problem.goal,
+                    &self.graph,
+                    workspace,
+                )
+                .with_weight(self.weight)
+                .with_limits(self.limits());
+                let mut astar = match &self.landmarks {
+                    Some(landmarks) => tied(astar).with_landmarks(landmarks.clone()),
+                    None => tied(astar),
+                };
+                let state = astar.run();
+                expansions = astar.expansions;
+                astar.into_workspace(workspace);
+                state
+            })
+        });
+        self.record_expansions(expansions);
+        result
+    }
+
+    fn timed_greedy(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut expansions = 0;
+        let result = Self::timed(|| {
+            let mut greedy = GreedySearch::new(problem.start, problem.goal, &self.graph)
+                .with_limits(self.limits());
+            if let Some(landmarks) = &self.landmarks {
+                greedy = greedy.with_landmarks(landmarks.clone());
+            }
+            let state = greedy.run();
+            expansions = greedy.expansions;
+            state
+        });
+        self.record_expansions(expansions);
+        result
+    }
+
+    fn timed_beam(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut expansions = 0;
+        let result = Self::timed(|| {
+            let mut beam =
+                BeamSearch::new(problem.start, problem.goal, &self.graph, self.beam_width)
+                    .with_limits(self.limits());
+            if let Some(landmarks) = &self.landmarks {
+                beam = beam.with_landmarks(landmarks.clone());
+            }
+            let state = beam.run();
+            expansions = beam.expansions;
+            state
+        });
+        self.record_expansions(expansions);
+        result
+    }
+
+    fn timed_fringe(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut expansions = 0;
+        let result = self.in_workspace(|workspace| {
+            Self::timed(|| {
+                let (start, goal) = (problem.start, problem.goal);
+                let mut heuristic = Heuristic::new(goal, self.graph.get_width());
+                if let Some(landmarks) = &self.landmarks {
+                    heuristic = heuristic.with_landmarks(landmarks.clone());
+                }
+                // Buckets are laid out by the workspace
+                let mut fringe =
+                    FringeSearch::with_heuristic_in(start, goal, &self.graph, heuristic, workspace)
+                        .with_limits(self.limits());
+                if let Some(tie_break) = self.tie_break {
+                    fringe = fringe.with_tie_break(tie_break, self.seed);
+                }
+                let state = fringe.run();
+                expansions = fringe.fringe().expansions;
+                fringe.into_workspace(workspace);
+                state
+            })
+        });
+        self.record_expansions(expansions);
+        result
+    }
+
+    /// Run `solve` with a workspace of an earlier search, or a new one if every workspace is in use
+    fn in_workspace<T>(&self, solve: impl FnOnce(&mut Workspace) -> T) -> T {
+        let workspace = self.workspaces.lock().unwrap().pop();
+        let mut workspace = workspace.unwrap_or_else(|| match self.buckets {
+            Some(layout) => Workspace::default().with_buckets(layout),
+            None => Workspace::default(),
+        });
+        let result = solve(&mut workspace);
+        self.workspaces.lock().unwrap().push(workspace);
+        result
+    }
+
+    fn timed_parallel_fringe(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let pool = self
+            .pool
+            .as_ref()
+            .expect("Parallel Fringe search needs a thread pool");
+        pool.install(|| {
+            Self::timed(|| {
+                ParallelFringe::new(problem.start, problem.goal, &self.graph)
+                    .with_limits(self.limits())
+                    .solve_limited()
+            })
+        })
+    }
+
+    /// Check if expansions of the mode are compared with a baseline:
+    /// ALT heuristic with octile distance, or tie-breaking with ties left to the open list
+    fn compares_expansions(&self) -> bool {
+        match self.mode {
+            Mode::AStar | Mode::Fringe => self.landmarks.is_some() || self.tie_break.is_some(),
+            Mode::Greedy | Mode::Beam => self.landmarks.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Add expansions of a timed search to the total, if they are compared with a baseline
+    fn record_expansions(&self, expansions: usize) {
+        if self.compares_expansions() {
+            self.expansions[0].fetch_add(expansions, Ordering::Relaxed);
+            if self.print_details() {
+                println!("Expansions: {expansions}");
+            }
+        }
+    }
+
+    /// Solve every problem again without timing to count expansions of the baseline.
+    /// With landmarks the baseline uses octile distance, otherwise ties are left to the open list.
+    fn count_baseline_expansions(&self) {
+        let problems: Vec<&Problem> = self.problems.iter().collect();
+        let count = |problem: &&Problem| self.baseline_expansions(problem);
+        let total = match &self.batch {
+            Some(batch) => batch.install(|| problems.par_iter().map(count).sum()),
+            None => problems.iter().map(count).sum(),
+        };
+        self.expansions[1].store(total, Ordering::Relaxed);
+    }
 
-        (solution, duration)
+    /// Expansions of the baseline search of a problem, with the same open list, buckets and limits
+    fn baseline_expansions(&self, problem: &Problem) -> usize {
+        let (start, goal) = (problem.start, problem.goal);
+        // Tie-breaking stays with landmarks, so that only the heuristic differs
+        let tie_break = self.landmarks.as_ref().and(self.tie_break);
+        match self.mode {
+            Mode::Fringe => {
+                let mut fringe =
+                    FringeSearch::new(start, goal, &self.graph).with_limits(self.limits());
+                if let Some(layout) = self.buckets {
+                    fringe = fringe.with_buckets(layout);
+                }
+                if let Some(tie_break) = tie_break {
+                    fringe = fringe.with_tie_break(tie_break, self.seed);
+                }
+                fringe.run();
+                fringe.fringe().expansions
+            }
+            Mode::Greedy => {
+                let mut greedy =
+                    GreedySearch::new(start, goal, &self.graph).with_limits(self.limits());
+                greedy.run();
+                greedy.expansions
+            }
+            Mode::Beam => {
+                let mut beam = BeamSearch::new(start, goal, &self.graph, self.beam_width)
+                    .with_limits(self.limits());
+                beam.run();
+                beam.expansions
+            }
+            _ => match (self.open_list, tie_break) {
+                (Queue::Binary, Some(tie_break)) => self
+                    .astar_expansions(problem, |astar: AStar<TiedFrontier>| {
+                        astar.with_tie_break(tie_break, self.seed)
+                    }),
+                (Queue::Binary, None) => self.astar_expansions::<Frontier>(problem, identity),
+                (Queue::Dary, _) => self.astar_expansions::<DaryHeap<4>>(problem, identity),
+                (Queue::Pairing, _) => self.astar_expansions::<PairingHeap>(problem, identity),
+                (Queue::Radix, _) => self.astar_expansions::<RadixHeap>(problem, identity),
+                (Queue::Buckets, _) => self.astar_expansions::<BucketQueue>(problem, identity),
+            },
+        }
+    }
+
+    /// Run A* with the open list `O` and octile distance to the end and count its expansions
+    fn astar_expansions<'a, O: OpenList>(
+        &'a self,
+        problem: &Problem,
+        tied: impl Fn(AStar<'a, O>) -> AStar<'a, O>,
+    ) -> usize {
+        let astar = AStar::<O>::with_open_list(problem.start, problem.goal, &self.graph)
+            .with_weight(self.weight)
+            .with_limits(self.limits());
+        let mut astar = tied(astar);
+        astar.run();
+        astar.expansions
+    }
+
+    /// Expansions of the timed searches and of the baseline, and what they were counted with
+    fn expansions_compared(&self, first: usize, second: usize) -> String {
+        match (&self.landmarks, self.tie_break) {
+            (None, Some(tie_break)) => format!(
+                "{first} with {tie_break:?} tie-breaking, {second} with ties left to the open list"
+            ),
+            _ => format!("{first} with landmarks, {second} with octile distance"),
+        }
+    }
+
+    fn timed_ida(&self, problem: &Problem) -> (State, Option<Duration>) {
+        Self::timed(|| {
+            let ida = Ida::new(problem.start, problem.goal, &self.graph).with_limits(self.limits());
+            if self.transposition_table {
+                ida.with_table().solve_limited()
+            } else {
+                ida.solve_limited()
+            }
+        })
     }
 
-    fn timed_fringe(&self, problem: &Problem) -> (Option<(Vec<Node>, f32)>, Option<Duration>) {
+    fn timed_jps(&self, problem: &Problem) -> (State, Option<Duration>) {
+        Self::timed(|| {
+            Jps::new(problem.start, problem.goal, &self.graph)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    fn timed_jps_plus(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let table = self.jump_table.as_ref().expect("JPS+ needs a jump table");
+        Self::timed(|| {
+            JpsPlus::new(problem.start, problem.goal, table)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    /// Solve with bidirectional A* and add expansions of both directions to the totals
+    fn timed_bidirectional(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut search = BidirectionalAStar::new(problem.start, problem.goal, &self.graph)
+            .with_limits(self.limits());
+        let result = Self::timed(|| search.run());
+
+        let (forward, backward) = search.expansions();
+        self.expansions[0].fetch_add(forward, Ordering::Relaxed);
+        self.expansions[1].fetch_add(backward, Ordering::Relaxed);
+        if self.print_details() {
+            println!("Expansions:\n\t{forward} forward, {backward} backward");
+        }
+        result
+    }
+
+    /// Solve with bidirectional Fringe search and add expansions of both directions to the totals
+    fn timed_bidirectional_fringe(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut search = BidirectionalFringe::new(problem.start, problem.goal, &self.graph)
+            .with_limits(self.limits());
+        if let Some(layout) = self.buckets {
+            search = search.with_buckets(layout);
+        }
+        let result = Self::timed(|| search.run());
+
+        let (forward, backward) = search.expansions();
+        self.expansions[0].fetch_add(forward, Ordering::Relaxed);
+        self.expansions[1].fetch_add(backward, Ordering::Relaxed);
+        if self.print_details() {
+            println!("Expansions:\n\t{forward} forward, {backward} backward");
+        }
+        result
+    }
+
+    fn timed_focal(&self, problem: &Problem) -> (State, Option<Duration>) {
+        Self::timed(|| {
+            FocalSearch::new(problem.start, problem.goal, &self.graph, self.weight)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    fn timed_dijkstra(&self, problem: &Problem) -> (State, Option<Duration>) {
+        Self::timed(|| {
+            Dijkstra::new(problem.start, problem.goal, &self.graph)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    fn timed_theta(&self, problem: &Problem) -> (State, Option<Duration>) {
+        Self::timed(|| {
+            let theta =
+                ThetaStar::new(problem.start, problem.goal, &self.graph).with_limits(self.limits());
+            if self.mode == Mode::LazyTheta {
+                theta.lazy().solve_limited()
+            } else {
+                theta.solve_limited()
+            }
+        })
+    }
+
+    fn timed_hpa(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let abstraction = self
+            .abstraction
+            .as_ref()
+            .expect("HPA* needs an abstraction");
+        Self::timed(|| {
+            Hpa::new(problem.start, problem.goal, &self.graph, abstraction)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    fn timed_cpd(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let database = self.database.as_ref().expect("CPD needs a path database");
+        Self::timed(|| {
+            Cpd::new(problem.start, problem.goal, database)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    fn timed_sipp(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let obstacles = self.obstacles.as_ref().expect("SIPP needs obstacles");
+        Self::timed(|| {
+            Sipp::new(problem.start, problem.goal, &self.graph, obstacles)
+                .with_limits(self.limits())
+                .solve_limited()
+        })
+    }
+
+    /// Solve with D* Lite and replay edits afterwards.
+    /// Returned solution is the first one, which is for the unchanged map.
+    fn timed_dstar_lite(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut dstar =
+            DStarLite::new(problem.start, problem.goal, &self.graph).with_limits(self.limits());
+        let result = Self::timed(|| dstar.replan_limited());
+
+        if let Some(edits) = &self.edits {
+            dstar.replay(edits, |_, replan| {
+                if self.print_details() {
+                    let length = replan
+                        .solution
+                        .map_or_else(|| "no path".to_string(), |(_, l)| format!("length {l}"));
+                    println!(
+                        "Replan {}:\n\t{length}, {} cells changed, {} expansions in {:?}",
+                        replan.number, replan.changed_cells, replan.expansions, replan.duration
+                    );
+                }
+            });
+        }
+        result
+    }
+
+    /// Solve with ARA* until the solution is optimal. Every improvement is printed with its bound.
+    fn timed_ara(&self, problem: &Problem) -> (State, Option<Duration>) {
+        let mut ara = Ara::new(problem.start, problem.goal, &self.graph, self.weight)
+            .with_limits(self.limits());
         let now = Instant::now();
+        Self::timed(|| loop {
+            let (improved, bound) = match ara.progress() {
+                State::Improved(improved, bound) => (improved, bound),
+                State::Finished(solution) => (solution, 1.0),
+                State::Processing(_) | State::Internal => continue,
+                state => return state,
+            };
+            if self.print_details() {
+                println!(
+                    "Improved:\n\t{} ≤ {bound} ⨉ optimal in {:?}",
+                    improved.1,
+                    now.elapsed()
+                );
+            }
+            if bound <= 1.0 {
+                return State::Finished(improved);
+            }
+        })
+    }
 
-        let fringe = FringeSearch::new(problem.start, problem.goal, &self.graph);
-        let solution = fringe.solve();
+    /// Time the solving function
+    fn timed(solve: impl FnOnce() -> State) -> (State, Option<Duration>) {
+        let now = Instant::now();
+
+        let state = solve();
 
         let done = Instant::now();
         let duration = done.checked_duration_since(now);
 
-        (solution, duration)
+        (state, duration)
     }
 
     fn print_timing(&self, duration: Option<Duration>) {
@@ -293,22 +1644,30 @@ impl Context {
         }
     }
 
-    /// Print solution, `full` specifies if map is printed
+    /// Print solution, `full` specifies if map is printed.
+    /// Returns the length, or why the search was stopped.
     fn print_solution(
         &self,
-        solution: Option<(Vec<Node>, f32)>,
+        state: State,
         problem: Problem,
         duration: Option<Duration>,
-    ) -> Option<f32> {
+    ) -> (Option<f32>, Option<Abort>) {
         let _path;
         let path_length;
 
-        if let Some((p, l)) = solution {
-            _path = p;
-            path_length = l;
-        } else {
-            println!("No path found");
-            return None;
+        match state {
+            State::Finished((p, l)) => {
+                _path = p;
+                path_length = l;
+            }
+            State::Aborted(reason) => {
+                println!("Search stopped: {reason}");
+                return (None, Some(reason));
+            }
+            _ => {
+                println!("No path found");
+                return (None, None);
+            }
         }
 
         if self.print_level == 0 {
@@ -333,7 +1692,46 @@ impl Context {
                 println!("Difference:\n\t{}\n", path_length - l);
             }
         }
-        Some(path_length)
+        (Some(path_length), None)
+    }
+
+    /// Check expected lengths of problems against shortest distances.
+    /// A [`DistanceField`] is computed once for every distinct start.
+    /// Returns the number of problems whose expected length differs from the shortest distance.
+    pub fn verify(&self) -> usize {
+        let mut problems: Vec<&Problem> = self.problems.iter().collect();
+        problems.sort_by_key(|p| p.start);
+
+        if self.print_level <= 2 {
+            println!("Verifying {} problems...", problems.len());
+        }
+
+        let mut field: Option<DistanceField> = None;
+        let mut mismatches = 0;
+        for problem in problems {
+            if field.as_ref().map(DistanceField::source) != Some(problem.start) {
+                field = Some(Dijkstra::distance_field(problem.start, &self.graph));
+            }
+            let Some(expected) = problem.length else {
+                continue;
+            };
+            let shortest = field.as_ref().unwrap().get(problem.goal);
+
+            if (shortest - expected).abs() > 0.001 {
+                mismatches += 1;
+                if self.print_level <= 2 {
+                    println!(
+                        "Problem {}: expected {expected}, shortest {shortest}",
+                        problem.number
+                    );
+                }
+            }
+        }
+
+        if self.print_level <= 2 {
+            println!("Expected lengths differing from shortest distances: {mismatches}");
+        }
+        mismatches
     }
 
     /// Print a problems in a map
@@ -360,6 +1758,9 @@ impl Context {
 pub struct BareContext {
     graph: Graph,
     bare_problems: Vec<(Node, Node)>,
+    jump_table: Option<JumpTable>,
+    beam_width: usize,
+    workspace: Mutex<Workspace>,
 }
 
 impl BareContext {
@@ -373,6 +1774,47 @@ impl BareContext {
         }
     }
 
+    /// Solve problems using A* with the open list `O` and drop the results
+    #[allow(unused_must_use)]
+    pub fn astar_with<O: OpenList>(&self) {
+        for (start, goal) in &self.bare_problems {
+            let astar = AStar::<O>::with_open_list(*start, *goal, &self.graph);
+
+            astar.solve();
+        }
+    }
+
+    /// Solve problems using A* that reuses the same workspace and drop the results
+    #[allow(unused_must_use)]
+    pub fn astar_in_workspace(&self) {
+        let workspace = &mut self.workspace.lock().unwrap();
+        for (start, goal) in &self.bare_problems {
+            let astar = AStar::in_workspace(*start, *goal, &self.graph, workspace);
+
+            astar.solve_in(workspace);
+        }
+    }
+
+    /// Solve problems using greedy best-first search and drop the results
+    #[allow(unused_must_use)]
+    pub fn greedy(&self) {
+        for (start, goal) in &self.bare_problems {
+            let greedy = GreedySearch::new(*start, *goal, &self.graph);
+
+            greedy.solve();
+        }
+    }
+
+    /// Solve problems using beam search and drop the results
+    #[allow(unused_must_use)]
+    pub fn beam(&self) {
+        for (start, goal) in &self.bare_problems {
+            let beam = BeamSearch::new(*start, *goal, &self.graph, self.beam_width);
+
+            beam.solve();
+        }
+    }
+
     /// Solve problems using Fringe search and drop the results
     #[allow(unused_must_use)]
     pub fn fringe(&self) {
@@ -382,4 +1824,68 @@ impl BareContext {
             fringe.solve();
         }
     }
+
+    /// Solve problems using Fringe search that reuses the same workspace and drop the results
+    #[allow(unused_must_use)]
+    pub fn fringe_in_workspace(&self) {
+        let workspace = &mut self.workspace.lock().unwrap();
+        for (start, goal) in &self.bare_problems {
+            let fringe = FringeSearch::in_workspace(*start, *goal, &self.graph, workspace);
+
+            fringe.solve_in(workspace);
+        }
+    }
+
+    /// Solve problems using parallel Fringe search in a thread pool and drop the results
+    #[allow(unused_must_use)]
+    pub fn parallel_fringe(&self, pool: &ThreadPool) {
+        pool.install(|| {
+            for (start, goal) in &self.bare_problems {
+                let fringe = ParallelFringe::new(*start, *goal, &self.graph);
+
+                fringe.solve();
+            }
+        });
+    }
+
+    /// Solve problems using bidirectional A* and drop the results
+    #[allow(unused_must_use)]
+    pub fn bidirectional_astar(&self) {
+        for (start, goal) in &self.bare_problems {
+            let astar = BidirectionalAStar::new(*start, *goal, &self.graph);
+
+            astar.solve();
+        }
+    }
+
+    /// Solve problems using bidirectional Fringe search and drop the results
+    #[allow(unused_must_use)]
+    pub fn bidirectional_fringe(&self) {
+        for (start, goal) in &self.bare_problems {
+            let fringe = BidirectionalFringe::new(*start, *goal, &self.graph);
+
+            fringe.solve();
+        }
+    }
+
+    /// Solve problems using Jump Point Search and drop the results
+    #[allow(unused_must_use)]
+    pub fn jps(&self) {
+        for (start, goal) in &self.bare_problems {
+            let jps = Jps::new(*start, *goal, &self.graph);
+
+            jps.solve();
+        }
+    }
+
+    /// Solve problems using JPS+ and drop the results
+    #[allow(unused_must_use)]
+    pub fn jps_plus(&self) {
+        let table = self.jump_table.as_ref().expect("JPS+ needs a jump table");
+        for (start, goal) in &self.bare_problems {
+            let jps = JpsPlus::new(*start, *goal, table);
+
+            jps.solve();
+        }
+    }
 }
@@ -0,0 +1,136 @@
+use crate::{xy_to_index, Node};
+use anyhow::{anyhow, bail};
+use std::{
+    fmt,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+/// One change to a map or a request to act on the changes.
+///
+/// In a file these are written one per line as `block x y`, `free x y`, `move x y` or `replan`.
+/// Empty lines and lines starting with `#` are skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// Cell at `(x, y)` becomes a wall
+    Block(usize, usize),
+    /// Cell at `(x, y)` becomes passable
+    Free(usize, usize),
+    /// Start moves to `(x, y)`
+    Move(usize, usize),
+    /// Find a new path after the previous edits
+    Replan,
+}
+
+impl Edit {
+    /// Parse a line of an edits file
+    /// ```
+    /// # use fringe_vs_astar::edits::Edit;
+    /// assert_eq!(Edit::parse("block 3 4").unwrap(), Edit::Block(3, 4));
+    /// assert_eq!(Edit::parse("replan").unwrap(), Edit::Replan);
+    /// assert!(Edit::parse("free 3").is_err());
+    /// ```
+    pub fn parse(value: &str) -> anyhow::Result<Edit> {
+        let fields: Vec<&str> = value.split_ascii_whitespace().collect();
+        let xy = || -> anyhow::Result<(usize, usize)> {
+            match fields[1..] {
+                [x, y] => Ok((x.parse()?, y.parse()?)),
+                _ => Err(anyhow!("Expected coordinates x y")),
+            }
+        };
+
+        match fields.first() {
+            Some(&"block") => xy().map(|(x, y)| Edit::Block(x, y)),
+            Some(&"free") => xy().map(|(x, y)| Edit::Free(x, y)),
+            Some(&"move") => xy().map(|(x, y)| Edit::Move(x, y)),
+            Some(&"replan") if fields.len() == 1 => Ok(Edit::Replan),
+            _ => bail!("Unknown edit: {value}"),
+        }
+    }
+
+    /// Coordinates of the cell affected by the edit, if any
+    #[must_use]
+    pub fn xy(&self) -> Option<(usize, usize)> {
+        match *self {
+            Edit::Block(x, y) | Edit::Free(x, y) | Edit::Move(x, y) => Some((x, y)),
+            Edit::Replan => None,
+        }
+    }
+
+    /// Cell affected by the edit as a [`Node`], if any
+    #[must_use]
+    pub fn node(&self, map_width: usize) -> Option<Node> {
+        self.xy().map(|(x, y)| xy_to_index(x, y, map_width))
+    }
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Edit::Block(x, y) => write!(f, "block {x} {y}"),
+            Edit::Free(x, y) => write!(f, "free {x} {y}"),
+            Edit::Move(x, y) => write!(f, "move {x} {y}"),
+            Edit::Replan => write!(f, "replan"),
+        }
+    }
+}
+
+/// Sequence of [`Edit`]s read from a file, with the line number of every edit
+pub struct Edits {
+    edits: Vec<Edit>,
+    lines: Vec<usize>,
+}
+
+impl Edits {
+    /// Read the supplied edits file
+    pub fn from_file(file_path: &PathBuf) -> anyhow::Result<Edits> {
+        let f = File::open(file_path)?;
+        let mut edits = vec![];
+        let mut lines = vec![];
+        for (i, row) in BufReader::new(f).lines().enumerate() {
+            let row = row?;
+            let row = row.trim();
+            if row.is_empty() || row.starts_with('#') {
+                continue;
+            }
+            edits.push(Edit::parse(row).map_err(|e| anyhow!("Line {}: {e}", i + 1))?);
+            lines.push(i + 1);
+        }
+        Ok(Edits { edits, lines })
+    }
+
+    /// Make sure that every edited cell is on a map of the given size
+    /// ```
+    /// # use fringe_vs_astar::edits::{Edit, Edits};
+    /// let edits = Edits::from(vec![Edit::Block(3, 4), Edit::Replan, Edit::Move(5, 0)]);
+    /// assert!(edits.check_bounds(6, 5).is_ok());
+    /// assert!(edits.check_bounds(5, 5).is_err());
+    /// ```
+    pub fn check_bounds(&self, width: usize, height: usize) -> anyhow::Result<()> {
+        for (edit, line) in self.edits.iter().zip(&self.lines) {
+            if let Some((x, y)) = edit.xy().filter(|(x, y)| *x >= width || *y >= height) {
+                bail!("Line {line}: Cell ({x}, {y}) is outside of the {width} ⨉ {height} map");
+            }
+        }
+        Ok(())
+    }
+
+    /// Wrapper for iterating over self
+    pub fn iter(&self) -> impl Iterator<Item = &Edit> {
+        self.edits.iter()
+    }
+
+    /// Number of replans requested
+    #[must_use]
+    pub fn replans(&self) -> usize {
+        self.edits.iter().filter(|e| **e == Edit::Replan).count()
+    }
+}
+
+impl From<Vec<Edit>> for Edits {
+    fn from(edits: Vec<Edit>) -> Self {
+        let lines = (1..=edits.len()).collect();
+        Edits { edits, lines }
+    }
+}
@@ -15,9 +15,21 @@ pub mod context;
 /// Instance of a problem
 pub mod problem;
 
+/// Changes to a map over time
+pub mod edits;
+
+/// Moving obstacles with known trajectories
+pub mod obstacles;
+
+/// Sources and targets for searches between sets of points
+pub mod points;
+
 /// Neat printing of a map and possibly start, goal and a path.
 pub mod printable;
 
+/// Helpers for files that are saved next to a map
+pub(crate) mod sidecar;
+
 // pub mod node;
 
 /// Octile distance of diagonal movement
@@ -3,9 +3,14 @@ use fringe_vs_astar::context::Context;
 
 use clap::Parser;
 
-fn main() {
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    if let Err(error) = cli.check() {
+        error.exit();
+    }
     // For some printings the full context is not needed
-    if let Some(context) = Context::new(Cli::parse()) {
-        context.run();
+    if let Some(context) = Context::new(cli) {
+        context.run()?;
     }
+    Ok(())
 }
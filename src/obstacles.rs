@@ -0,0 +1,179 @@
+use crate::{xy_to_index, Node};
+use anyhow::{anyhow, bail};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+/// Timed cells of one moving obstacle.
+///
+/// Obstacle appears at its first waypoint, stays at a cell until the timestep of the next waypoint
+/// and disappears after the last one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trajectory {
+    /// Waypoints as `(timestep, x, y)`, ordered by timestep
+    waypoints: Vec<(u32, usize, usize)>,
+}
+
+impl Trajectory {
+    /// Create a trajectory from `(timestep, x, y)` waypoints.
+    /// Timesteps must increase and each waypoint must be next to the previous one or in the same cell.
+    /// ```
+    /// # use fringe_vs_astar::obstacles::Trajectory;
+    /// let trajectory = Trajectory::new(vec![(2, 0, 0), (4, 1, 0)]).unwrap();
+    /// assert_eq!(trajectory.position(1), None);
+    /// assert_eq!(trajectory.position(3), Some((0, 0)));
+    /// assert_eq!(trajectory.position(4), Some((1, 0)));
+    /// assert_eq!(trajectory.position(5), None);
+    ///
+    /// assert!(Trajectory::new(vec![(0, 0, 0), (1, 2, 0)]).is_err());
+    /// ```
+    pub fn new(waypoints: Vec<(u32, usize, usize)>) -> anyhow::Result<Trajectory> {
+        if waypoints.is_empty() {
+            bail!("Trajectory has no waypoints");
+        }
+        for pair in waypoints.windows(2) {
+            let ((t0, x0, y0), (t1, x1, y1)) = (pair[0], pair[1]);
+            if t1 <= t0 {
+                bail!("Timestep {t1} does not come after {t0}");
+            }
+            if x0.abs_diff(x1) > 1 || y0.abs_diff(y1) > 1 {
+                bail!("Obstacle jumps from ({x0}, {y0}) to ({x1}, {y1})");
+            }
+        }
+        Ok(Trajectory { waypoints })
+    }
+
+    /// Cell of the obstacle at a timestep, or `None` if it is not on the map then
+    #[must_use]
+    pub fn position(&self, time: u32) -> Option<(usize, usize)> {
+        if time > self.end() {
+            return None;
+        }
+        let next = self.waypoints.partition_point(|(t, _, _)| *t <= time);
+        next.checked_sub(1).map(|i| {
+            let (_, x, y) = self.waypoints[i];
+            (x, y)
+        })
+    }
+
+    /// First timestep of the obstacle
+    #[must_use]
+    pub fn start(&self) -> u32 {
+        self.waypoints[0].0
+    }
+
+    /// Last timestep of the obstacle
+    #[must_use]
+    pub fn end(&self) -> u32 {
+        self.waypoints[self.waypoints.len() - 1].0
+    }
+
+    /// [`Node`] occupied at every timestep from start to end
+    pub fn nodes(&self, map_width: usize) -> impl Iterator<Item = (u32, Node)> + '_ {
+        (self.start()..=self.end()).map(move |time| {
+            let (x, y) = self.position(time).unwrap();
+            (time, xy_to_index(x, y, map_width))
+        })
+    }
+}
+
+/// Moving obstacles read from a file.
+///
+/// A file lists waypoints `t x y`, one per line, and every trajectory begins with a line `obstacle`.
+/// Empty lines and lines starting with `#` are skipped.
+/// ```text
+/// # Walks right along a corridor and waits at its end
+/// obstacle
+/// 0 3 5
+/// 1 4 5
+/// 6 4 5
+/// ```
+pub struct Obstacles {
+    trajectories: Vec<Trajectory>,
+}
+
+impl Obstacles {
+    /// Read the supplied obstacles file
+    pub fn from_file(file_path: &PathBuf) -> anyhow::Result<Obstacles> {
+        let f = File::open(file_path)?;
+        let mut waypoints: Vec<Vec<(u32, usize, usize)>> = vec![];
+        for (i, row) in BufReader::new(f).lines().enumerate() {
+            let row = row?;
+            let row = row.trim();
+            if row.is_empty() || row.starts_with('#') {
+                continue;
+            }
+            if row == "obstacle" {
+                waypoints.push(vec![]);
+                continue;
+            }
+            let waypoint = Self::parse_waypoint(row).map_err(|e| anyhow!("Line {}: {e}", i + 1))?;
+            waypoints
+                .last_mut()
+                .ok_or_else(|| anyhow!("Line {}: Waypoint before any obstacle", i + 1))?
+                .push(waypoint);
+        }
+
+        let trajectories = waypoints
+            .into_iter()
+            .enumerate()
+            .map(|(i, w)| Trajectory::new(w).map_err(|e| anyhow!("Obstacle {}: {e}", i + 1)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Obstacles { trajectories })
+    }
+
+    fn parse_waypoint(value: &str) -> anyhow::Result<(u32, usize, usize)> {
+        match value.split_ascii_whitespace().collect::<Vec<_>>()[..] {
+            [t, x, y] => Ok((t.parse()?, x.parse()?, y.parse()?)),
+            _ => bail!("Expected a waypoint t x y: {value}"),
+        }
+    }
+
+    /// Make sure that every waypoint is on a map of the given size
+    /// ```
+    /// # use fringe_vs_astar::obstacles::{Obstacles, Trajectory};
+    /// let obstacles = Obstacles::from(vec![Trajectory::new(vec![(0, 4, 2), (1, 5, 2)]).unwrap()]);
+    /// assert!(obstacles.check_bounds(6, 3).is_ok());
+    /// assert!(obstacles.check_bounds(5, 3).is_err());
+    /// ```
+    pub fn check_bounds(&self, width: usize, height: usize) -> anyhow::Result<()> {
+        for (i, trajectory) in self.trajectories.iter().enumerate() {
+            if let Some((t, x, y)) = trajectory
+                .waypoints
+                .iter()
+                .find(|(_, x, y)| *x >= width || *y >= height)
+            {
+                bail!(
+                    "Obstacle {}: Waypoint ({x}, {y}) at timestep {t} is outside of the {width} ⨉ {height} map",
+                    i + 1
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Wrapper for iterating over self
+    pub fn iter(&self) -> impl Iterator<Item = &Trajectory> {
+        self.trajectories.iter()
+    }
+
+    /// Returns the number of obstacles
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.trajectories.len()
+    }
+
+    /// Returns `true` if there are no obstacles
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.trajectories.is_empty()
+    }
+}
+
+impl From<Vec<Trajectory>> for Obstacles {
+    fn from(trajectories: Vec<Trajectory>) -> Self {
+        Obstacles { trajectories }
+    }
+}
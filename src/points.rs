@@ -0,0 +1,84 @@
+use crate::{xy_to_index, Node};
+use anyhow::{anyhow, bail};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+
+/// Sources and targets read from a file.
+///
+/// Every line is `source x y` or `target x y`. Empty lines and lines starting with `#` are skipped.
+/// ```text
+/// # Depot and two customers
+/// source 3 5
+/// target 10 12
+/// target 40 2
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Points {
+    sources: Vec<(usize, usize)>,
+    targets: Vec<(usize, usize)>,
+}
+
+impl Points {
+    /// Read the supplied points file
+    pub fn from_file(file_path: &PathBuf) -> anyhow::Result<Points> {
+        let f = File::open(file_path)?;
+        let mut points = Points::default();
+        for (i, row) in BufReader::new(f).lines().enumerate() {
+            let row = row?;
+            let row = row.trim();
+            if row.is_empty() || row.starts_with('#') {
+                continue;
+            }
+            points
+                .parse_point(row)
+                .map_err(|e| anyhow!("Line {}: {e}", i + 1))?;
+        }
+        if points.targets.is_empty() {
+            bail!("No targets in points file");
+        }
+        Ok(points)
+    }
+
+    fn parse_point(&mut self, value: &str) -> anyhow::Result<()> {
+        match value.split_ascii_whitespace().collect::<Vec<_>>()[..] {
+            ["source", x, y] => self.sources.push((x.parse()?, y.parse()?)),
+            ["target", x, y] => self.targets.push((x.parse()?, y.parse()?)),
+            _ => bail!("Expected source x y or target x y: {value}"),
+        }
+        Ok(())
+    }
+
+    /// Make sure that every point is on a map of the given size
+    pub fn check_bounds(&self, width: usize, height: usize) -> anyhow::Result<()> {
+        match self
+            .sources
+            .iter()
+            .chain(&self.targets)
+            .find(|(x, y)| *x >= width || *y >= height)
+        {
+            Some((x, y)) => bail!("Point ({x}, {y}) is outside of the {width} ⨉ {height} map"),
+            None => Ok(()),
+        }
+    }
+
+    /// Sources as nodes of a map with the given width
+    #[must_use]
+    pub fn sources(&self, map_width: usize) -> Vec<Node> {
+        self.sources
+            .iter()
+            .map(|(x, y)| xy_to_index(*x, *y, map_width))
+            .collect()
+    }
+
+    /// Targets as nodes of a map with the given width
+    #[must_use]
+    pub fn targets(&self, map_width: usize) -> Vec<Node> {
+        self.targets
+            .iter()
+            .map(|(x, y)| xy_to_index(*x, *y, map_width))
+            .collect()
+    }
+}
@@ -4,6 +4,8 @@ use std::{fmt, time::Duration};
 
 use rayon::prelude::*;
 
+use crate::structures::grid::line;
+use crate::structures::DistanceField;
 use crate::Cost;
 use crate::{index_to_xy, problem::Problem, structures::map::Map, Node};
 
@@ -23,8 +25,20 @@ pub enum Cell {
     Second,
     Third,
     InFrontier,
+    Distance(u8),
+    Agent(usize),
+    Route(usize),
 }
 
+/// Colors of [`Cell::Distance`] from the nearest to the farthest
+pub const GRADIENT: [char; 7] = ['🔴', '🟠', '🟡', '🟢', '🔵', '🟣', '🟤'];
+
+/// Symbols of [`Cell::Route`] from the best route to the worst. Routes share symbols if there are more of them.
+pub const ROUTES: [char; 6] = ['🟦', '🟩', '🟨', '🟧', '🟥', '🟫'];
+
+/// Symbols of [`Cell::Agent`]. Agents share symbols if there are more of them.
+pub const AGENTS: [char; 10] = ['🐶', '🐱', '🐭', '🐹', '🐰', '🦊', '🐻', '🐼', '🐨', '🐯'];
+
 #[derive(Clone)]
 pub struct Printable {
     pub grid: Vec<Vec<Cell>>,
@@ -72,14 +86,23 @@ impl Printable {
             self.add_header("Expected", l);
         }
     }
-    pub fn add_path(&mut self, mut path: Vec<Node>) {
-        let path: Vec<(usize, usize)> = path
-            .drain(..)
-            .map(|i| index_to_xy(i, self.width))
+    /// Draw a path. Consecutive nodes that are not neighbors are waypoints,
+    /// and the straight line between them is drawn.
+    pub fn add_path(&mut self, path: Vec<Node>) {
+        let waypoints: Vec<(i32, i32)> = path
+            .iter()
+            .map(|i| index_to_xy(*i, self.width))
+            .map(|(x, y)| (x as i32, y as i32))
+            .collect();
+        let cells: Vec<(usize, usize)> = waypoints
+            .windows(2)
+            .flat_map(|pair| line(pair[0], pair[1]))
+            .chain(waypoints.first().copied())
+            .map(|(x, y)| (x as usize, y as usize))
             .filter(|(x, y)| !matches!(self.grid[*y][*x], Cell::Start | Cell::Goal))
             .collect();
 
-        for (x, y) in path {
+        for (x, y) in cells {
             self.grid[y][x] = Cell::Path;
         }
     }
@@ -99,6 +122,14 @@ impl Printable {
             self.add_spacing();
         }
     }
+    pub fn add_wall(&mut self, node: Node) {
+        let (x, y) = index_to_xy(node, self.width);
+        self.grid[y][x] = Cell::Wall;
+    }
+    pub fn add_open(&mut self, node: Node) {
+        let (x, y) = index_to_xy(node, self.width);
+        self.grid[y][x] = Cell::Open;
+    }
     pub fn add_inopen(&mut self, node: Node) {
         let (x, y) = index_to_xy(node, self.width);
         self.grid[y][x] = Cell::InOpen;
@@ -131,6 +162,39 @@ impl Printable {
         let (x, y) = index_to_xy(node, self.width);
         self.grid[y][x] = Cell::InFrontier;
     }
+    pub fn add_agent(&mut self, node: Node, agent: usize) {
+        let (x, y) = index_to_xy(node, self.width);
+        self.grid[y][x] = Cell::Agent(agent);
+    }
+    /// Draw a cell of the `route`th route, unless it is start or goal
+    pub fn add_route(&mut self, node: Node, route: usize) {
+        let (x, y) = index_to_xy(node, self.width);
+        if !matches!(self.grid[y][x], Cell::Start | Cell::Goal) {
+            self.grid[y][x] = Cell::Route(route);
+        }
+    }
+
+    /// Color every reached cell by its distance from the source of the field
+    pub fn add_distance_field(&mut self, field: &DistanceField) {
+        let max = field.max_distance();
+        let levels = (GRADIENT.len() - 1) as Cost;
+
+        for (i, distance) in field.iter().enumerate() {
+            if distance.is_finite() {
+                let (x, y) = index_to_xy(i.try_into().unwrap(), self.width);
+                let level = if max > 0.0 {
+                    distance / max * levels
+                } else {
+                    0.0
+                };
+                self.grid[y][x] = Cell::Distance(level.round() as u8);
+            }
+        }
+        self.add_start(field.source());
+
+        self.add_header("Reached", field.reached());
+        self.add_header("Farthest", max);
+    }
 
     pub fn add_header<T: ToString, U: ToString>(&mut self, key: T, value: U) {
         self.headers.push((key.to_string(), value.to_string()));
@@ -166,7 +230,7 @@ impl Printable {
         let average = total_duration / operations;
         let variance = durations
             .par_iter()
-            .map(|d| (d.as_secs_f64() - average.as_secs_f64()))
+            .map(|d| d.as_secs_f64() - average.as_secs_f64())
             .sum::<f64>()
             / operations as f64;
         let standard_deviation = Duration::from_secs_f64(variance.sqrt());
@@ -219,6 +283,9 @@ impl From<Cell> for char {
             Cell::OldLater | Cell::InFrontier => '⭕',
             Cell::InClosed => '✅',
             Cell::First => '❕',
+            Cell::Distance(level) => GRADIENT[usize::from(level).min(GRADIENT.len() - 1)],
+            Cell::Agent(agent) => AGENTS[agent % AGENTS.len()],
+            Cell::Route(route) => ROUTES[route % ROUTES.len()],
         }
     }
 }
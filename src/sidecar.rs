@@ -0,0 +1,16 @@
+use crate::structures::Grid;
+use std::io::Read;
+
+/// FNV-1a hash of the passability of every cell
+pub(crate) fn checksum<G: Grid>(grid: &G) -> u64 {
+    (0..(grid.get_width() * grid.get_height()) as i32).fold(0xcbf2_9ce4_8422_2325, |hash, i| {
+        (hash ^ u64::from(grid.get(i))).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Read a little endian `u32`
+pub(crate) fn read_u32(file: &mut impl Read) -> anyhow::Result<u32> {
+    let mut bytes = [0; 4];
+    file.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
@@ -5,3 +5,11 @@ pub use crate::structures::graph::Graph;
 /// Representation of a file as boolean structure
 pub mod map;
 pub use crate::structures::map::Map;
+
+/// Common interface for grid representations
+pub mod grid;
+pub use crate::structures::grid::Grid;
+
+/// Distances from one source to every cell
+pub mod distance_field;
+pub use crate::structures::distance_field::DistanceField;
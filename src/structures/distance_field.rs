@@ -0,0 +1,103 @@
+use crate::{Cost, Node, Path};
+
+/// Shortest distances and parents from one source to every [`Node`] of a map.
+/// Unreached nodes have infinite distance and no parent.
+/// ```
+/// # use fringe_vs_astar::structures::DistanceField;
+/// // 012
+/// // 345
+/// let mut field = DistanceField::new(0, 6, 3);
+/// field.set(1, 1.0, 0);
+/// field.set(5, 2.4, 1);
+///
+/// assert_eq!(field.path_to(5), Some(vec![0, 1, 5]));
+/// assert_eq!(field.get(4), f32::INFINITY);
+/// assert_eq!(field.path_to(4), None);
+/// ```
+#[derive(Clone)]
+pub struct DistanceField {
+    source: Node,
+    distances: Vec<Cost>,
+    parents: Vec<Node>,
+    width: usize,
+}
+
+impl DistanceField {
+    /// Create a field for a map of `size` cells where only the source has been reached
+    #[must_use]
+    pub fn new(source: Node, size: usize, width: usize) -> Self {
+        let mut distances = vec![f32::INFINITY; size];
+        distances[source as usize] = 0.0;
+        let mut parents = vec![Node::MAX; size];
+        parents[source as usize] = source;
+
+        DistanceField {
+            source,
+            distances,
+            parents,
+            width,
+        }
+    }
+
+    /// Set distance and parent of a [`Node`]
+    pub fn set(&mut self, node: Node, distance: Cost, parent: Node) {
+        self.distances[node as usize] = distance;
+        self.parents[node as usize] = parent;
+    }
+
+    /// Distance from the source to a [`Node`]
+    #[must_use]
+    pub fn get(&self, node: Node) -> Cost {
+        self.distances[node as usize]
+    }
+
+    /// Parent of a [`Node`] on the shortest path from the source. Source is its own parent.
+    #[must_use]
+    pub fn parent(&self, node: Node) -> Option<Node> {
+        let parent = self.parents[node as usize];
+        (parent != Node::MAX).then_some(parent)
+    }
+
+    /// Shortest path from the source to a [`Node`], if it has been reached
+    #[must_use]
+    pub fn path_to(&self, node: Node) -> Option<Path> {
+        let mut path = vec![node];
+        while path[path.len() - 1] != self.source {
+            path.push(self.parent(path[path.len() - 1])?);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Get source of the field
+    #[must_use]
+    pub fn source(&self) -> Node {
+        self.source
+    }
+
+    /// Get width of the map
+    #[must_use]
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    /// Iterator over distances of every cell, infinite for unreached ones
+    pub fn iter(&self) -> impl Iterator<Item = &Cost> {
+        self.distances.iter()
+    }
+
+    /// Number of reached nodes, including the source
+    #[must_use]
+    pub fn reached(&self) -> usize {
+        self.distances.iter().filter(|d| d.is_finite()).count()
+    }
+
+    /// Largest finite distance
+    #[must_use]
+    pub fn max_distance(&self) -> Cost {
+        self.distances
+            .iter()
+            .filter(|d| d.is_finite())
+            .fold(0.0, |a, b| a.max(*b))
+    }
+}
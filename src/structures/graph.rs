@@ -1,13 +1,17 @@
 use std::vec::Vec;
 
 use crate::structures::map::Map;
-use crate::Node;
+use crate::structures::Grid;
 use crate::DIAGONAL_COST;
+use crate::{index_to_xy, xy_to_index, Node};
 
 /// Adjacency listed representation of a [`Map`].
+/// Passability of the original cells is kept, so this can be used as a [`Grid`] as well.
 #[derive(Clone)]
 pub struct Graph {
     adjacency_list: Vec<Vec<(Node, f32)>>,
+    passable: Vec<bool>,
+    max_edge_cost: f32,
     height: usize,
     width: usize,
 }
@@ -15,12 +19,15 @@ impl Graph {
     /// Constructor
     #[must_use]
     pub fn new(map: Map) -> Graph {
+        let adjacency_list: Vec<Vec<(Node, f32)>> = map
+            .iter()
+            .zip(0..)
+            .map(|(b, i)| generate_neighbors(i, b, &map))
+            .collect();
         Graph {
-            adjacency_list: map
-                .iter()
-                .zip(0..)
-                .map(|(b, i)| generate_neighbors(i, b, &map))
-                .collect(),
+            max_edge_cost: max_cost(adjacency_list.iter()),
+            adjacency_list,
+            passable: map.array(),
             height: map.get_height(),
             width: map.get_width(),
         }
@@ -46,6 +53,37 @@ impl Graph {
         self.width * self.height
     }
 
+    /// Change passability of a cell.
+    /// Neighbors are generated again for the cell and the cells around it,
+    /// because diagonal moves past the cell depend on it as well.
+    pub fn set_passable(&mut self, node: Node, passable: bool) {
+        self.passable[node as usize] = passable;
+
+        let (x, y) = index_to_xy(node, self.width);
+        for (dx, dy) in [-1, 0, 1]
+            .iter()
+            .flat_map(|dy| [(-1, *dy), (0, *dy), (1, *dy)])
+        {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            if nx < 0 || ny < 0 || nx as usize >= self.width || ny as usize >= self.height {
+                continue;
+            }
+            let i = xy_to_index(nx as usize, ny as usize, self.width);
+            self.adjacency_list[i as usize] =
+                generate_neighbors(i, &self.passable[i as usize], self);
+            self.max_edge_cost = self
+                .max_edge_cost
+                .max(max_cost(std::iter::once(&self.adjacency_list[i as usize])));
+        }
+    }
+
+    /// Largest weight of an edge, or 0 if there are no edges.
+    /// Removing cells never lowers this, so it is an upper bound after changes to the map.
+    #[must_use]
+    pub fn max_edge_cost(&self) -> f32 {
+        self.max_edge_cost
+    }
+
     /// Get size of graph
     pub fn size(&self) -> usize {
         self.adjacency_list.iter().filter(|a| !a.is_empty()).count()
@@ -66,9 +104,28 @@ impl Graph {
     }
 }
 
+impl Grid for Graph {
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, i: i32) -> bool {
+        i >= 0 && (i as usize) < self.passable.len() && self.passable[i as usize]
+    }
+}
+
+/// Largest weight in adjacency lists
+fn max_cost<'a>(lists: impl Iterator<Item = &'a Vec<(Node, f32)>>) -> f32 {
+    lists.flatten().map(|(_, w)| *w).fold(0.0, f32::max)
+}
+
 /// Provide a list of neighbors for given cell in a grid.
 /// Makes sure that path does not cut through corners of unpassable cells.
-fn generate_neighbors(node: Node, generate: &bool, map: &Map) -> Vec<(Node, f32)> {
+fn generate_neighbors(node: Node, generate: &bool, map: &impl Grid) -> Vec<(Node, f32)> {
     /*
        |--|--|--|    |--|--|--|
        |-4|-3|-2|    | 0| 1| 2|
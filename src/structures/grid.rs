@@ -0,0 +1,121 @@
+use crate::xy_to_index;
+
+/// Passability of cells in a rectangular grid.
+///
+/// This is implemented by [`Map`](super::Map) and by [`Graph`](super::Graph),
+/// so grid based algorithms can work straight off either of them.
+pub trait Grid {
+    /// Width of the grid
+    fn get_width(&self) -> usize;
+
+    /// Height of the grid
+    fn get_height(&self) -> usize;
+
+    /// Passability of a cell at array index `i`. Indexes outside of the grid are not passable.
+    fn get(&self, i: i32) -> bool;
+
+    /// Passability of a cell at `(x, y)`. Cells outside of the grid are not passable.
+    ///```
+    /// # use fringe_vs_astar::structures::{Grid, Map};
+    /// # use std::path::PathBuf;
+    /// // .@.
+    /// let map = Map::new(PathBuf::from("maps/3x3.map"));
+    /// assert!(map.passable(0, 1));
+    /// assert!(!map.passable(1, 1));
+    /// assert!(!map.passable(-1, 1));
+    /// assert!(!map.passable(3, 1));
+    ///```
+    fn passable(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.get_width() || y as usize >= self.get_height() {
+            false
+        } else {
+            self.get(xy_to_index(x as usize, y as usize, self.get_width()) as i32)
+        }
+    }
+
+    /// Check if a straight line between centers of two cells stays in passable cells.
+    ///
+    /// Every cell the line touches must be passable. When the line passes exactly through a corner,
+    /// both cells beside the corner must be passable too, which matches the rule that diagonal moves
+    /// can't cut corners.
+    ///```
+    /// # use fringe_vs_astar::structures::{Grid, Map};
+    /// # use std::path::PathBuf;
+    /// // ...
+    /// // .@.
+    /// // ...
+    /// let map = Map::new(PathBuf::from("maps/3x3.map"));
+    /// assert!(map.line_of_sight((0, 0), (2, 0)));
+    /// assert!(!map.line_of_sight((0, 0), (2, 2)));
+    /// assert!(!map.line_of_sight((0, 1), (1, 0)));
+    ///```
+    fn line_of_sight(&self, from: (i32, i32), to: (i32, i32)) -> bool {
+        walk_line(from, to, |(x, y), corner| {
+            self.passable(x, y)
+                && corner.is_none_or(|[(ax, ay), (bx, by)]| {
+                    self.passable(ax, ay) && self.passable(bx, by)
+                })
+        })
+    }
+}
+
+/// Cells on a straight line between centers of two cells, both ends included.
+/// When the line passes exactly through a corner, it continues diagonally.
+/// ```
+/// # use fringe_vs_astar::structures::grid::line;
+/// assert_eq!(line((0, 0), (3, 1)), vec![(0, 0), (1, 0), (2, 1), (3, 1)]);
+/// assert_eq!(line((2, 2), (0, 0)), vec![(2, 2), (1, 1), (0, 0)]);
+/// ```
+#[must_use]
+pub fn line(from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+    let mut cells = vec![];
+    walk_line(from, to, |cell, _| {
+        cells.push(cell);
+        true
+    });
+    cells
+}
+
+/// Walk through cells on a line from `from` to `to` and call `visit` for each of them.
+/// If the line crossed a corner to get to the cell, the two cells beside the corner are given as well.
+/// Walking stops when `visit` returns false, and the result tells if the whole line was walked.
+fn walk_line(
+    from: (i32, i32),
+    to: (i32, i32),
+    mut visit: impl FnMut((i32, i32), Option<[(i32, i32); 2]>) -> bool,
+) -> bool {
+    let (mut x, mut y) = from;
+    let (nx, ny) = ((to.0 - x).abs(), (to.1 - y).abs());
+    let (sx, sy) = ((to.0 - x).signum(), (to.1 - y).signum());
+
+    if !visit((x, y), None) {
+        return false;
+    }
+
+    let (mut ix, mut iy) = (0, 0);
+    while ix < nx || iy < ny {
+        // Which border of the cell is crossed first, compared without division
+        let decision = (1 + 2 * ix) * ny - (1 + 2 * iy) * nx;
+        let corner = if decision == 0 {
+            let corner = [(x + sx, y), (x, y + sy)];
+            x += sx;
+            y += sy;
+            ix += 1;
+            iy += 1;
+            Some(corner)
+        } else if decision < 0 {
+            x += sx;
+            ix += 1;
+            None
+        } else {
+            y += sy;
+            iy += 1;
+            None
+        };
+
+        if !visit((x, y), corner) {
+            return false;
+        }
+    }
+    true
+}
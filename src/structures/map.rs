@@ -1,3 +1,4 @@
+use super::Grid;
 use crate::xy_to_index;
 use crate::Node;
 use std::fs::File;
@@ -119,6 +120,20 @@ fn read(file_path: PathBuf) -> anyhow::Result<(usize, usize, Vec<String>)> {
     Ok((height, width, map))
 }
 
+impl Grid for Map {
+    fn get_width(&self) -> usize {
+        self.width
+    }
+
+    fn get_height(&self) -> usize {
+        self.height
+    }
+
+    fn get(&self, i: i32) -> bool {
+        Map::get(self, i)
+    }
+}
+
 impl Index<Node> for Map {
     type Output = bool;
 
@@ -1,10 +1,52 @@
 use fringe_vs_astar::cli::*;
 use fringe_vs_astar::context::*;
+use fringe_vs_astar::problem::Problems;
 
 use clap::Parser;
+use std::ffi::OsString;
+use std::path::PathBuf;
 
 pub fn full_lak104d_context(mode: &str) -> Context {
-    let arguments = ["", "-sss", mode, "maps/lak104d.map"];
+    full_lak104d_context_with(mode, &[])
+}
+
+pub fn full_lak104d_context_with(mode: &str, flags: &[&str]) -> Context {
+    let mut arguments = vec!["", "-sss"];
+    arguments.extend_from_slice(flags);
+    arguments.extend_from_slice(&[mode, "maps/lak104d.map"]);
     let cli = Cli::parse_from(arguments.iter());
     Context::new(cli).unwrap()
 }
+
+pub fn full_context(mode: &str, map: &str) -> Context {
+    let arguments = ["", "-sss", mode, map];
+    let cli = Cli::parse_from(arguments.iter());
+    Context::new(cli).unwrap()
+}
+
+/// Context for a copy of `map` in a temporary directory, so sidecar files are not written into maps/
+pub fn temporary_map_context(mode: &str, map: &str) -> Context {
+    let map = PathBuf::from(map);
+    let name = map.file_name().unwrap();
+    let directory = std::env::temp_dir().join(format!(
+        "fringe-vs-astar-{}-{mode}-{}",
+        std::process::id(),
+        name.to_string_lossy()
+    ));
+    std::fs::create_dir_all(&directory).unwrap();
+    let copy = directory.join(name);
+    std::fs::copy(&map, &copy).unwrap();
+
+    let problem_file = Problems::deduce_problem_file(map);
+    let arguments: [OsString; 5] = [
+        "".into(),
+        "-sss".into(),
+        format!("--problem-file={}", problem_file.display()).into(),
+        mode.into(),
+        copy.into(),
+    ];
+    let cli = Cli::parse_from(arguments);
+    let context = Context::new(cli).unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+    context
+}
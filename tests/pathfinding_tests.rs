@@ -1,5 +1,34 @@
 mod common;
 
+use fringe_vs_astar::algorithms::alternatives::Route;
+use fringe_vs_astar::algorithms::astar::open_list::{PairingHeap, Queue, TiedFrontier};
+use fringe_vs_astar::algorithms::cpd::database::PathDatabase;
+use fringe_vs_astar::algorithms::fringesearch::bucket::BucketLayout;
+use fringe_vs_astar::algorithms::hpa::abstraction::{Abstraction, Refinement};
+use fringe_vs_astar::algorithms::jpsplus::table::JumpTable;
+use fringe_vs_astar::algorithms::landmarks::{Landmarks, Strategy};
+use fringe_vs_astar::algorithms::solver::{self, Algorithm, Solver};
+use fringe_vs_astar::algorithms::theta::euclidean;
+use fringe_vs_astar::algorithms::{
+    AStar, Abort, Ara, BeamSearch, BidirectionalAStar, Cbs, DStarLite, Dijkstra, FringeSearch,
+    GreedySearch, Heuristic, Hpa, Ida, Limits, MultiGoal, Sipp, State, ThetaStar, TieBreak,
+    Workspace,
+};
+use fringe_vs_astar::cli::Cli;
+use fringe_vs_astar::edits::{Edit, Edits};
+use fringe_vs_astar::obstacles::{Obstacles, Trajectory};
+use fringe_vs_astar::points::Points;
+use fringe_vs_astar::printable::Printable;
+use fringe_vs_astar::problem::Problem;
+use fringe_vs_astar::structures::{Graph, Grid, Map};
+use fringe_vs_astar::{index_to_xy, xy_to_index, Cost, Path};
+
+use clap::Parser;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
 #[test]
 fn correct_fringe_solve_lak104d() {
     let context = common::full_lak104d_context("fringe");
@@ -43,3 +72,1176 @@ fn correct_astar_solve_lak104d() {
         );
     }
 }
+
+#[test]
+fn correct_ida_solve_lak104d() {
+    let context = common::full_lak104d_context_with("ida", &["--transposition-table"]);
+    for problem in context.problems() {
+        let result = context.solve(*problem);
+
+        assert!(
+            result.is_some(),
+            "Problem {} path not found",
+            problem.number
+        );
+
+        assert!(
+            result.unwrap() <= (problem.length.unwrap() + 0.001),
+            "Problem {} failed:\n  Expected: {}\n  Actual:   {}",
+            problem.number,
+            problem.length.unwrap(),
+            result.unwrap(),
+        );
+    }
+}
+
+#[test]
+fn correct_ida_solve_without_transposition_table_lak104d() {
+    // Without the table every path is searched again, which is too slow for long problems
+    let context = common::full_lak104d_context("ida");
+    for problem in context.problems().filter(|p| p.length < Some(20.0)) {
+        let result = context.solve(*problem);
+
+        assert!(
+            result.is_some(),
+            "Problem {} path not found",
+            problem.number
+        );
+
+        assert!(
+            (result.unwrap() - problem.length.unwrap()).abs() < 0.001,
+            "Problem {} failed:\n  Expected: {}\n  Actual:   {}",
+            problem.number,
+            problem.length.unwrap(),
+            result.unwrap(),
+        );
+    }
+}
+
+#[test]
+fn jps_matches_astar() {
+    for map in [
+        "maps/lak104d.map",
+        "maps/AR0401SR.map",
+        "maps/ost102d.map",
+        "maps/room-100-10.map",
+        "maps/CSC2F.map",
+    ] {
+        let jps = common::full_context("jps", map);
+        let astar = common::full_context("a-star", map);
+        for problem in jps.problems() {
+            let expected = astar.solve(*problem);
+            let result = jps.solve(*problem);
+
+            assert!(
+                (result.unwrap() - expected.unwrap()).abs() < 0.001,
+                "Problem {} in {map} failed:\n  A*:  {}\n  JPS: {}",
+                problem.number,
+                expected.unwrap(),
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn jps_plus_matches_astar() {
+    for map in [
+        "maps/lak104d.map",
+        "maps/AR0401SR.map",
+        "maps/ost102d.map",
+        "maps/room-100-10.map",
+        "maps/CSC2F.map",
+    ] {
+        let jps_plus = common::temporary_map_context("jps-plus", map);
+        let astar = common::full_context("a-star", map);
+        for problem in jps_plus.problems() {
+            let expected = astar.solve(*problem);
+            let result = jps_plus.solve(*problem);
+
+            assert!(
+                (result.unwrap() - expected.unwrap()).abs() < 0.001,
+                "Problem {} in {map} failed:\n  A*:   {}\n  JPS+: {}",
+                problem.number,
+                expected.unwrap(),
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn jump_table_survives_saving() {
+    let map = Map::new(PathBuf::from("maps/lak104d.map"));
+    let table = JumpTable::new(&map);
+    let file = std::env::temp_dir().join(format!("lak104d-{}.map.jps", std::process::id()));
+
+    table.save(&file).unwrap();
+    let loaded = JumpTable::load(&file, &map);
+    let other_map = JumpTable::load(&file, &Map::new(PathBuf::from("maps/ost102d.map")));
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(table, loaded.unwrap());
+    assert!(other_map.is_err());
+}
+
+#[test]
+fn bidirectional_astar_matches_astar() {
+    for map in [
+        "maps/lak104d.map",
+        "maps/AR0401SR.map",
+        "maps/room-100-10.map",
+    ] {
+        let bidirectional = common::full_context("bidirectional-a-star", map);
+        let astar = common::full_context("a-star", map);
+        for problem in bidirectional.problems() {
+            let expected = astar.solve(*problem);
+            let result = bidirectional.solve(*problem);
+
+            assert!(
+                (result.unwrap() - expected.unwrap()).abs() < 0.001,
+                "Problem {} in {map} failed:\n  A*:            {}\n  Bidirectional: {}",
+                problem.number,
+                expected.unwrap(),
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn bidirectional_fringe_matches_astar() {
+    for map in [
+        "maps/lak104d.map",
+        "maps/AR0401SR.map",
+        "maps/room-100-10.map",
+    ] {
+        let bidirectional = common::full_context("bidirectional-fringe", map);
+        let astar = common::full_context("a-star", map);
+        for problem in bidirectional.problems() {
+            let expected = astar.solve(*problem);
+            let result = bidirectional.solve(*problem);
+
+            assert!(
+                (result.unwrap() - expected.unwrap()).abs() < 0.001,
+                "Problem {} in {map} failed:\n  A*:            {}\n  Bidirectional: {}",
+                problem.number,
+                expected.unwrap(),
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn parallel_fringe_matches_astar() {
+    for map in [
+        "maps/lak104d.map",
+        "maps/AR0401SR.map",
+        "maps/room-100-10.map",
+    ] {
+        let parallel = common::full_context("parallel-fringe", map);
+        let astar = common::full_context("a-star", map);
+        for problem in parallel.problems() {
+            let expected = astar.solve(*problem);
+            let result = parallel.solve(*problem);
+
+            assert!(
+                (result.unwrap() - expected.unwrap()).abs() < 0.001,
+                "Problem {} in {map} failed:\n  A*:       {}\n  Parallel: {}",
+                problem.number,
+                expected.unwrap(),
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn parallel_fringe_with_threads_lak104d() {
+    let context = common::full_lak104d_context_with("parallel-fringe", &["--threads", "3"]);
+    for problem in context.problems() {
+        let result = context.solve(*problem).unwrap();
+        let expected = problem.length.unwrap();
+        assert!(
+            (result - expected).abs() < 0.001,
+            "Problem {} failed: {result} != {expected}",
+            problem.number,
+        );
+    }
+}
+
+#[test]
+fn batch_results_in_scenario_order() {
+    for mode in ["a-star", "fringe", "bidirectional-fringe"] {
+        let batch = common::full_lak104d_context_with(mode, &["--jobs", "3"]);
+        let results = batch.solve_all();
+        assert_eq!(results.len(), batch.problems().count());
+
+        for (problem, result) in batch.problems().zip(results) {
+            let expected = problem.length.unwrap();
+            assert!(
+                (result.unwrap() - expected).abs() < 0.001,
+                "Problem {} failed with {mode}: {} != {expected}",
+                problem.number,
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn weighted_solutions_within_bound_lak104d() {
+    for mode in ["a-star", "focal"] {
+        let context = common::full_lak104d_context_with(mode, &["--weight", "1.5"]);
+        for problem in context.problems() {
+            let result = context.solve(*problem).unwrap();
+            let expected = problem.length.unwrap();
+
+            assert!(
+                result >= expected - 0.001 && result <= 1.5 * expected + 0.001,
+                "Problem {} with {mode} failed:\n  Optimal: {}\n  Actual:  {}",
+                problem.number,
+                expected,
+                result,
+            );
+        }
+    }
+}
+
+#[test]
+fn solve_full_accepts_solutions_at_tight_bound_lak104d() {
+    for mode in ["a-star", "focal", "ara"] {
+        let mut context = common::full_lak104d_context_with(mode, &["--weight", "1.01"]);
+        assert!(context.solve_full().is_ok(), "{mode} exceeds the bound");
+    }
+}
+
+#[test]
+fn solve_full_fails_when_bound_is_exceeded_lak104d() {
+    // Shortest path of the problem is 3.82842712, which is more than 1.01 times the expected length
+    let file = std::env::temp_dir().join(format!("lak104d-{}.map.scen", std::process::id()));
+    std::fs::write(
+        &file,
+        "version 1\n0\tlak104d.map\t41\t41\t18\t35\t15\t37\t3.0\n",
+    )
+    .unwrap();
+    let problem_file = file.to_str().unwrap();
+    let mut context = common::full_lak104d_context_with(
+        "a-star",
+        &["--weight", "1.01", "--problem-file", problem_file],
+    );
+    std::fs::remove_file(&file).unwrap();
+
+    let error = context.solve_full().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "1 solutions exceed the bound of 1.01 ⨉ expected length"
+    );
+}
+
+#[test]
+fn ara_improves_within_bounds_lak104d() {
+    let context = common::full_lak104d_context("ara");
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    for problem in context.problems() {
+        let optimal = problem.length.unwrap();
+        let mut previous = f32::INFINITY;
+        let mut last = None;
+
+        for ((_, length), bound) in Ara::new(problem.start, problem.goal, &graph, 3.0) {
+            assert!(
+                length <= previous && length <= bound * optimal + 0.001,
+                "Problem {} failed:\n  Optimal: {optimal}\n  Actual:  {length} with bound {bound}",
+                problem.number,
+            );
+            previous = length;
+            last = Some((length, bound));
+        }
+
+        let (length, bound) = last.unwrap();
+        assert_eq!(bound, 1.0);
+        assert!((length - optimal).abs() < 0.001);
+    }
+}
+
+#[test]
+fn distance_fields_match_scenarios() {
+    for map in [
+        "maps/lak104d.map",
+        "maps/ost102d.map",
+        "maps/room-100-10.map",
+    ] {
+        let context = common::full_context("verify", map);
+        assert_eq!(context.verify(), 0, "Shortest distances differ in {map}");
+    }
+}
+
+#[test]
+fn correct_dijkstra_solve_lak104d() {
+    let context = common::full_lak104d_context("dijkstra");
+    for problem in context.problems() {
+        let result = context.solve(*problem);
+
+        assert!(
+            (result.unwrap() - problem.length.unwrap()).abs() < 0.001,
+            "Problem {} failed:\n  Expected: {}\n  Actual:   {}",
+            problem.number,
+            problem.length.unwrap(),
+            result.unwrap(),
+        );
+    }
+}
+
+#[test]
+fn theta_paths_are_visible_and_short() {
+    for map in ["maps/lak104d.map", "maps/AR0401SR.map"] {
+        let context = common::full_context("a-star", map);
+        let grid = Map::new(PathBuf::from(map));
+        let graph = Graph::new(Map::new(PathBuf::from(map)));
+        let xy = |n: u32| {
+            let (x, y) = index_to_xy(n, grid.get_width());
+            (x as i32, y as i32)
+        };
+
+        for problem in context.problems() {
+            let optimal = context.solve(*problem).unwrap();
+            for lazy in [false, true] {
+                let theta = ThetaStar::new(problem.start, problem.goal, &graph);
+                let theta = if lazy { theta.lazy() } else { theta };
+                let (waypoints, length) = theta.solve().unwrap();
+
+                let mut total = 0.0;
+                for pair in waypoints.windows(2) {
+                    let ((fx, fy), (tx, ty)) = (xy(pair[0]), xy(pair[1]));
+                    assert!(grid.line_of_sight((fx, fy), (tx, ty)));
+                    total += euclidean(tx - fx, ty - fy);
+                }
+                assert!(
+                    length <= optimal + 0.001 && (total - length).abs() < 0.001,
+                    "Problem {} in {map} failed:\n  A*:     {optimal}\n  Theta*: {length}",
+                    problem.number,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn d_star_lite_replans_match_dijkstra_lak104d() {
+    let context = common::full_lak104d_context("d-star-lite");
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let width = graph.get_width();
+
+    for problem in context.problems() {
+        let mut dstar = DStarLite::new(problem.start, problem.goal, &graph);
+        let (path, length) = dstar.replan().unwrap();
+        assert!((length - problem.length.unwrap()).abs() < 0.001);
+        if path.len() < 3 {
+            continue;
+        }
+
+        // Block the middle of the path, then free it again
+        let (x, y) = index_to_xy(path[path.len() / 2], width);
+        let edits = Edits::from(vec![
+            Edit::Block(x, y),
+            Edit::Replan,
+            Edit::Free(x, y),
+            Edit::Replan,
+        ]);
+        let mut changed = graph.clone();
+        changed.set_passable(path[path.len() / 2], false);
+        let expected = [
+            Dijkstra::new(problem.start, problem.goal, &changed)
+                .solve()
+                .map(|(_, l)| l),
+            Some(length),
+        ];
+
+        let mut replans = vec![];
+        dstar.replay(&edits, |_, replan| {
+            replans.push(replan.solution.map(|(_, l)| l));
+        });
+        for (actual, expected) in replans.iter().zip(expected) {
+            assert!(
+                actual
+                    .zip(expected)
+                    .map_or(actual == &expected, |(a, e)| (a - e).abs() < 0.001),
+                "Problem {} failed:\n  Expected: {expected:?}\n  Actual:   {actual:?}",
+                problem.number,
+            );
+        }
+    }
+}
+
+#[test]
+fn d_star_lite_moved_start_matches_dijkstra_lak104d() {
+    let context = common::full_lak104d_context("d-star-lite");
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+
+    // Start moves along the path and away from it, to the start of the next problem
+    let problems: Vec<_> = context.problems().collect();
+    for (problem, next) in problems.iter().zip(problems.iter().skip(1)) {
+        let mut dstar = DStarLite::new(problem.start, problem.goal, &graph);
+        let (path, _) = dstar.replan().unwrap();
+
+        for start in [path[path.len() / 2], next.start] {
+            dstar.move_start(start);
+            let actual = dstar.replan().map(|(_, l)| l);
+            let expected = Dijkstra::new(start, problem.goal, &graph)
+                .solve()
+                .map(|(_, l)| l);
+            assert!(
+                actual
+                    .zip(expected)
+                    .map_or(actual == expected, |(a, e)| (a - e).abs() < 0.001),
+                "Problem {} failed:\n  Expected: {expected:?}\n  Actual:   {actual:?}",
+                problem.number,
+            );
+        }
+    }
+}
+
+#[test]
+fn edits_outside_of_the_map_are_rejected() {
+    let map = Map::new(PathBuf::from("maps/lak104d.map"));
+    let file = std::env::temp_dir().join(format!("lak104d-{}.map.edits", std::process::id()));
+    std::fs::write(
+        &file,
+        "# Column 41 is past the right edge\nblock 3 4\nreplan\nfree 41 4\n",
+    )
+    .unwrap();
+
+    let edits = Edits::from_file(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    let error = edits
+        .check_bounds(map.get_width(), map.get_height())
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.starts_with("Line 4:"), "{error}");
+    assert!(Edits::from(vec![Edit::Move(3, 41)])
+        .check_bounds(map.get_width(), map.get_height())
+        .is_err());
+}
+
+#[test]
+fn hpa_paths_are_connected_and_not_shorter_than_optimal() {
+    for map in ["maps/lak104d.map", "maps/room-100-10.map"] {
+        let context = common::full_context("hpa", map);
+        let graph = Graph::new(Map::new(PathBuf::from(map)));
+
+        for (cluster_size, refinement) in [(8, Refinement::Full), (10, Refinement::Lazy)] {
+            let abstraction = Abstraction::new(&graph, cluster_size, refinement);
+            for problem in context.problems() {
+                let (path, length) = Hpa::new(problem.start, problem.goal, &graph, &abstraction)
+                    .solve()
+                    .unwrap();
+
+                let mut total = 0.0;
+                for pair in path.windows(2) {
+                    let (_, w) = graph
+                        .neighbors(pair[0])
+                        .find(|(n, _)| *n == pair[1])
+                        .expect("Consecutive nodes are neighbors");
+                    total += w;
+                }
+                assert_eq!(path[0], problem.start);
+                assert_eq!(path[path.len() - 1], problem.goal);
+                assert!(
+                    (total - length).abs() < 0.001 && length >= problem.length.unwrap() - 0.001,
+                    "Problem {} in {map} failed:\n  Expected: {}\n  Actual:   {length}",
+                    problem.number,
+                    problem.length.unwrap(),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn landmarks_keep_solutions_optimal_lak104d() {
+    for mode in ["a-star", "fringe"] {
+        for strategy in ["random", "farthest", "avoid"] {
+            let context = common::full_lak104d_context_with(
+                mode,
+                &["--landmarks", "6", "--landmark-strategy", strategy],
+            );
+            for problem in context.problems() {
+                let result = context.solve(*problem);
+
+                assert!(
+                    (result.unwrap() - problem.length.unwrap()).abs() < 0.001,
+                    "Problem {} failed with {mode} and {strategy}:\n  Expected: {}\n  Actual:   {}",
+                    problem.number,
+                    problem.length.unwrap(),
+                    result.unwrap(),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn fringe_with_landmarks_counts_children_of_current_bucket_lak104d() {
+    let context = common::full_lak104d_context_with("fringe", &["--landmarks", "4"]);
+    let problem = context.problems().find(|p| p.number == 92).unwrap();
+    let result = context.solve(*problem).unwrap();
+
+    assert!(
+        (result - problem.length.unwrap()).abs() < 0.001,
+        "Expected {}, got {result}",
+        problem.length.unwrap()
+    );
+}
+
+#[test]
+fn landmarks_survive_saving() {
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let landmarks = Landmarks::new(&graph, 4, Strategy::Avoid);
+    let file = std::env::temp_dir().join(format!("lak104d-{}.map.alt", std::process::id()));
+
+    landmarks.save(&file).unwrap();
+    let loaded = Landmarks::load(&file, &graph);
+    let other_map = Landmarks::load(
+        &file,
+        &Graph::new(Map::new(PathBuf::from("maps/ost102d.map"))),
+    );
+
+    // Count and landmark nodes follow the 28 byte header: make the count huge, a node outside the map
+    // and the file one distance short
+    let bytes = std::fs::read(&file).unwrap();
+    let mut corrupted = [bytes.clone(), bytes.clone(), bytes];
+    corrupted[0][28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+    corrupted[1][32..36].copy_from_slice(&(graph.map_size() as u32).to_le_bytes());
+    corrupted[2].truncate(corrupted[2].len() - 4);
+    let loaded_corrupted = corrupted.map(|bytes| {
+        std::fs::write(&file, bytes).unwrap();
+        Landmarks::load(&file, &graph)
+    });
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(landmarks, loaded.unwrap());
+    assert!(other_map.is_err());
+    assert!(loaded_corrupted.iter().all(Result::is_err));
+}
+
+#[test]
+fn cpd_matches_astar() {
+    for map in ["maps/lak104d.map", "maps/AR0401SR.map", "maps/ost102d.map"] {
+        let cpd = common::temporary_map_context("cpd", map);
+        let astar = common::full_context("a-star", map);
+        for problem in cpd.problems() {
+            let expected = astar.solve(*problem);
+            let result = cpd.solve(*problem);
+
+            assert!(
+                (result.unwrap() - expected.unwrap()).abs() < 0.001,
+                "Problem {} in {map} failed:\n  A*:  {}\n  CPD: {}",
+                problem.number,
+                expected.unwrap(),
+                result.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn path_database_survives_saving() {
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let database = PathDatabase::new(&graph);
+    let file = std::env::temp_dir().join(format!("lak104d-{}.map.cpd", std::process::id()));
+
+    database.save(&file).unwrap();
+    let loaded = PathDatabase::load(&file, &graph);
+    let other_map = PathDatabase::load(
+        &file,
+        &Graph::new(Map::new(PathBuf::from("maps/ost102d.map"))),
+    );
+
+    // Offsets follow the 24 byte header and the run count: make one decrease and the last one too small
+    let bytes = std::fs::read(&file).unwrap();
+    let last = 28 + 4 * graph.map_size();
+    let mut corrupted = [bytes.clone(), bytes];
+    corrupted[0][32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+    let run_count = u32::from_le_bytes(corrupted[1][24..28].try_into().unwrap());
+    corrupted[1][last..last + 4].copy_from_slice(&(run_count - 1).to_le_bytes());
+    let loaded_corrupted = corrupted.map(|bytes| {
+        std::fs::write(&file, bytes).unwrap();
+        PathDatabase::load(&file, &graph)
+    });
+    std::fs::remove_file(&file).unwrap();
+
+    assert_eq!(database, loaded.unwrap());
+    assert!(other_map.is_err());
+    assert!(loaded_corrupted.iter().all(Result::is_err));
+}
+
+#[test]
+fn cbs_agents_pass_each_other_around_the_wall() {
+    // Agents swap the top corners of 3x3.map, so one of them has to go around the wall in the middle
+    let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    let mut cbs = Cbs::new(vec![(0, 2), (2, 0)], &graph);
+    let plan = cbs.solve().unwrap();
+
+    assert_eq!(plan.first_conflict(), None);
+    assert!((plan.sum_of_costs() - 8.0).abs() < 0.001);
+    assert_eq!(plan.makespan(), 6);
+}
+
+#[test]
+fn cbs_plans_are_valid_lak104d() {
+    let context = common::full_lak104d_context_with("cbs", &["--agents", "12"]);
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let plan = context.plan_agents().unwrap();
+    assert_eq!(plan.first_conflict(), None);
+
+    for (agent, problem) in context.problems().take(12).enumerate() {
+        let path = &plan.paths[agent];
+        assert_eq!(path[0], problem.start);
+        assert_eq!(path[path.len() - 1], problem.goal);
+        for step in path.windows(2) {
+            assert!(
+                step[0] == step[1] || graph.neighbors(step[0]).any(|(n, _)| *n == step[1]),
+                "Agent {agent} jumps from {} to {}",
+                step[0],
+                step[1]
+            );
+        }
+        assert!(plan.costs[agent] >= problem.length.unwrap() - 0.001);
+    }
+}
+
+#[test]
+fn sipp_waits_for_obstacle_to_pass() {
+    // Obstacle stays between the top corners of 3x3.map until timestep 3,
+    // and waiting for it is faster than going around the wall in the middle
+    let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    let obstacles = Obstacles::from(vec![Trajectory::new(vec![(0, 1, 0), (3, 1, 0)]).unwrap()]);
+    let (path, cost) = Sipp::new(0, 2, &graph, &obstacles).solve().unwrap();
+
+    assert_eq!(path, vec![0, 0, 0, 0, 1, 2]);
+    assert!((cost - 2.0).abs() < 0.001);
+}
+
+#[test]
+fn obstacles_outside_of_the_map_are_rejected() {
+    let map = Map::new(PathBuf::from("maps/lak104d.map"));
+    let file = std::env::temp_dir().join(format!("lak104d-{}.map.obstacles", std::process::id()));
+    std::fs::write(
+        &file,
+        "obstacle\n0 3 5\nobstacle\n0 5000 5000\n1000 5000 5000\n",
+    )
+    .unwrap();
+
+    let obstacles = Obstacles::from_file(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    let error = obstacles
+        .check_bounds(map.get_width(), map.get_height())
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.starts_with("Obstacle 2:"), "{error}");
+    // Columns past the right edge would wrap to the next row of the 41 wide map
+    let wrapping = Trajectory::new(vec![(0, 40, 3), (1, 41, 3), (2, 42, 3)]).unwrap();
+    assert!(Obstacles::from(vec![wrapping])
+        .check_bounds(map.get_width(), map.get_height())
+        .is_err());
+}
+
+#[test]
+fn sipp_avoids_obstacles_lak104d() {
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let obstacles = Obstacles::from_file(&PathBuf::from("maps/lak104d.map.obstacles")).unwrap();
+    let width = graph.get_width();
+    let at = |time: u32| -> Vec<u32> {
+        obstacles
+            .iter()
+            .filter_map(|o| o.position(time))
+            .map(|(x, y)| xy_to_index(x, y, width))
+            .collect()
+    };
+    let last = obstacles.iter().map(Trajectory::end).max().unwrap();
+
+    let context = common::full_lak104d_context("sipp");
+    for problem in context.problems() {
+        if at(0).contains(&problem.start) {
+            continue;
+        }
+        let (path, _) = Sipp::new(problem.start, problem.goal, &graph, &obstacles)
+            .solve()
+            .unwrap_or_else(|| panic!("Problem {} not solved", problem.number));
+        assert_eq!(path[0], problem.start);
+        assert_eq!(path[path.len() - 1], problem.goal);
+
+        let position = |time: u32| path[(time as usize).min(path.len() - 1)];
+        for time in 0..=last.max(path.len() as u32) {
+            let (now, next) = (position(time), position(time + 1));
+            assert!(
+                now == next || graph.neighbors(now).any(|(n, _)| *n == next),
+                "Problem {} jumps from {now} to {next}",
+                problem.number
+            );
+            assert!(
+                !at(time).contains(&now),
+                "Problem {} hits an obstacle at timestep {time}",
+                problem.number
+            );
+            let swapped = obstacles.iter().any(|o| {
+                let cell = |t| o.position(t).map(|(x, y)| xy_to_index(x, y, width));
+                now != next && cell(time) == Some(next) && cell(time + 1) == Some(now)
+            });
+            assert!(
+                !swapped,
+                "Problem {} swaps with an obstacle",
+                problem.number
+            );
+        }
+    }
+}
+
+#[test]
+fn yen_paths_are_loopless_and_ordered_lak104d() {
+    let context = common::full_lak104d_context_with("k-shortest", &["-k", "4"]);
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    for problem in context.problems().take(30) {
+        let routes = context.find_routes(problem);
+        assert!((routes[0].cost - problem.length.unwrap()).abs() < 0.001);
+
+        for (i, route) in routes.iter().enumerate() {
+            assert_valid_route(route, problem.start, problem.goal, &graph);
+            let mut nodes = route.path.clone();
+            nodes.sort_unstable();
+            nodes.dedup();
+            assert_eq!(nodes.len(), route.path.len(), "Route {i} has a loop");
+            assert!(routes[..i].iter().all(|r| r.path != route.path));
+            if i > 0 {
+                assert!(routes[i - 1].cost <= route.cost + 0.001);
+            }
+        }
+    }
+}
+
+#[test]
+fn penalty_routes_share_less_than_yen_lak104d() {
+    let yen = common::full_lak104d_context_with("k-shortest", &["-k", "3"]);
+    let penalty = common::full_lak104d_context_with("alternatives", &["-k", "3"]);
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+
+    let (mut yen_overlap, mut penalty_overlap) = (0.0, 0.0);
+    for problem in penalty.problems() {
+        let routes = penalty.find_routes(problem);
+        assert!((routes[0].cost - problem.length.unwrap()).abs() < 0.001);
+        for (i, route) in routes.iter().enumerate() {
+            assert_valid_route(route, problem.start, problem.goal, &graph);
+            assert!(routes[..i].iter().all(|r| r.path != route.path));
+        }
+
+        penalty_overlap += routes.iter().map(|r| r.overlap).sum::<f32>();
+        yen_overlap += yen
+            .find_routes(problem)
+            .iter()
+            .map(|r| r.overlap)
+            .sum::<f32>();
+    }
+    assert!(penalty_overlap < yen_overlap);
+}
+
+fn assert_valid_route(route: &Route, start: u32, goal: u32, graph: &Graph) {
+    assert!((0.0..=1.0).contains(&route.overlap));
+    assert_valid_path((&route.path, route.cost), start, goal, graph);
+}
+
+fn assert_valid_path((path, cost): (&Path, Cost), start: u32, goal: u32, graph: &Graph) {
+    assert_eq!(path[0], start);
+    assert_eq!(path[path.len() - 1], goal);
+    let length: f32 = path
+        .windows(2)
+        .map(|pair| {
+            graph
+                .neighbors(pair[0])
+                .find(|(n, _)| *n == pair[1])
+                .expect("Path jumps between cells that are not neighbors")
+                .1
+        })
+        .sum();
+    assert!((length - cost).abs() < 0.001);
+}
+
+#[test]
+fn nearest_target_matches_distance_fields_lak104d() {
+    let context =
+        common::full_lak104d_context_with("nearest", &["--points", "maps/lak104d.map.points"]);
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let points = Points::from_file(&PathBuf::from("maps/lak104d.map.points")).unwrap();
+    let targets = points.targets(graph.get_width());
+    let fields: Vec<_> = targets
+        .iter()
+        .map(|t| Dijkstra::distance_field(*t, &graph))
+        .collect();
+    let nearest = |node| {
+        fields
+            .iter()
+            .map(|f| f.get(node))
+            .fold(f32::INFINITY, f32::min)
+    };
+
+    for problem in context.problems() {
+        let (path, cost) = MultiGoal::new(problem.start, &targets, &graph)
+            .solve()
+            .unwrap();
+        assert_eq!(path[0], problem.start);
+        assert!(targets.contains(&path[path.len() - 1]));
+        assert!((cost - nearest(problem.start)).abs() < 0.001);
+    }
+
+    let sources = points.sources(graph.get_width());
+    for (source, result) in sources.iter().zip(context.nearest_targets()) {
+        let (_, cost) = result.unwrap();
+        assert!((cost - nearest(*source)).abs() < 0.001);
+    }
+}
+
+#[test]
+fn distance_matrix_matches_distance_fields_lak104d() {
+    let context =
+        common::full_lak104d_context_with("matrix", &["--points", "maps/lak104d.map.points"]);
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let matrix = context.distance_matrix();
+    assert_eq!(matrix.sources().len(), 2);
+    assert_eq!(matrix.targets().len(), 5);
+
+    for (i, source) in matrix.sources().iter().enumerate() {
+        let field = Dijkstra::distance_field(*source, &graph);
+        for (j, target) in matrix.targets().iter().enumerate() {
+            assert!((matrix.get(i, j) - field.get(*target)).abs() < 0.001);
+        }
+    }
+}
+
+#[test]
+fn greedy_and_beam_paths_are_valid_lak104d() {
+    let context = common::full_lak104d_context("greedy");
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    for problem in context.problems() {
+        let optimal = problem.length.unwrap();
+        let greedy = GreedySearch::new(problem.start, problem.goal, &graph).solve();
+        let beam = BeamSearch::new(problem.start, problem.goal, &graph, 64).solve();
+        for (path, cost) in [greedy.unwrap(), beam.unwrap()] {
+            assert_valid_path((&path, cost), problem.start, problem.goal, &graph);
+            assert!(cost >= optimal - 0.001);
+        }
+    }
+}
+
+#[test]
+fn narrow_beam_prunes_routes_lak104d() {
+    let narrow = common::full_lak104d_context_with("beam", &["--beam-width", "1"]);
+    let wide = common::full_lak104d_context_with("beam", &["--beam-width", "1000"]);
+
+    let failures = narrow.solve_all().iter().filter(|r| r.is_none()).count();
+    assert!(failures > 0);
+    assert!(wide.solve_all().iter().all(Option::is_some));
+}
+
+#[test]
+fn custom_bucket_layouts_keep_fringe_optimal_lak104d() {
+    for flags in [
+        &["--buckets", "16"][..],
+        &["--bucket-width", "0.5"],
+        &["--bucket-width", "3"],
+        &["--buckets", "6", "--bucket-width", "2"],
+    ] {
+        let context = common::full_lak104d_context_with("fringe", flags);
+        for problem in context.problems() {
+            let result = context.solve(*problem).unwrap();
+            assert!(
+                (result - problem.length.unwrap()).abs() < 0.001,
+                "Problem {} with {flags:?}: expected {}, got {result}",
+                problem.number,
+                problem.length.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn too_few_buckets_are_rejected() {
+    let graph = Graph::new(Map::new(PathBuf::from("maps/3x3.map")));
+    assert_eq!(BucketLayout::for_graph(&graph, 1.0).count(), 5);
+    assert!(BucketLayout::new(5, 1.0, &graph).is_ok());
+    assert!(BucketLayout::new(2, 1.0, &graph).is_err());
+
+    let error = BucketLayout::new(4, 1.0, &graph).unwrap_err();
+    assert!(
+        error.to_string().contains("need at least 5 buckets"),
+        "{error}"
+    );
+}
+
+#[test]
+fn open_lists_match_astar_lak104d() {
+    for queue in ["dary", "pairing", "radix", "buckets"] {
+        let context = common::full_lak104d_context_with("a-star", &["--open-list", queue]);
+        for problem in context.problems() {
+            let result = context.solve(*problem).unwrap();
+            assert!(
+                (result - problem.length.unwrap()).abs() < 0.001,
+                "Problem {} with {queue} open list: expected {}, got {result}",
+                problem.number,
+                problem.length.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn weighted_open_lists_stay_within_bound_lak104d() {
+    for queue in ["dary", "pairing", "buckets"] {
+        let context =
+            common::full_lak104d_context_with("a-star", &["--open-list", queue, "--weight", "2"]);
+        for problem in context.problems() {
+            let result = context.solve(*problem).unwrap();
+            assert!(
+                result <= 2.0 * problem.length.unwrap() + 0.001,
+                "Problem {} with {queue} open list exceeds bound",
+                problem.number,
+            );
+        }
+    }
+}
+
+#[test]
+fn radix_open_list_is_rejected_with_weight() {
+    let cli = Cli::parse_from([
+        "",
+        "--open-list",
+        "radix",
+        "--weight",
+        "2",
+        "a-star",
+        "maps/3x3.map",
+    ]);
+    assert!(cli.check().is_err());
+    let cli = Cli::parse_from(["", "--open-list", "radix", "a-star", "maps/3x3.map"]);
+    assert!(cli.check().is_ok());
+
+    let map = Map::new(PathBuf::from("maps/3x3.map"));
+    let printable = solver::Result::Time(Printable::new(&map));
+    let graph = Graph::new(map);
+    let problem = Problem::from_file(&PathBuf::from("maps/3x3.map.scen"), 1).unwrap();
+    let solver = Solver::new(Algorithm::AStar, printable, problem, &graph)
+        .with_open_list(Queue::Radix)
+        .with_weight(2.0);
+    assert!(solver.run().is_err());
+}
+
+#[test]
+fn tie_breaking_is_rejected_with_other_open_lists() {
+    let cli = Cli::parse_from([
+        "",
+        "--open-list",
+        "dary",
+        "--tie-break",
+        "high-g",
+        "a-star",
+        "maps/3x3.map",
+    ]);
+    assert!(cli.check().is_err());
+    let cli = Cli::parse_from(["", "--tie-break", "high-g", "a-star", "maps/3x3.map"]);
+    assert!(cli.check().is_ok());
+
+    let map = Map::new(PathBuf::from("maps/3x3.map"));
+    let printable = solver::Result::Time(Printable::new(&map));
+    let graph = Graph::new(map);
+    let problem = Problem::from_file(&PathBuf::from("maps/3x3.map.scen"), 1).unwrap();
+    let solver = Solver::new(Algorithm::AStar, printable, problem, &graph)
+        .with_open_list(Queue::Pairing)
+        .with_tie_break(TieBreak::HighG, 0);
+    assert!(solver.run().is_err());
+}
+
+#[test]
+fn tie_breaking_keeps_solutions_optimal_lak104d() {
+    for mode in ["a-star", "fringe"] {
+        for policy in ["high-g", "low-g", "lifo", "fifo", "random"] {
+            let context = common::full_lak104d_context_with(mode, &["--tie-break", policy]);
+            for problem in context.problems() {
+                let result = context.solve(*problem).unwrap();
+                assert!(
+                    (result - problem.length.unwrap()).abs() < 0.001,
+                    "Problem {} with {mode} and {policy} ties: expected {}, got {result}",
+                    problem.number,
+                    problem.length.unwrap(),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn high_g_ties_expand_less_on_open_ground() {
+    let map = Map::new(PathBuf::from("maps/dia30x30.map"));
+    let graph = Graph::new(map);
+    let (start, goal) = (xy_to_index(0, 0, 30), xy_to_index(20, 2, 30));
+    let expansions = |tie_break| {
+        let mut astar =
+            AStar::<TiedFrontier>::with_open_list(start, goal, &graph).with_tie_break(tie_break, 1);
+        while let State::Processing(_) = astar.progress() {}
+        astar.expansions
+    };
+
+    assert!(expansions(TieBreak::HighG) < expansions(TieBreak::LowG));
+}
+
+#[test]
+fn reused_workspaces_keep_solutions_optimal_lak104d() {
+    let context = common::full_lak104d_context("a-star");
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let landmarks = Arc::new(Landmarks::new(&graph, 4, Strategy::Avoid));
+    let layout = BucketLayout::new(8, 0.5, &graph).unwrap();
+    let alt = |goal| Heuristic::new(goal, graph.get_width()).with_landmarks(landmarks.clone());
+
+    // Same workspaces are shared by every problem, and the layout changes between searches
+    let mut workspace = Workspace::default();
+    let mut custom = Workspace::default().with_buckets(layout);
+    for problem in context.problems() {
+        let (start, goal) = (problem.start, problem.goal);
+        let results = [
+            AStar::in_workspace(start, goal, &graph, &mut workspace).solve_in(&mut workspace),
+            AStar::in_workspace(start, goal, &graph, &mut workspace)
+                .with_landmarks(landmarks.clone())
+                .solve_in(&mut workspace),
+            AStar::<PairingHeap>::with_open_list_in(start, goal, &graph, &mut workspace)
+                .solve_in(&mut workspace),
+            FringeSearch::in_workspace(start, goal, &graph, &mut workspace)
+                .solve_in(&mut workspace),
+            FringeSearch::in_workspace(start, goal, &graph, &mut custom).solve_in(&mut workspace),
+            FringeSearch::with_heuristic_in(start, goal, &graph, alt(goal), &mut workspace)
+                .solve_in(&mut custom),
+        ];
+        for (i, result) in results.into_iter().enumerate() {
+            let (_, length) = result.solution().unwrap();
+            assert!(
+                (length - problem.length.unwrap()).abs() < 0.001,
+                "Problem {} with search {i}: expected {}, got {length}",
+                problem.number,
+                problem.length.unwrap(),
+            );
+        }
+    }
+}
+
+#[test]
+fn limits_stop_searches_lak104d() {
+    let context = common::full_lak104d_context("a-star");
+    let graph = Graph::new(Map::new(PathBuf::from("maps/lak104d.map")));
+    let cancel = Arc::new(AtomicBool::new(true));
+    let cases = [
+        (Limits::default().with_expansions(10), Abort::Expansions),
+        (
+            Limits::default().with_deadline(Instant::now()),
+            Abort::Deadline,
+        ),
+        (Limits::default().with_cancel(cancel), Abort::Cancelled),
+    ];
+
+    // Short paths can be found within ten expansions
+    for problem in context.problems().filter(|p| p.length > Some(30.0)) {
+        let (start, goal) = (problem.start, problem.goal);
+        for (limits, reason) in &cases {
+            let states = [
+                AStar::new(start, goal, &graph)
+                    .with_limits(limits.clone())
+                    .solve_limited(),
+                FringeSearch::new(start, goal, &graph)
+                    .with_limits(limits.clone())
+                    .solve_limited(),
+                BidirectionalAStar::new(start, goal, &graph)
+                    .with_limits(limits.clone())
+                    .solve_limited(),
+                Ida::new(start, goal, &graph)
+                    .with_limits(limits.clone())
+                    .solve_limited(),
+                Dijkstra::new(start, goal, &graph)
+                    .with_limits(limits.clone())
+                    .solve_limited(),
+            ];
+            for (i, state) in states.into_iter().enumerate() {
+                assert!(
+                    matches!(state, State::Aborted(r) if r == *reason),
+                    "Problem {} with search {i} was not stopped by {reason}",
+                    problem.number
+                );
+            }
+        }
+    }
+
+    // Conflict tree can be solved within ten expansions, but the clock and the flag are read at once
+    let agents: Vec<_> = context
+        .problems()
+        .map(|p| (p.start, p.goal))
+        .take(4)
+        .collect();
+    for (limits, reason) in &cases[1..] {
+        let state = Cbs::new(agents.clone(), &graph)
+            .with_limits(limits.clone())
+            .solve_limited();
+        assert!(matches!(state, State::Aborted(r) if r == *reason));
+    }
+}
+
+#[test]
+fn limits_far_away_keep_solutions_optimal_lak104d() {
+    let context = common::full_lak104d_context_with("fringe", &["--max-expansions", "100000"]);
+    for problem in context.problems() {
+        let result = context.solve(*problem).unwrap();
+        assert!((result - problem.length.unwrap()).abs() < 0.001);
+    }
+}
+
+#[test]
+fn batch_records_stopped_searches_as_timeouts_lak104d() {
+    let context =
+        common::full_lak104d_context_with("a-star", &["--max-expansions", "10", "-j", "2"]);
+    let results = context.solve_all();
+    for (problem, result) in context.problems().zip(&results) {
+        if problem.length > Some(30.0) {
+            assert!(
+                result.is_none(),
+                "Problem {} was not stopped",
+                problem.number
+            );
+        }
+    }
+
+    // Timeouts are counted instead of failing the run
+    let mut context = common::full_lak104d_context("fringe");
+    context.cancel_flag().store(true, Ordering::Relaxed);
+    assert!(context.solve_full().is_ok());
+    assert!(context.solve_all().iter().all(Option::is_none));
+}
+
+#[test]
+fn problems_without_a_path_are_counted_as_failures() {
+    // The wall in the middle splits the map in two
+    let directory =
+        std::env::temp_dir().join(format!("fringe-vs-astar-{}-split", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let map = directory.join("split.map");
+    std::fs::write(&map, "type octile\nheight 3\nwidth 3\nmap\n.@.\n.@.\n.@.\n").unwrap();
+    std::fs::write(
+        directory.join("split.map.scen"),
+        "version 1\n0\tsplit.map\t3\t3\t0\t0\t0\t2\t2\n0\tsplit.map\t3\t3\t0\t0\t2\t0\t2\n",
+    )
+    .unwrap();
+
+    for mode in ["a-star", "fringe", "compare"] {
+        let mut context = common::full_context(mode, map.to_str().unwrap());
+        assert!(context.solve_full().is_ok(), "{mode} failed");
+        let results = context.solve_all();
+        assert!(results[0].is_some(), "{mode} found no path");
+        assert!(results[1].is_none(), "{mode} found a path through the wall");
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}